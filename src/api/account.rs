@@ -1,3 +1,4 @@
+use self::account_fields::Team;
 use super::{HasResponse, HasValue};
 use crate::method::Get;
 use crate::request::AccountRequest;
@@ -36,6 +37,25 @@ pub struct Account {
     /// A human-readable message giving more details about the status of the
     /// account.
     status_message: String,
+
+    /// The team the token belongs to. Absent for personal accounts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    team: Option<Team>,
+}
+
+/// Fields which exists inside the account.
+pub mod account_fields {
+    use serde::Deserialize;
+    use serde::Serialize;
+
+    /// The team context of an account.
+    #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct Team {
+        /// The unique universal identifier for the team.
+        pub uuid: String,
+        /// The name of the team.
+        pub name: String,
+    }
 }
 
 impl Account {
@@ -1,9 +1,10 @@
 use super::{ApiLinks, ApiMeta};
 use super::{HasPagination, HasResponse, HasValue};
+use crate::error::Error;
 use crate::method::{Get, List};
 use crate::request::ActionRequest;
 use crate::request::Request;
-use crate::{ROOT_URL, STATIC_URL_ERROR};
+use crate::{DigitalOcean, ROOT_URL, STATIC_URL_ERROR};
 use chrono::{DateTime, Utc};
 use getset::{Getters, Setters};
 use serde::Deserialize;
@@ -37,6 +38,14 @@ pub struct Action {
     /// "in-progress", "completed", or "errored".
     status: String,
 
+    /// This is the type of action that the object represents. For example,
+    /// this could be "attach" to represent the state of a volume attach
+    /// action.
+    ///
+    /// *Note:* Since `type` is a keyword in Rust `kind` is used instead.
+    #[serde(rename = "type")]
+    kind: String,
+
     /// The type of action that the event is executing (reboot, power_off,
     /// etc.).
     started_at: DateTime<Utc>,
@@ -83,6 +92,49 @@ impl Action {
     }
 }
 
+impl ActionRequest<List, Vec<Action>> {
+    /// Retrieve a single action from an action history. This is the one
+    /// lookup for every list of actions: `Action::list()` and the `actions()`
+    /// history of a Droplet, Floating IP, image or volume.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#retrieve-an-existing-action)
+    pub fn get(mut self, id: usize) -> ActionRequest<Get, Action> {
+        self.url_mut()
+            .path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(&id.to_string());
+
+        self.transmute()
+    }
+
+    /// Fetch the actions started after `after`, newest first.
+    ///
+    /// The API lists actions newest first, so pages are only fetched until
+    /// one reaches an action started at or before `after`. This makes it
+    /// cheap to keep an audit log in sync by passing the `started_at` of the
+    /// newest action already seen.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-all-actions)
+    pub async fn since(
+        self,
+        instance: &DigitalOcean,
+        after: DateTime<Utc>,
+    ) -> Result<Vec<Action>, Error> {
+        let mut cursor = self.cursor(instance);
+        let mut actions = Vec::new();
+
+        while let Some(page) = cursor.next_page().await? {
+            for action in page {
+                if action.started_at <= after {
+                    return Ok(actions);
+                }
+                actions.push(action);
+            }
+        }
+        Ok(actions)
+    }
+}
+
 /// Response type returned from Digital Ocean.
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ActionResponse {
@@ -102,10 +154,12 @@ impl HasResponse for Action {
 }
 
 /// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Getters)]
 pub struct ActionListResponse {
     actions: Vec<Action>,
+    #[get = "pub"]
     links: ApiLinks,
+    #[get = "pub"]
     meta: ApiMeta,
 }
 
@@ -126,3 +180,12 @@ impl HasValue for ActionListResponse {
         self.actions
     }
 }
+
+impl IntoIterator for ActionListResponse {
+    type Item = Action;
+    type IntoIter = std::vec::IntoIter<Action>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.actions.into_iter()
+    }
+}
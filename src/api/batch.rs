@@ -0,0 +1,38 @@
+use crate::error::Error;
+
+/// The outcome of a helper executing several requests, where some may fail
+/// without stopping the others.
+///
+/// `I` is the input of a request, kept so that only the failures need to be
+/// retried.
+#[derive(Debug)]
+pub struct BatchResult<T, I = T> {
+    /// The items which succeeded.
+    pub succeeded: Vec<T>,
+    /// The input of every failed request, with its error.
+    pub failed: Vec<(I, Error)>,
+}
+
+impl<T, I> BatchResult<T, I> {
+    /// Whether every request succeeded.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    /// The items which succeeded, or the first error if any request failed.
+    pub fn into_result(self) -> Result<Vec<T>, Error> {
+        match self.failed.into_iter().next() {
+            Some((_, error)) => Err(error),
+            None => Ok(self.succeeded),
+        }
+    }
+}
+
+impl<T, I> Default for BatchResult<T, I> {
+    fn default() -> Self {
+        BatchResult {
+            succeeded: Vec::new(),
+            failed: Vec::new(),
+        }
+    }
+}
@@ -1,14 +1,16 @@
 use super::{ApiLinks, ApiMeta};
 use super::{HasPagination, HasResponse, HasValue};
+use crate::error::Error;
 use crate::method::{Create, Delete, Get, List};
 use crate::request::CertificateRequest;
-use crate::request::Request;
-use crate::{ROOT_URL, STATIC_URL_ERROR};
-use chrono::{DateTime, Utc};
+use crate::request::{Executable, Request};
+use crate::{DigitalOcean, ROOT_URL, STATIC_URL_ERROR};
+use chrono::{DateTime, TimeDelta, Utc};
 use getset::{Getters, Setters};
 use serde::Deserialize;
 use serde::Serialize;
-use std::fmt::Display;
+use std::fmt::{self, Display};
+use std::time::Duration;
 use url::Url;
 
 const CERTIFICATES_SEGMENT: &str = "certificates";
@@ -38,6 +40,30 @@ pub struct Certificate {
     /// A time value given in ISO8601 combined date and time format that
     /// represents when the certificate was created.
     created_at: DateTime<Utc>,
+
+    /// The fully qualified domain names the certificate was issued for.
+    #[serde(default)]
+    dns_names: Vec<String>,
+
+    /// Whether the certificate was uploaded or is managed by DigitalOcean
+    /// through Let's Encrypt.
+    ///
+    /// *Note:* Since `type` is a keyword in Rust `kind` is used instead.
+    #[serde(rename = "type", default)]
+    kind: Option<CertificateType>,
+}
+
+/// The `type` of a certificate.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CertificateType {
+    /// A certificate uploaded by the user.
+    Custom,
+    /// A certificate issued and renewed by DigitalOcean through Let's Encrypt.
+    LetsEncrypt,
+    /// A type this crate does not know about yet.
+    #[serde(other)]
+    Unknown,
 }
 
 impl Certificate {
@@ -66,6 +92,33 @@ impl Certificate {
         req
     }
 
+    /// Have DigitalOcean issue a certificate for `dns_names` through Let's
+    /// Encrypt. Every domain must be managed by DigitalOcean.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/certificates_create)
+    pub fn create_lets_encrypt<S>(
+        name: S,
+        dns_names: Vec<S>,
+    ) -> CertificateRequest<Create, Certificate>
+    where
+        S: AsRef<str> + Serialize + Display,
+    {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(CERTIFICATES_SEGMENT);
+
+        let mut req = Request::new(url);
+
+        req.set_body(json!({
+            "name": name,
+            "type": CertificateType::LetsEncrypt,
+            "dns_names": dns_names,
+        }));
+
+        req
+    }
+
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-all-certificates)
     pub fn list() -> CertificateRequest<List, Vec<Certificate>> {
         let mut url = ROOT_URL.clone();
@@ -110,6 +163,60 @@ impl CertificateRequest<Create, Certificate> {
     }
 }
 
+impl CertificateRequest<Get, Certificate> {
+    /// Issue a new Let's Encrypt certificate for the same domains when this
+    /// one expires within `within` from now, returning it. Returns `None`
+    /// when the certificate is not due yet.
+    ///
+    /// The API cannot renew a certificate, so a new one is created, named
+    /// after the old one with the time of renewal appended. The old
+    /// certificate is left in place, to be deleted once nothing uses it
+    /// anymore.
+    ///
+    /// Returns `Error::InvalidRequest` for certificates which are not managed
+    /// through Let's Encrypt.
+    ///
+    /// **Note:** DigitalOcean renews its Let's Encrypt certificates on its
+    /// own, this is for those wanting to control when it happens.
+    pub async fn renew(
+        self,
+        instance: &DigitalOcean,
+        within: Duration,
+    ) -> Result<Option<Certificate>, Error> {
+        let current = self.execute(instance).await?;
+
+        if current.kind != Some(CertificateType::LetsEncrypt) {
+            return Err(Error::InvalidRequest(format!(
+                "certificate {} is not a Let's Encrypt certificate",
+                current.name
+            )));
+        }
+
+        let now = Utc::now();
+        let deadline = TimeDelta::from_std(within)
+            .ok()
+            .and_then(|within| now.checked_add_signed(within))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+        if current.not_after > deadline {
+            return Ok(None);
+        }
+
+        let name = format!("{}-{}", current.name, now.format("%Y%m%d%H%M%S"));
+        let dns_names = current.dns_names.iter().map(String::as_str).collect();
+        let renewed = Certificate::create_lets_encrypt(name.as_str(), dns_names)
+            .execute(instance)
+            .await?;
+
+        Ok(Some(renewed))
+    }
+}
+
+impl fmt::Display for Certificate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "certificate {:?} ({})", self.name, self.id)
+    }
+}
+
 /// Response type returned from Digital Ocean.
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct CertificateResponse {
@@ -129,10 +236,12 @@ impl HasValue for CertificateResponse {
 }
 
 /// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Getters)]
 pub struct CertificateListResponse {
     certificates: Vec<Certificate>,
+    #[get = "pub"]
     links: ApiLinks,
+    #[get = "pub"]
     meta: ApiMeta,
 }
 
@@ -153,3 +262,12 @@ impl HasValue for CertificateListResponse {
         self.certificates
     }
 }
+
+impl IntoIterator for CertificateListResponse {
+    type Item = Certificate;
+    type IntoIter = std::vec::IntoIter<Certificate>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.certificates.into_iter()
+    }
+}
@@ -0,0 +1,68 @@
+use super::{Droplet, LoadBalancer, Size, Volume};
+use crate::error::Error;
+use crate::request::Executable;
+use crate::DigitalOcean;
+use futures_util::future::try_join4;
+use log::warn;
+use std::collections::HashMap;
+
+/// The monthly price of a GiB of Block Storage, in US dollars, assumed by
+/// `estimated_monthly_cost()`. The API does not expose it.
+pub const VOLUME_PRICE_PER_GIB: f64 = 0.10;
+
+/// The monthly price of a single node Load Balancer, in US dollars, assumed
+/// by `estimated_monthly_cost()`. The API does not expose it.
+pub const LOAD_BALANCER_PRICE: f64 = 12.0;
+
+impl DigitalOcean {
+    /// Estimate the monthly cost of the Droplets, volumes and Load Balancers
+    /// of the account, in US dollars.
+    ///
+    /// Droplets cost the `price_monthly` of their size, volumes
+    /// [`VOLUME_PRICE_PER_GIB`](api/constant.VOLUME_PRICE_PER_GIB.html) per
+    /// GiB and Load Balancers
+    /// [`LOAD_BALANCER_PRICE`](api/constant.LOAD_BALANCER_PRICE.html) each.
+    ///
+    /// **Note:** This is an estimate. Snapshots, backups, bandwidth overages,
+    /// Load Balancers of more than one node and any discount are not
+    /// accounted for, and Droplets of an unknown size count as free.
+    pub async fn estimated_monthly_cost(&self) -> Result<f64, Error> {
+        let (droplets, volumes, load_balancers, sizes) = try_join4(
+            Droplet::list().execute(self),
+            Volume::list().execute(self),
+            LoadBalancer::list().execute(self),
+            Size::list().execute(self),
+        )
+        .await?;
+
+        let prices: HashMap<&str, f64> = sizes
+            .iter()
+            .map(|size| (size.slug().as_str(), *size.price_monthly()))
+            .collect();
+
+        let droplets: f64 = droplets
+            .iter()
+            .map(|droplet| match droplet.size() {
+                Some(size) => *size.price_monthly(),
+                None => {
+                    let price = droplet
+                        .size_slug()
+                        .and_then(|slug| prices.get(slug).copied());
+                    if price.is_none() {
+                        warn!("No price for the size of Droplet {}", droplet.id());
+                    }
+                    price.unwrap_or_default()
+                }
+            })
+            .sum();
+
+        let volumes: f64 = volumes
+            .iter()
+            .map(|volume| f64::from(*volume.size_gigabytes()) * VOLUME_PRICE_PER_GIB)
+            .sum();
+
+        let load_balancers = load_balancers.len() as f64 * LOAD_BALANCER_PRICE;
+
+        Ok(droplets + volumes + load_balancers)
+    }
+}
@@ -1,13 +1,20 @@
 use super::{HasResponse, HasValue};
-use crate::method::Create;
+use super::{ImageStatus, ImageType};
+use crate::error::Error;
+use crate::method::{Create, Get};
 use crate::request::CustomImageRequest;
-use crate::request::Request;
-use crate::{ROOT_URL, STATIC_URL_ERROR};
+use crate::request::{Executable, Request};
+use crate::wait::poll;
+use crate::{DigitalOcean, ROOT_URL, STATIC_URL_ERROR};
 use chrono::{DateTime, Utc};
 use getset::{Getters, Setters};
 use serde::Deserialize;
 use serde::Serialize;
 use std::fmt::Display;
+use std::future::{pending, Future};
+use std::time::Duration;
+#[cfg(feature = "cancellation")]
+use tokio_util::sync::CancellationToken;
 
 const IMAGES_SEGMENT: &str = "images";
 
@@ -18,6 +25,7 @@ const IMAGES_SEGMENT: &str = "images";
 ///
 /// [Digital Ocean Documentation.](https://www.digitalocean.com/docs/images/custom-images/)
 #[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
 pub struct CustomImage {
     /// A unique number that can be used to identify and reference a specific
     /// image.
@@ -33,7 +41,7 @@ pub struct CustomImage {
     ///
     /// *Note:* Since `type` is a keyword in Rust `kind` is used instead.
     #[serde(rename = "type")]
-    kind: String,
+    kind: ImageType,
     // 'type' is reserved in Rust.
     /// This attribute describes the base distribution used for this image.
     distribution: String,
@@ -54,7 +62,7 @@ pub struct CustomImage {
     description: String,
 
     /// The status of the image
-    status: String,
+    status: ImageStatus,
 }
 
 impl CustomImage {
@@ -83,6 +91,74 @@ impl CustomImage {
         }));
         req
     }
+
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#retrieve-an-existing-image-by-id)
+    pub fn get(id: usize) -> CustomImageRequest<Get, CustomImage> {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(IMAGES_SEGMENT)
+            .push(&id.to_string());
+
+        Request::new(url)
+    }
+}
+
+impl CustomImageRequest<Create, CustomImage> {
+    /// Start the import, then fetch the image every `interval` until it is
+    /// available and return it.
+    ///
+    /// Returns `Error::Failed` if the import ends in any other state and
+    /// `Error::Timeout` if it is still pending after `timeout`.
+    pub async fn create_and_wait(
+        self,
+        instance: &DigitalOcean,
+        interval: Duration,
+        timeout: Duration,
+    ) -> Result<CustomImage, Error> {
+        self.create_and_wait_or(instance, interval, timeout, pending())
+            .await
+    }
+
+    /// Like [`create_and_wait()`](#method.create_and_wait), but returns
+    /// `Error::Cancelled` as soon as `token` is cancelled.
+    ///
+    /// **Note:** Cancelling only stops the wait, the import carries on.
+    #[cfg(feature = "cancellation")]
+    pub async fn create_and_wait_cancellable(
+        self,
+        instance: &DigitalOcean,
+        interval: Duration,
+        timeout: Duration,
+        token: &CancellationToken,
+    ) -> Result<CustomImage, Error> {
+        self.create_and_wait_or(instance, interval, timeout, token.cancelled())
+            .await
+    }
+
+    async fn create_and_wait_or<C: Future<Output = ()>>(
+        self,
+        instance: &DigitalOcean,
+        interval: Duration,
+        timeout: Duration,
+        cancelled: C,
+    ) -> Result<CustomImage, Error> {
+        let image = self.execute(instance).await?;
+        let what = format!("custom image {}", image.id);
+
+        poll(&what, interval, timeout, cancelled, || async {
+            let image = CustomImage::get(image.id).execute(instance).await?;
+            match image.status {
+                ImageStatus::Available => Ok(Some(image)),
+                ImageStatus::New | ImageStatus::Pending => Ok(None),
+                _ => Err(Error::Failed(format!(
+                    "Import of custom image {}",
+                    image.id
+                ))),
+            }
+        })
+        .await
+    }
 }
 
 /// Response type returned from Digital Ocean.
@@ -1,13 +1,14 @@
 use super::{ApiLinks, ApiMeta};
 use super::{HasPagination, HasResponse, HasValue};
+use crate::error::Error;
 use crate::method::{Create, Delete, Get, List};
 use crate::request::DomainRequest;
-use crate::request::Request;
-use crate::{ROOT_URL, STATIC_URL_ERROR};
+use crate::request::{Executable, Request};
+use crate::{DigitalOcean, ROOT_URL, STATIC_URL_ERROR};
 use getset::{Getters, Setters};
 use serde::Deserialize;
 use serde::Serialize;
-use std::fmt::Display;
+use std::fmt::{self, Display};
 use std::net::IpAddr;
 use url::Url;
 
@@ -90,6 +91,29 @@ impl Domain {
     }
 }
 
+impl DomainRequest<Get, Domain> {
+    /// Fetch the domain and return its zone file in BIND format, for example
+    /// to back it up or migrate it elsewhere.
+    ///
+    /// Returns `Error::EmptyZoneFile` if the domain has no zone file.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#retrieve-an-existing-domain)
+    pub async fn export_zone(self, instance: &DigitalOcean) -> Result<String, Error> {
+        let domain = self.execute(instance).await?;
+
+        match domain.zone_file {
+            Some(zone_file) if !zone_file.trim().is_empty() => Ok(zone_file),
+            _ => Err(Error::EmptyZoneFile(domain.name)),
+        }
+    }
+}
+
+impl fmt::Display for Domain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "domain {:?}", self.name)
+    }
+}
+
 /// Response type returned from Digital Ocean.
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct DomainResponse {
@@ -109,10 +133,12 @@ impl HasValue for DomainResponse {
 }
 
 /// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Getters)]
 pub struct DomainListResponse {
     domains: Vec<Domain>,
+    #[get = "pub"]
     links: ApiLinks,
+    #[get = "pub"]
     meta: ApiMeta,
 }
 
@@ -133,3 +159,12 @@ impl HasValue for DomainListResponse {
         self.domains
     }
 }
+
+impl IntoIterator for DomainListResponse {
+    type Item = Domain;
+    type IntoIter = std::vec::IntoIter<Domain>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.domains.into_iter()
+    }
+}
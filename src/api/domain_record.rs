@@ -52,6 +52,170 @@ pub struct DomainRecord {
 
     /// The weight for SRV records.
     weight: Option<usize>,
+
+    /// An unsigned integer between 0-255 used for CAA records.
+    flags: Option<usize>,
+
+    /// The parameter tag for CAA records. Valid values are "issue",
+    /// "issuewild", or "iodef".
+    tag: Option<String>,
+}
+
+/// The SOA record of a domain is managed by DigitalOcean, it can be neither
+/// created nor deleted.
+const SOA: &str = "SOA";
+
+/// DigitalOcean creates NS records at the apex of every domain, pointing at
+/// these nameservers. Deleting them breaks the zone.
+const MANAGED_NAMESERVERS: [&str; 3] = [
+    "ns1.digitalocean.com",
+    "ns2.digitalocean.com",
+    "ns3.digitalocean.com",
+];
+
+impl DomainRecord {
+    /// Whether DigitalOcean manages the record: the SOA record and the NS
+    /// records at the apex pointing at DigitalOcean's nameservers.
+    pub fn is_managed(&self) -> bool {
+        self.kind == SOA
+            || (self.kind == "NS"
+                && self.name == "@"
+                && MANAGED_NAMESERVERS.contains(&self.data.trim_end_matches('.')))
+    }
+}
+
+/// Equal when every field but the `id` is, so that a record compares equal to
+/// the same record in another zone or fetched at another time.
+impl PartialEq for DomainRecord {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.name == other.name
+            && self.data == other.data
+            && self.priority == other.priority
+            && self.port == other.port
+            && self.ttl == other.ttl
+            && self.weight == other.weight
+            && self.flags == other.flags
+            && self.tag == other.tag
+    }
+}
+
+impl Eq for DomainRecord {}
+
+impl PartialEq<NewDomainRecord> for DomainRecord {
+    fn eq(&self, other: &NewDomainRecord) -> bool {
+        self.kind == other.kind
+            && self.name == other.name
+            && self.data == other.data
+            && self.priority == other.priority
+            && self.port == other.port
+            && self.ttl == other.ttl
+            && self.weight == other.weight
+            && self.flags == other.flags
+            && self.tag == other.tag
+    }
+}
+
+/// A domain record which does not exist yet, such as a record wanted by a
+/// declarative configuration. It has every field of a
+/// [`DomainRecord`](struct.DomainRecord.html) but the `id`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct NewDomainRecord {
+    /// *Note:* Since `type` is a keyword in Rust `kind` is used instead.
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub name: String,
+    pub data: String,
+    pub priority: Option<usize>,
+    pub port: Option<usize>,
+    pub ttl: usize,
+    pub weight: Option<usize>,
+    pub flags: Option<usize>,
+    pub tag: Option<String>,
+}
+
+impl From<&DomainRecord> for NewDomainRecord {
+    fn from(record: &DomainRecord) -> Self {
+        NewDomainRecord {
+            kind: record.kind.clone(),
+            name: record.name.clone(),
+            data: record.data.clone(),
+            priority: record.priority,
+            port: record.port,
+            ttl: record.ttl,
+            weight: record.weight,
+            flags: record.flags,
+            tag: record.tag.clone(),
+        }
+    }
+}
+
+/// The changes turning the records of a domain into the desired ones, as
+/// computed by [`diff_records()`](fn.diff_records.html).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecordDiff {
+    /// The desired records which do not exist yet.
+    pub to_create: Vec<NewDomainRecord>,
+    /// The id of each record to update, with what it should become.
+    pub to_update: Vec<(usize, NewDomainRecord)>,
+    /// The records which are not desired.
+    pub to_delete: Vec<DomainRecord>,
+}
+
+impl RecordDiff {
+    /// Whether the records already are as desired.
+    pub fn is_empty(&self) -> bool {
+        self.to_create.is_empty() && self.to_update.is_empty() && self.to_delete.is_empty()
+    }
+}
+
+/// Compute the changes turning the `actual` records of a domain into the
+/// `desired` ones, ignoring ids.
+///
+/// Records equal to a desired one are left alone. A remaining desired record
+/// replaces, by updating it, a remaining record with the same type and name,
+/// such as an A record pointing elsewhere. Every other desired record is
+/// created and every other record deleted, except for the records managed by
+/// DigitalOcean, see [`is_managed()`](struct.DomainRecord.html#method.is_managed),
+/// which are never changed. Desired records matching a managed one are
+/// already present.
+pub fn diff_records(desired: Vec<NewDomainRecord>, actual: Vec<DomainRecord>) -> RecordDiff {
+    let (managed, mut actual): (Vec<DomainRecord>, Vec<DomainRecord>) =
+        actual.into_iter().partition(DomainRecord::is_managed);
+
+    let mut changed = Vec::new();
+    for record in desired {
+        let is_present = managed.iter().any(|existing| {
+            existing.kind == record.kind
+                && existing.name == record.name
+                && existing.data == record.data
+        });
+        if is_present {
+            continue;
+        }
+
+        match actual.iter().position(|existing| *existing == record) {
+            Some(i) => {
+                actual.remove(i);
+            }
+            None => changed.push(record),
+        }
+    }
+
+    let mut diff = RecordDiff::default();
+    for record in changed {
+        let same_name = actual
+            .iter()
+            .position(|existing| existing.kind == record.kind && existing.name == record.name);
+
+        match same_name {
+            Some(i) => diff.to_update.push((actual.remove(i).id, record)),
+            None => diff.to_create.push(record),
+        }
+    }
+    diff.to_delete = actual;
+
+    diff
 }
 
 impl DomainRequest<Get, Domain> {
@@ -150,6 +314,23 @@ impl DomainRecordRequest<Create, DomainRecord> {
         self.body_mut()["weight"] = json!(val);
         self
     }
+
+    /// An unsigned integer between 0-255 used for CAA records.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#domain-records)
+    pub fn flags(mut self, val: Option<usize>) -> Self {
+        self.body_mut()["flags"] = json!(val);
+        self
+    }
+
+    /// The parameter tag for CAA records. Valid values are "issue",
+    /// "issuewild", or "iodef".
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#domain-records)
+    pub fn caa_tag<S: AsRef<str> + Display + Serialize>(mut self, val: S) -> Self {
+        self.body_mut()["tag"] = json!(val);
+        self
+    }
 }
 
 impl DomainRecordRequest<Update, DomainRecord> {
@@ -211,6 +392,23 @@ impl DomainRecordRequest<Update, DomainRecord> {
         self.body_mut()["weight"] = json!(val);
         self
     }
+
+    /// An unsigned integer between 0-255 used for CAA records.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#domain-records)
+    pub fn flags(mut self, val: Option<usize>) -> Self {
+        self.body_mut()["flags"] = json!(val);
+        self
+    }
+
+    /// The parameter tag for CAA records. Valid values are "issue",
+    /// "issuewild", or "iodef".
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#domain-records)
+    pub fn caa_tag<S: AsRef<str> + Display + Serialize>(mut self, val: S) -> Self {
+        self.body_mut()["tag"] = json!(val);
+        self
+    }
 }
 
 /// Response type returned from Digital Ocean.
@@ -232,10 +430,12 @@ impl HasResponse for DomainRecord {
 }
 
 /// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Getters)]
 pub struct DomainRecordListResponse {
     domain_records: Vec<DomainRecord>,
+    #[get = "pub"]
     links: ApiLinks,
+    #[get = "pub"]
     meta: ApiMeta,
 }
 
@@ -256,3 +456,12 @@ impl HasValue for DomainRecordListResponse {
         self.domain_records
     }
 }
+
+impl IntoIterator for DomainRecordListResponse {
+    type Item = DomainRecord;
+    type IntoIter = std::vec::IntoIter<DomainRecord>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.domain_records.into_iter()
+    }
+}
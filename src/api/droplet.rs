@@ -1,17 +1,25 @@
-use self::droplet_fields::{Kernel, Networks, NextBackupWindow};
+use self::droplet_fields::{
+    AssociatedResources, BackupPolicy, DestroyAssociated, DropletLinks, Kernel, Networks,
+    NextBackupWindow,
+};
 use super::snapshot::Snapshot;
-use super::{ApiLinks, ApiMeta};
+use super::{Action, Firewall, Image, ImageSource, Region, Size, SshKeyRef, Urn};
+use super::{ApiLinks, ApiMeta, BatchResult};
 use super::{HasPagination, HasResponse, HasValue};
-use super::{Image, Region, Size};
+use crate::error::Error;
 use crate::method::{Create, Delete, Get, List};
-use crate::request::Request;
-use crate::request::{DropletRequest, SnapshotRequest};
-use crate::{ROOT_URL, STATIC_URL_ERROR};
+use crate::request::{DropletRequest, FirewallRequest, SnapshotRequest};
+use crate::request::{Executable, Request};
+use crate::{DigitalOcean, ROOT_URL, STATIC_URL_ERROR};
 use chrono::{DateTime, Utc};
+use futures_util::stream::{self, StreamExt};
 use getset::{Getters, Setters};
+use log::warn;
 use serde::Deserialize;
 use serde::Serialize;
-use std::fmt::Display;
+use serde_json::Value;
+use std::fmt::{self, Display};
+use std::net::{Ipv4Addr, Ipv6Addr};
 use url::Url;
 
 const DROPLETS_SEGMENT: &str = "droplets";
@@ -20,6 +28,11 @@ const DROPLET_NEIGHBORS_SEGMENT: &str = "droplet_neighbors";
 const NEIGHBORS_SEGMENT: &str = "neighbors";
 const SNAPSHOTS_SEGMENT: &str = "snapshots";
 const BACKUPS_SEGMENT: &str = "backups";
+const FIREWALLS_SEGMENT: &str = "firewalls";
+const KERNELS_SEGMENT: &str = "kernels";
+const DESTROY_WITH_ASSOCIATED_SEGMENT: &str = "destroy_with_associated_resources";
+const SELECTIVE_SEGMENT: &str = "selective";
+const DANGEROUS_SEGMENT: &str = "dangerous";
 
 /// A Droplet is a DigitalOcean virtual machine. By sending requests to the
 /// Droplet endpoint, you can list, create, or delete Droplets.
@@ -88,10 +101,13 @@ pub struct Droplet {
     /// entire size object will be returned. Note that the disk volume of a
     /// Droplet may not match the size's disk due to Droplet resize actions.
     /// The disk attribute on the Droplet should always be referenced.
-    size: Size,
+    #[serde(default)]
+    size: Option<Size>,
 
     /// The unique slug identifier for the size of this Droplet.
-    size_slug: String,
+    #[serde(default)]
+    #[getset(skip)]
+    size_slug: Option<String>,
 
     /// The details of the network that are configured for the Droplet
     /// instance. This is an object that contains keys for IPv4 and IPv6.
@@ -118,7 +134,30 @@ pub struct Droplet {
     volume_ids: Vec<String>,
 }
 
+/// A reduced view of a Droplet, holding only what is needed to identify it.
+///
+/// Every other field in the response is skipped while deserializing, which
+/// makes listing a large number of Droplets considerably cheaper. Retrieved
+/// with [`Droplet::list_summary()`](struct.Droplet.html#method.list_summary).
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct DropletSummary {
+    /// A unique identifier for each Droplet instance.
+    id: usize,
+
+    /// The human-readable name set for the Droplet instance.
+    name: String,
+
+    /// A status string indicating the state of the Droplet instance. This may
+    /// be "new", "active", "off", or "archive".
+    status: String,
+}
+
 /// Fields which exists inside Droplets.
+///
+/// The API does not support selecting which fields are returned, see
+/// [`DropletSummary`](../struct.DropletSummary.html) for a cheaper way to
+/// list many Droplets.
 pub mod droplet_fields {
     use chrono::{DateTime, Utc};
     use serde::Deserialize;
@@ -168,56 +207,235 @@ pub mod droplet_fields {
         pub name: String,
         pub version: String,
     }
+
+    /// The `links` returned alongside a newly created droplet.
+    #[derive(Deserialize, Serialize, Debug, Clone, Default)]
+    pub struct DropletLinks {
+        #[serde(default)]
+        pub actions: Vec<ActionLink>,
+    }
+
+    /// When and how often the backups of a Droplet are taken, set with
+    /// `backup_policy()` on Droplet creation.
+    #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+    pub struct BackupPolicy {
+        pub plan: BackupPlan,
+        /// The day of the week weekly backups are taken on.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub weekday: Option<BackupWeekday>,
+        /// The hour of the day, in UTC, the backup window starts at. One of
+        /// 0, 4, 8, 12, 16 or 20.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub hour: Option<u8>,
+    }
+
+    /// How often the backups of a Droplet are taken.
+    #[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+    #[serde(rename_all = "lowercase")]
+    pub enum BackupPlan {
+        Daily,
+        Weekly,
+        #[serde(other)]
+        Unknown,
+    }
+
+    /// The day of the week weekly backups are taken on.
+    #[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+    #[serde(rename_all = "UPPERCASE")]
+    pub enum BackupWeekday {
+        Sun,
+        Mon,
+        Tue,
+        Wed,
+        Thu,
+        Fri,
+        Sat,
+        #[serde(other)]
+        Unknown,
+    }
+
+    /// Which associated resources to destroy along with a Droplet, see
+    /// `destroy_with_associated_resources()`.
+    #[derive(Debug, Clone)]
+    pub enum DestroyAssociated {
+        /// Destroy only the listed resources.
+        Selective(AssociatedResourceIds),
+        /// Destroy every resource associated with the Droplet. This sends the
+        /// `X-Dangerous: true` header the API requires to confirm it.
+        Dangerous,
+    }
+
+    /// The ids of the resources destroyed by `DestroyAssociated::Selective`,
+    /// as found in the `AssociatedResources` listing. Every list may be left
+    /// empty.
+    #[derive(Deserialize, Serialize, Debug, Clone, Default)]
+    pub struct AssociatedResourceIds {
+        /// The addresses of Floating IPs, which DigitalOcean now calls
+        /// Reserved IPs.
+        pub reserved_ips: Vec<String>,
+        /// The ids of Droplet snapshots.
+        pub snapshots: Vec<String>,
+        /// The ids of volumes.
+        pub volumes: Vec<String>,
+        /// The ids of volume snapshots.
+        pub volume_snapshots: Vec<String>,
+    }
+
+    /// The resources which `destroy_with_associated_resources()` can destroy
+    /// along with a Droplet, as listed by `associated_resources()`.
+    #[derive(Deserialize, Serialize, Debug, Clone, Default)]
+    pub struct AssociatedResources {
+        #[serde(default)]
+        pub reserved_ips: Vec<AssociatedResource>,
+        /// The same addresses as `reserved_ips`, under their former name.
+        #[serde(default)]
+        pub floating_ips: Vec<AssociatedResource>,
+        #[serde(default)]
+        pub snapshots: Vec<AssociatedResource>,
+        #[serde(default)]
+        pub volumes: Vec<AssociatedResource>,
+        #[serde(default)]
+        pub volume_snapshots: Vec<AssociatedResource>,
+    }
+
+    /// A resource associated with a Droplet.
+    #[derive(Deserialize, Serialize, Debug, Clone)]
+    pub struct AssociatedResource {
+        pub id: String,
+        pub name: String,
+        /// The monthly cost of the resource in US dollars, as a decimal
+        /// string such as "0.05".
+        pub cost: String,
+    }
+
+    /// This exists in the `actions` field of the droplet `links`.
+    #[derive(Deserialize, Serialize, Debug, Clone)]
+    pub struct ActionLink {
+        pub id: usize,
+        /// The relation of the action to the droplet, "create" for the
+        /// action tracking its creation.
+        pub rel: String,
+        pub href: String,
+    }
 }
 
 impl Droplet {
+    /// `image` is either a slug or the id of an image, snapshot or backup, see
+    /// [`ImageSource`](enum.ImageSource.html).
+    ///
+    /// `region` and `size` may be checked ahead of time with
+    /// [`RegionSlug`](struct.RegionSlug.html) and
+    /// [`SizeSlug`](struct.SizeSlug.html).
+    ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-droplet)
-    pub fn create<S, D>(name: S, region: S, size: S, image: D) -> DropletRequest<Create, Droplet>
+    pub fn create<S, R, Z, I>(
+        name: S,
+        region: R,
+        size: Z,
+        image: I,
+    ) -> DropletRequest<Create, Droplet>
     where
         S: AsRef<str> + Serialize + Display,
-        D: Serialize + Display,
+        R: AsRef<str> + Serialize + Display,
+        Z: AsRef<str> + Serialize + Display,
+        I: Into<ImageSource>,
     {
         let mut url = ROOT_URL.clone();
         url.path_segments_mut()
             .expect(STATIC_URL_ERROR)
             .push(DROPLETS_SEGMENT);
 
-        let mut req = Request::new(url);
+        let mut req = Request::new(url).validator(validate_create);
         req.set_body(json!({
             "name": name,
             "region": region,
             "size": size,
-            "image": format!("{}", image),
+            "image": image.into(),
         }));
         req
     }
 
+    /// `image` is either a slug or the id of an image, snapshot or backup, see
+    /// [`ImageSource`](enum.ImageSource.html).
+    ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-multiple-droplets)
-    pub fn create_multiple<S, D>(
+    pub fn create_multiple<S, R, Z, I>(
         names: Vec<S>,
-        region: S,
-        size: S,
-        image: D,
+        region: R,
+        size: Z,
+        image: I,
     ) -> DropletRequest<Create, Vec<Droplet>>
     where
         S: AsRef<str> + Serialize + Display,
-        D: Serialize + Display,
+        R: AsRef<str> + Serialize + Display,
+        Z: AsRef<str> + Serialize + Display,
+        I: Into<ImageSource>,
     {
         let mut url = ROOT_URL.clone();
         url.path_segments_mut()
             .expect(STATIC_URL_ERROR)
             .push(DROPLETS_SEGMENT);
 
-        let mut req = Request::new(url);
+        let mut req = Request::new(url).validator(validate_create);
         req.set_body(json!({
             "names": names,
             "region": region,
             "size": size,
-            "image": format!("{}", image),
+            "image": image.into(),
         }));
         req
     }
 
+    /// Create `per_region` Droplets in each of `regions`, named
+    /// `{name_prefix}-{region}-{n}` with `n` counting from 1, with at most
+    /// `concurrency` requests in flight at once.
+    ///
+    /// Every Droplet is created by its own request, a failure does not stop
+    /// the others. Failed Droplets are reported by name.
+    pub async fn create_spread<S, I>(
+        instance: &DigitalOcean,
+        name_prefix: S,
+        regions: Vec<String>,
+        size: S,
+        image: I,
+        per_region: usize,
+        concurrency: usize,
+    ) -> BatchResult<Droplet, String>
+    where
+        S: AsRef<str>,
+        I: Into<ImageSource>,
+    {
+        let image = image.into();
+        let name_prefix = name_prefix.as_ref();
+        let names: Vec<(String, &str)> = regions
+            .iter()
+            .flat_map(|region| {
+                (1..=per_region).map(move |n| {
+                    let name = format!("{name_prefix}-{region}-{n}");
+                    (name, region.as_str())
+                })
+            })
+            .collect();
+
+        let results: Vec<_> = stream::iter(names)
+            .map(|(name, region)| {
+                let create = Droplet::create(name.as_str(), region, size.as_ref(), image.clone());
+                async move { (name, create.execute(instance).await) }
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+
+        let mut batch = BatchResult::default();
+        for (name, result) in results {
+            match result {
+                Ok(droplet) => batch.succeeded.push(droplet),
+                Err(e) => batch.failed.push((name, e)),
+            }
+        }
+        batch
+    }
+
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#retrieve-an-existing-droplet-by-id)
     pub fn get(id: usize) -> DropletRequest<Get, Droplet> {
         let mut url = ROOT_URL.clone();
@@ -239,6 +457,19 @@ impl Droplet {
         Request::new(url)
     }
 
+    /// Like [`list()`](#method.list), but only the `id`, `name` and `status`
+    /// of each Droplet are deserialized.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-all-droplets)
+    pub fn list_summary() -> DropletRequest<List, Vec<DropletSummary>> {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(DROPLETS_SEGMENT);
+
+        Request::new(url)
+    }
+
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#listing-droplets-by-tag)
     pub fn list_by_tag<S: AsRef<str> + Serialize>(name: S) -> DropletRequest<List, Vec<Droplet>> {
         let mut url = ROOT_URL.clone();
@@ -251,6 +482,70 @@ impl Droplet {
         Request::new(url)
     }
 
+    /// Fetch the Droplet tagged with `name`, or `None` if no Droplet has the
+    /// tag.
+    ///
+    /// **Note:** Meant for tags which identify a single Droplet. If several
+    /// Droplets have the tag a warning is logged and the first one is returned.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#listing-droplets-by-tag)
+    pub async fn get_by_tag<S: AsRef<str> + Serialize>(
+        instance: &DigitalOcean,
+        name: S,
+    ) -> Result<Option<Droplet>, Error> {
+        let droplets = Droplet::list_by_tag(name.as_ref())
+            .limit(Some(2))
+            .execute(instance)
+            .await?;
+
+        if droplets.len() > 1 {
+            warn!(
+                "More than one Droplet is tagged {:?}, using the first.",
+                name.as_ref()
+            );
+        }
+
+        Ok(droplets.into_iter().next())
+    }
+
+    /// Fetch a Droplet by whichever identifier is at hand, as pasted into a
+    /// CLI: a numeric id, a URN such as `do:droplet:4126873`, or else its
+    /// name.
+    ///
+    /// Returns `Error::NotFound` when no Droplet has the name and
+    /// `Error::InvalidRequest` when several do, or when the URN is not that
+    /// of a Droplet.
+    pub async fn lookup(instance: &DigitalOcean, identifier: &str) -> Result<Droplet, Error> {
+        if let Ok(id) = identifier.parse::<usize>() {
+            return Droplet::get(id).execute(instance).await;
+        }
+
+        if identifier.starts_with("do:") {
+            return match identifier.parse::<Urn>()? {
+                Urn::Droplet(id) => Droplet::get(id).execute(instance).await,
+                _ => Err(Error::InvalidRequest(format!(
+                    "urn {identifier:?} does not refer to a droplet"
+                ))),
+            };
+        }
+
+        let mut droplets: Vec<Droplet> = Droplet::list()
+            .name(identifier)
+            .execute(instance)
+            .await?
+            .into_iter()
+            .filter(|droplet| droplet.name == identifier)
+            .collect();
+
+        match droplets.len() {
+            0 => Err(Error::NotFound { request_id: None }),
+            1 => Ok(droplets.remove(0)),
+            n => Err(Error::InvalidRequest(format!(
+                "{n} droplets are named {identifier:?}, use an id instead"
+            ))),
+        }
+    }
+
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#delete-a-droplet)
     pub fn delete(id: usize) -> DropletRequest<Delete, ()> {
         let mut url = ROOT_URL.clone();
@@ -286,15 +581,180 @@ impl Droplet {
     }
 }
 
+/// Catches Droplet creates the API would refuse.
+fn validate_create(body: &Value) -> Result<(), Error> {
+    match body["region"].as_str() {
+        Some(region) if !region.is_empty() => {}
+        _ => {
+            return Err(Error::InvalidRequest(
+                "a region is required to create a Droplet".to_string(),
+            ))
+        }
+    }
+
+    if !body["name"].is_null() && !body["names"].is_null() {
+        return Err(Error::InvalidRequest(
+            "`name` and `names` are mutually exclusive".to_string(),
+        ));
+    }
+
+    if body["names"]
+        .as_array()
+        .is_some_and(|names| names.is_empty())
+    {
+        return Err(Error::InvalidRequest(
+            "at least one name is required to create multiple Droplets".to_string(),
+        ));
+    }
+
+    if !body["backup_policy"].is_null() {
+        if body["backups"] != json!(true) {
+            return Err(Error::InvalidRequest(
+                "a backup policy requires backups to be enabled".to_string(),
+            ));
+        }
+
+        if body["backup_policy"]["hour"]
+            .as_u64()
+            .is_some_and(|hour| hour >= 24 || hour % 4 != 0)
+        {
+            return Err(Error::InvalidRequest(
+                "the backup hour must be one of 0, 4, 8, 12, 16 or 20".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// A feature which may be enabled on a Droplet, as listed in its `features`.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DropletFeature {
+    Backups,
+    Ipv6,
+    Monitoring,
+    PrivateNetworking,
+    Virtio,
+    DropletAgent,
+    /// A feature this crate does not know about yet.
+    #[serde(other)]
+    Unknown,
+}
+
+impl Droplet {
+    /// Whether `feature` is enabled on the Droplet. Never true for
+    /// `DropletFeature::Unknown`.
+    pub fn has_feature(&self, feature: DropletFeature) -> bool {
+        let name = json!(feature);
+        self.features.iter().any(|f| name == *f)
+    }
+
+    /// The unique slug identifier for the size of this Droplet. Falls back to
+    /// the slug of the nested `size` object when the API omits `size_slug`.
+    pub fn size_slug(&self) -> Option<&str> {
+        self.size_slug
+            .as_deref()
+            .or_else(|| self.size.as_ref().map(|size| size.slug().as_str()))
+    }
+
+    /// The first public IPv4 address assigned to the Droplet, if any.
+    pub fn public_ipv4(&self) -> Option<Ipv4Addr> {
+        self.networks
+            .v4
+            .iter()
+            .find(|network| network.kind == "public")
+            .map(|network| network.ip_address)
+    }
+
+    /// The first private IPv4 address assigned to the Droplet, if any.
+    pub fn private_ipv4(&self) -> Option<Ipv4Addr> {
+        self.networks
+            .v4
+            .iter()
+            .find(|network| network.kind == "private")
+            .map(|network| network.ip_address)
+    }
+
+    /// The first public IPv6 address assigned to the Droplet, if any.
+    pub fn public_ipv6(&self) -> Option<Ipv6Addr> {
+        self.networks
+            .v6
+            .iter()
+            .find(|network| network.kind == "public")
+            .map(|network| network.ip_address)
+    }
+}
+
 impl DropletRequest<Create, Droplet> {
+    /// Create the droplet, unless a Droplet with the same name already
+    /// exists, in which case `Error::AlreadyExists` is returned and nothing
+    /// is created.
+    ///
+    /// **Note:** The API allows several Droplets to share a name, this check
+    /// is opt-in. It is not atomic, a Droplet created concurrently by someone
+    /// else is not detected.
+    pub async fn fail_if_exists(self, instance: &DigitalOcean) -> Result<Droplet, Error> {
+        self.validate()?;
+
+        if let Some(name) = self.body()["name"].as_str() {
+            let mut existing = Droplet::list_summary().limit(Some(1));
+            existing
+                .url_mut()
+                .query_pairs_mut()
+                .append_pair("name", name);
+
+            if !existing.execute(instance).await?.is_empty() {
+                return Err(Error::AlreadyExists(format!("Droplet {name}")));
+            }
+        }
+
+        self.execute(instance).await
+    }
+
+    /// Create the droplet, then fetch the action tracking its creation so it
+    /// can be polled until the droplet is active.
+    ///
+    /// The action is `None` if the response does not link a create action,
+    /// or if fetching it failed. The Droplet exists by then, so it is always
+    /// returned rather than an error which would invite creating it again.
+    pub async fn execute_with_action(
+        self,
+        instance: &DigitalOcean,
+    ) -> Result<(Droplet, Option<Action>), Error> {
+        self.validate()?;
+        let response = instance.post_response(self).await?;
+
+        let action_id = response
+            .links
+            .actions
+            .iter()
+            .find(|link| link.rel == "create")
+            .map(|link| link.id);
+
+        let action = match action_id {
+            Some(action_id) => match Action::get(action_id).execute(instance).await {
+                Ok(action) => Some(action),
+                Err(e) => {
+                    warn!("Fetching create action {} failed: {}", action_id, e);
+                    None
+                }
+            },
+            None => None,
+        };
+        Ok((response.droplet, action))
+    }
+
     /// An array containing the IDs or fingerprints of the SSH keys that you
-    /// wish to embed in the Droplet's root account upon creation.
+    /// wish to embed in the Droplet's root account upon creation. Ids and
+    /// fingerprints can be mixed by passing [`SshKeyRef`](../api/enum.SshKeyRef.html)s.
     ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-droplet)
     pub fn ssh_keys<D>(mut self, val: Vec<D>) -> Self
     where
-        D: Display + Serialize,
+        D: Into<SshKeyRef>,
     {
+        let val: Vec<SshKeyRef> = val.into_iter().map(Into::into).collect();
         self.body_mut()["ssh_keys"] = json!(val);
         self
     }
@@ -309,6 +769,16 @@ impl DropletRequest<Create, Droplet> {
         self
     }
 
+    /// When and how often backups are taken. Setting a policy also enables
+    /// `backups()`.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_create)
+    pub fn backup_policy(mut self, val: BackupPolicy) -> Self {
+        self.body_mut()["backups"] = json!(true);
+        self.body_mut()["backup_policy"] = json!(val);
+        self
+    }
+
     /// A boolean indicating whether IPv6 is enabled on the Droplet.
     ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-droplet)
@@ -318,8 +788,10 @@ impl DropletRequest<Create, Droplet> {
     }
 
     /// A boolean indicating whether private networking is enabled for the
-    /// Droplet. Private networking is currently only available in certain
-    /// regions.
+    /// Droplet.
+    ///
+    /// **Note:** This is a legacy flag kept for existing automation. Droplets
+    /// are now always placed in a VPC, prefer choosing it with `vpc_uuid()`.
     ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-droplet)
     pub fn private_networking(mut self, val: bool) -> Self {
@@ -327,6 +799,15 @@ impl DropletRequest<Create, Droplet> {
         self
     }
 
+    /// The UUID of the VPC the Droplet will be placed in. When unset the
+    /// default VPC of the region is used.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_create)
+    pub fn vpc_uuid<S: AsRef<str> + Serialize + Display>(mut self, val: S) -> Self {
+        self.body_mut()["vpc_uuid"] = json!(val);
+        self
+    }
+
     /// A string containing 'user data' which may be used to configure the
     /// Droplet on first boot, often a 'cloud-config' file or Bash script.
     /// It must be plain text and may not exceed 64 KiB in size.
@@ -346,10 +827,23 @@ impl DropletRequest<Create, Droplet> {
         self
     }
 
+    /// A boolean indicating whether to install the DigitalOcean agent used
+    /// for providing access to the Droplet web console. When unset the agent
+    /// is installed on images which support it.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_create)
+    pub fn with_droplet_agent(mut self, val: bool) -> Self {
+        self.body_mut()["with_droplet_agent"] = json!(val);
+        self
+    }
+
     /// A flat array including the unique string identifier for each Block
     /// Storage volume to be attached to the Droplet. At the moment a volume
     /// can only be attached to a single Droplet.
     ///
+    /// **Note:** The volumes must be in the same region as the Droplet,
+    /// otherwise the API rejects the request.
+    ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-droplet)
     pub fn volumes(mut self, val: Vec<String>) -> Self {
         self.body_mut()["volumes"] = json!(val);
@@ -368,13 +862,15 @@ impl DropletRequest<Create, Droplet> {
 
 impl DropletRequest<Create, Vec<Droplet>> {
     /// An array containing the IDs or fingerprints of the SSH keys that you
-    /// wish to embed in the Droplet's root account upon creation.
+    /// wish to embed in the Droplet's root account upon creation. Ids and
+    /// fingerprints can be mixed by passing [`SshKeyRef`](../api/enum.SshKeyRef.html)s.
     ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-droplet)
     pub fn ssh_keys<D>(mut self, val: Vec<D>) -> Self
     where
-        D: Display + Serialize,
+        D: Into<SshKeyRef>,
     {
+        let val: Vec<SshKeyRef> = val.into_iter().map(Into::into).collect();
         self.body_mut()["ssh_keys"] = json!(val);
         self
     }
@@ -389,6 +885,16 @@ impl DropletRequest<Create, Vec<Droplet>> {
         self
     }
 
+    /// When and how often backups are taken. Setting a policy also enables
+    /// `backups()`.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_create)
+    pub fn backup_policy(mut self, val: BackupPolicy) -> Self {
+        self.body_mut()["backups"] = json!(true);
+        self.body_mut()["backup_policy"] = json!(val);
+        self
+    }
+
     /// A boolean indicating whether IPv6 is enabled on the Droplet.
     ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-droplet)
@@ -398,8 +904,10 @@ impl DropletRequest<Create, Vec<Droplet>> {
     }
 
     /// A boolean indicating whether private networking is enabled for the
-    /// Droplet. Private networking is currently only available in certain
-    /// regions.
+    /// Droplet.
+    ///
+    /// **Note:** This is a legacy flag kept for existing automation. Droplets
+    /// are now always placed in a VPC, prefer choosing it with `vpc_uuid()`.
     ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-droplet)
     pub fn private_networking(mut self, val: bool) -> Self {
@@ -407,6 +915,15 @@ impl DropletRequest<Create, Vec<Droplet>> {
         self
     }
 
+    /// The UUID of the VPC the Droplet will be placed in. When unset the
+    /// default VPC of the region is used.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_create)
+    pub fn vpc_uuid<S: AsRef<str> + Serialize + Display>(mut self, val: S) -> Self {
+        self.body_mut()["vpc_uuid"] = json!(val);
+        self
+    }
+
     /// A string containing 'user data' which may be used to configure the
     /// Droplet on first boot, often a 'cloud-config' file or Bash script.
     /// It must be plain text and may not exceed 64 KiB in size.
@@ -426,10 +943,23 @@ impl DropletRequest<Create, Vec<Droplet>> {
         self
     }
 
+    /// A boolean indicating whether to install the DigitalOcean agent used
+    /// for providing access to the Droplet web console. When unset the agent
+    /// is installed on images which support it.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_create)
+    pub fn with_droplet_agent(mut self, val: bool) -> Self {
+        self.body_mut()["with_droplet_agent"] = json!(val);
+        self
+    }
+
     /// A flat array including the unique string identifier for each Block
     /// Storage volume to be attached to the Droplet. At the moment a volume
     /// can only be attached to a single Droplet.
     ///
+    /// **Note:** The volumes must be in the same region as the Droplet,
+    /// otherwise the API rejects the request.
+    ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-droplet)
     pub fn volumes(mut self, val: Vec<String>) -> Self {
         self.body_mut()["volumes"] = json!(val);
@@ -446,6 +976,45 @@ impl DropletRequest<Create, Vec<Droplet>> {
     }
 }
 
+impl DropletRequest<List, Vec<Droplet>> {
+    /// Restrict the listing to a kind of Droplet. This may be "droplets" or
+    /// "gpus".
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_list)
+    pub fn kind<S: AsRef<str> + Serialize + Display>(mut self, kind: S) -> Self {
+        self.url_mut()
+            .query_pairs_mut()
+            .append_pair("type", kind.as_ref());
+
+        self
+    }
+
+    /// Only list the Droplets with exactly this name.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_list)
+    pub fn name<S: AsRef<str>>(mut self, name: S) -> Self {
+        self.url_mut()
+            .query_pairs_mut()
+            .append_pair("name", name.as_ref());
+
+        self
+    }
+
+    /// Only list GPU Droplets.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_list)
+    pub fn only_gpus(self) -> Self {
+        self.kind("gpus")
+    }
+
+    /// Only list regular, non-GPU Droplets.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_list)
+    pub fn only_droplets(self) -> Self {
+        self.kind("droplets")
+    }
+}
+
 impl DropletRequest<Get, Droplet> {
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-snapshots-for-a-droplet)
     pub fn snapshots(mut self) -> SnapshotRequest<List, Vec<Snapshot>> {
@@ -476,12 +1045,96 @@ impl DropletRequest<Get, Droplet> {
 
         self.transmute()
     }
+
+    /// The resources `destroy_with_associated_resources()` can destroy along
+    /// with the Droplet, with their cost, to review before destroying them.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_list_associatedResources)
+    pub async fn associated_resources(
+        mut self,
+        instance: &DigitalOcean,
+    ) -> Result<AssociatedResources, Error> {
+        self.url_mut()
+            .path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(DESTROY_WITH_ASSOCIATED_SEGMENT);
+
+        let request: DropletRequest<Get, AssociatedResources> = self.transmute();
+        request.execute(instance).await
+    }
+
+    /// Destroy the Droplet together with its associated resources, such as
+    /// volumes, snapshots and Floating IPs. The resources are destroyed
+    /// asynchronously after the request is accepted.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_destroy_withAssociatedResourcesSelective)
+    pub fn destroy_with_associated_resources(
+        mut self,
+        which: DestroyAssociated,
+    ) -> DropletRequest<Delete, ()> {
+        self.url_mut()
+            .path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(DESTROY_WITH_ASSOCIATED_SEGMENT);
+
+        match which {
+            DestroyAssociated::Selective(resources) => {
+                self.url_mut()
+                    .path_segments_mut()
+                    .expect(STATIC_URL_ERROR)
+                    .push(SELECTIVE_SEGMENT);
+                self.set_body(json!(resources));
+                self.transmute()
+            }
+            DestroyAssociated::Dangerous => {
+                self.url_mut()
+                    .path_segments_mut()
+                    .expect(STATIC_URL_ERROR)
+                    .push(DANGEROUS_SEGMENT);
+                self.header("X-Dangerous", "true").transmute()
+            }
+        }
+    }
+
+    /// The firewalls which apply to the Droplet.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/droplets_list_firewalls)
+    pub fn firewalls(mut self) -> FirewallRequest<List, Vec<Firewall>> {
+        self.url_mut()
+            .path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(FIREWALLS_SEGMENT);
+
+        self.transmute()
+    }
+
+    /// The kernels the Droplet can be switched to with
+    /// [`change_kernel()`](#method.change_kernel).
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-all-available-kernels-for-a-droplet)
+    pub fn kernels(mut self) -> DropletRequest<List, Vec<Kernel>> {
+        self.url_mut()
+            .path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(KERNELS_SEGMENT);
+
+        self.transmute()
+    }
+}
+
+impl fmt::Display for Droplet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "droplet {:?} ({})", self.name, self.id)
+    }
 }
 
 /// Response type returned from Digital Ocean.
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct DropletResponse {
     droplet: Droplet,
+    /// Only returned when the droplet has just been created.
+    #[serde(default)]
+    links: DropletLinks,
 }
 
 impl HasResponse for Droplet {
@@ -497,10 +1150,12 @@ impl HasValue for DropletResponse {
 }
 
 /// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Getters)]
 pub struct DropletListResponse {
     droplets: Vec<Droplet>,
+    #[get = "pub"]
     links: ApiLinks,
+    #[get = "pub"]
     meta: ApiMeta,
 }
 
@@ -522,6 +1177,52 @@ impl HasValue for DropletListResponse {
     }
 }
 
+impl IntoIterator for DropletListResponse {
+    type Item = Droplet;
+    type IntoIter = std::vec::IntoIter<Droplet>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.droplets.into_iter()
+    }
+}
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone, Getters)]
+pub struct DropletSummaryListResponse {
+    droplets: Vec<DropletSummary>,
+    #[get = "pub"]
+    links: ApiLinks,
+    #[get = "pub"]
+    meta: ApiMeta,
+}
+
+impl HasResponse for Vec<DropletSummary> {
+    type Response = DropletSummaryListResponse;
+}
+
+impl HasPagination for DropletSummaryListResponse {
+    fn next_page(&self) -> Option<Url> {
+        self.links.next()
+    }
+}
+
+impl HasValue for DropletSummaryListResponse {
+    type Value = Vec<DropletSummary>;
+
+    fn value(self) -> Vec<DropletSummary> {
+        self.droplets
+    }
+}
+
+impl IntoIterator for DropletSummaryListResponse {
+    type Item = DropletSummary;
+    type IntoIter = std::vec::IntoIter<DropletSummary>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.droplets.into_iter()
+    }
+}
+
 /// Response type returned from Digital Ocean
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct DropletNeighborsResponse {
@@ -539,3 +1240,53 @@ impl HasValue for DropletNeighborsResponse {
         self.neighbors
     }
 }
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone, Getters)]
+pub struct KernelListResponse {
+    kernels: Vec<Kernel>,
+    #[get = "pub"]
+    links: ApiLinks,
+    #[get = "pub"]
+    meta: ApiMeta,
+}
+
+impl HasResponse for Vec<Kernel> {
+    type Response = KernelListResponse;
+}
+
+impl HasPagination for KernelListResponse {
+    fn next_page(&self) -> Option<Url> {
+        self.links.next()
+    }
+}
+
+impl HasValue for KernelListResponse {
+    type Value = Vec<Kernel>;
+
+    fn value(self) -> Vec<Kernel> {
+        self.kernels
+    }
+}
+
+impl IntoIterator for KernelListResponse {
+    type Item = Kernel;
+    type IntoIter = std::vec::IntoIter<Kernel>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.kernels.into_iter()
+    }
+}
+
+// The associated resources are not wrapped in a key.
+impl HasResponse for AssociatedResources {
+    type Response = AssociatedResources;
+}
+
+impl HasValue for AssociatedResources {
+    type Value = AssociatedResources;
+
+    fn value(self) -> AssociatedResources {
+        self
+    }
+}
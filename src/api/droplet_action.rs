@@ -1,4 +1,5 @@
 use super::action::Action;
+use super::droplet::droplet_fields::BackupPolicy;
 use super::droplet::Droplet;
 use crate::method::{Create, Get, List};
 use crate::request::{DropletActionRequest, DropletRequest};
@@ -47,6 +48,27 @@ impl DropletRequest<Get, Droplet> {
         self.transmute()
     }
 
+    /// Change when and how often the backups of a Droplet with backups
+    /// enabled are taken.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/dropletActions_post)
+    pub fn change_backup_policy(
+        mut self,
+        policy: BackupPolicy,
+    ) -> DropletActionRequest<Create, Action> {
+        self.url_mut()
+            .path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(DROPLET_ACTIONS_SEGMENT);
+
+        self.set_body(json!({
+            "type": "change_backup_policy",
+            "backup_policy": policy,
+        }));
+
+        self.transmute()
+    }
+
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#reboot-a-droplet)
     pub fn reboot(mut self) -> DropletActionRequest<Create, Action> {
         self.url_mut()
@@ -187,8 +209,11 @@ impl DropletRequest<Get, Droplet> {
         self.transmute()
     }
 
+    /// Switch the Droplet to one of the kernels listed by
+    /// [`kernels()`](#method.kernels).
+    ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#change-the-kernel)
-    pub fn kernel(mut self, kernel: usize) -> DropletActionRequest<Create, Action> {
+    pub fn change_kernel(mut self, kernel: usize) -> DropletActionRequest<Create, Action> {
         self.url_mut()
             .path_segments_mut()
             .expect(STATIC_URL_ERROR)
@@ -202,6 +227,12 @@ impl DropletRequest<Get, Droplet> {
         self.transmute()
     }
 
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#change-the-kernel)
+    #[deprecated(since = "0.2.3", note = "renamed to `change_kernel()`")]
+    pub fn kernel(self, kernel: usize) -> DropletActionRequest<Create, Action> {
+        self.change_kernel(kernel)
+    }
+
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#enable-ipv6)
     pub fn enable_ipv6(mut self) -> DropletActionRequest<Create, Action> {
         self.url_mut()
@@ -0,0 +1,301 @@
+use self::firewall_fields::{InboundRule, OutboundRule, PendingChange};
+use super::{ApiLinks, ApiMeta};
+use super::{HasPagination, HasResponse, HasValue};
+use crate::error::Error;
+use crate::method::{Get, List};
+use crate::request::FirewallRequest;
+use crate::request::{Executable, Request};
+use crate::wait::poll;
+use crate::{DigitalOcean, ROOT_URL, STATIC_URL_ERROR};
+use chrono::{DateTime, Utc};
+use getset::{Getters, Setters};
+use serde::Deserialize;
+use serde::Serialize;
+use std::fmt::{self, Display};
+use std::future::{pending, Future};
+use std::time::Duration;
+#[cfg(feature = "cancellation")]
+use tokio_util::sync::CancellationToken;
+use url::Url;
+
+const FIREWALLS_SEGMENT: &str = "firewalls";
+
+/// Cloud Firewalls provide the ability to restrict network access to and
+/// from a Droplet allowing you to define which ports will accept inbound or
+/// outbound connections.
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#tag/Firewalls)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct Firewall {
+    /// A unique ID that can be used to identify and reference a firewall.
+    id: String,
+
+    /// A status string indicating the current state of the firewall. This
+    /// can be "waiting", "succeeded", or "failed".
+    status: String,
+
+    /// A time value given in ISO8601 combined date and time format that
+    /// represents when the firewall was created.
+    created_at: DateTime<Utc>,
+
+    /// An array of objects each containing the fields "droplet_id",
+    /// "removing", and "status". It is provided to detail exactly which
+    /// Droplets are having their security policies updated.
+    #[serde(default)]
+    pending_changes: Vec<PendingChange>,
+
+    /// A human-readable name for a firewall.
+    name: String,
+
+    /// The inbound access rules which specify the protocol, ports, and
+    /// sources of allowed traffic.
+    #[serde(default)]
+    inbound_rules: Vec<InboundRule>,
+
+    /// The outbound access rules which specify the protocol, ports, and
+    /// destinations of allowed traffic.
+    #[serde(default)]
+    outbound_rules: Vec<OutboundRule>,
+
+    /// The IDs of the Droplets assigned to the firewall.
+    #[serde(default)]
+    droplet_ids: Vec<usize>,
+
+    /// The names of the Tags assigned to the firewall.
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// Fields which exists inside Firewalls.
+pub mod firewall_fields {
+    use serde::Deserialize;
+    use serde::Serialize;
+
+    /// This exists in the `inbound_rules` field of a firewall.
+    #[derive(Deserialize, Serialize, Debug, Clone)]
+    pub struct InboundRule {
+        /// The type of traffic to be allowed. This may be one of "tcp",
+        /// "udp", or "icmp".
+        pub protocol: String,
+
+        /// The ports on which traffic will be allowed specified as a string
+        /// containing a single port, a range (e.g. "8000-9000"), or "0" when
+        /// all ports are open for a protocol.
+        #[serde(default)]
+        pub ports: String,
+
+        /// Where the traffic is allowed to come from.
+        pub sources: Targets,
+    }
+
+    /// This exists in the `outbound_rules` field of a firewall.
+    #[derive(Deserialize, Serialize, Debug, Clone)]
+    pub struct OutboundRule {
+        /// The type of traffic to be allowed. This may be one of "tcp",
+        /// "udp", or "icmp".
+        pub protocol: String,
+
+        /// The ports on which traffic will be allowed specified as a string
+        /// containing a single port, a range (e.g. "8000-9000"), or "0" when
+        /// all ports are open for a protocol.
+        #[serde(default)]
+        pub ports: String,
+
+        /// Where the traffic is allowed to go to.
+        pub destinations: Targets,
+    }
+
+    /// The `sources` of an inbound rule or the `destinations` of an outbound
+    /// rule.
+    #[derive(Deserialize, Serialize, Debug, Clone, Default)]
+    pub struct Targets {
+        /// IPv4 addresses, IPv6 addresses, IPv4 CIDRs, and/or IPv6 CIDRs.
+        #[serde(default)]
+        pub addresses: Vec<String>,
+
+        /// The IDs of Droplets.
+        #[serde(default)]
+        pub droplet_ids: Vec<usize>,
+
+        /// The IDs of Load Balancers.
+        #[serde(default)]
+        pub load_balancer_uids: Vec<String>,
+
+        /// The names of Tags.
+        #[serde(default)]
+        pub tags: Vec<String>,
+    }
+
+    /// This exists in the `pending_changes` field of a firewall.
+    #[derive(Deserialize, Serialize, Debug, Clone)]
+    pub struct PendingChange {
+        /// The ID of the Droplet being updated.
+        pub droplet_id: usize,
+
+        /// Whether the Droplet is being removed from the firewall.
+        pub removing: bool,
+
+        /// The status of the change, such as "waiting".
+        pub status: String,
+    }
+}
+
+impl Firewall {
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/firewalls_list)
+    pub fn list() -> FirewallRequest<List, Vec<Firewall>> {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(FIREWALLS_SEGMENT);
+
+        Request::new(url)
+    }
+
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/firewalls_get)
+    pub fn get<S: AsRef<str> + Display>(id: S) -> FirewallRequest<Get, Firewall> {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(FIREWALLS_SEGMENT)
+            .push(id.as_ref());
+
+        Request::new(url)
+    }
+
+    /// List every firewall and keep those assigned to the Droplet `id`. The
+    /// API has no filter for this, so all pages are fetched.
+    ///
+    /// **Note:** Only firewalls listing the Droplet in `droplet_ids` are
+    /// returned, not those applied through one of its tags. Use
+    /// `Droplet::get(id).firewalls()` to also include those.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/firewalls_list)
+    pub async fn for_droplet(instance: &DigitalOcean, id: usize) -> Result<Vec<Firewall>, Error> {
+        Firewall::list()
+            .filter(move |firewall| firewall.droplet_ids.contains(&id))
+            .execute(instance)
+            .await
+    }
+}
+
+impl FirewallRequest<Get, Firewall> {
+    /// Fetch the firewall every `interval` until its status is "succeeded"
+    /// and return it.
+    ///
+    /// Returns `Error::Failed` if the status becomes "failed" and
+    /// `Error::Timeout` if it has not succeeded within `timeout`. Pass
+    /// `Duration::MAX` to wait without a deadline.
+    pub async fn wait_until_ready(
+        self,
+        instance: &DigitalOcean,
+        interval: Duration,
+        timeout: Duration,
+    ) -> Result<Firewall, Error> {
+        self.wait_until_ready_or(instance, interval, timeout, pending())
+            .await
+    }
+
+    /// Like [`wait_until_ready()`](#method.wait_until_ready), but returns
+    /// `Error::Cancelled` as soon as `token` is cancelled.
+    #[cfg(feature = "cancellation")]
+    pub async fn wait_until_ready_cancellable(
+        self,
+        instance: &DigitalOcean,
+        interval: Duration,
+        timeout: Duration,
+        token: &CancellationToken,
+    ) -> Result<Firewall, Error> {
+        self.wait_until_ready_or(instance, interval, timeout, token.cancelled())
+            .await
+    }
+
+    async fn wait_until_ready_or<C: Future<Output = ()>>(
+        self,
+        instance: &DigitalOcean,
+        interval: Duration,
+        timeout: Duration,
+        cancelled: C,
+    ) -> Result<Firewall, Error> {
+        let id = self
+            .url()
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .expect(STATIC_URL_ERROR);
+        let what = format!("firewall {id}");
+
+        poll(&what, interval, timeout, cancelled, || {
+            let request = self.clone();
+            async move {
+                let firewall = request.execute(instance).await?;
+                match firewall.status.as_str() {
+                    "succeeded" => Ok(Some(firewall)),
+                    "failed" => Err(Error::Failed(format!("Firewall {}", firewall.id))),
+                    _ => Ok(None),
+                }
+            }
+        })
+        .await
+    }
+}
+
+impl fmt::Display for Firewall {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "firewall {:?} ({})", self.name, self.id)
+    }
+}
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FirewallResponse {
+    firewall: Firewall,
+}
+
+impl HasResponse for Firewall {
+    type Response = FirewallResponse;
+}
+
+impl HasValue for FirewallResponse {
+    type Value = Firewall;
+
+    fn value(self) -> Firewall {
+        self.firewall
+    }
+}
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone, Getters)]
+pub struct FirewallListResponse {
+    firewalls: Vec<Firewall>,
+    #[get = "pub"]
+    links: ApiLinks,
+    #[get = "pub"]
+    meta: ApiMeta,
+}
+
+impl HasResponse for Vec<Firewall> {
+    type Response = FirewallListResponse;
+}
+
+impl HasPagination for FirewallListResponse {
+    fn next_page(&self) -> Option<Url> {
+        self.links.next()
+    }
+}
+
+impl HasValue for FirewallListResponse {
+    type Value = Vec<Firewall>;
+
+    fn value(self) -> Vec<Firewall> {
+        self.firewalls
+    }
+}
+
+impl IntoIterator for FirewallListResponse {
+    type Item = Firewall;
+    type IntoIter = std::vec::IntoIter<Firewall>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.firewalls.into_iter()
+    }
+}
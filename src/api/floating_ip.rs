@@ -1,6 +1,7 @@
 use super::{ApiLinks, ApiMeta};
-use super::{Droplet, Region};
+use super::{Droplet, Region, Urn};
 use super::{HasPagination, HasResponse, HasValue};
+use crate::error::Error;
 use crate::method::{Create, Delete, Get, List};
 use crate::request::FloatingIpRequest;
 use crate::request::Request;
@@ -8,7 +9,8 @@ use crate::{ROOT_URL, STATIC_URL_ERROR};
 use getset::{Getters, Setters};
 use serde::Deserialize;
 use serde::Serialize;
-use std::fmt::Display;
+use serde_json::Value;
+use std::fmt::{self, Display};
 use std::net::IpAddr;
 use url::Url;
 
@@ -37,6 +39,10 @@ pub struct FloatingIp {
     /// a Floating IP, if it is assigned to a Droplet, the entire Droplet
     /// object will be returned. If it is not assigned, the value will be null.
     droplet: Option<Droplet>,
+
+    /// The UUID of the project the Floating IP belongs to.
+    #[serde(default)]
+    project_id: Option<String>,
 }
 
 impl FloatingIp {
@@ -50,6 +56,9 @@ impl FloatingIp {
         Request::new(url)
     }
 
+    /// **Note:** Droplet ids start at 1, an id of 0 is refused before the
+    /// request is sent rather than by the API.
+    ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-floating-ip-assigned-to-a-droplet)
     pub fn for_droplet(id: usize) -> FloatingIpRequest<Create, FloatingIp> {
         let mut url = ROOT_URL.clone();
@@ -57,7 +66,7 @@ impl FloatingIp {
             .expect(STATIC_URL_ERROR)
             .push(FLOATING_IP_SEGMENT);
 
-        let mut req = Request::new(url);
+        let mut req = Request::new(url).validator(validate_create);
         req.set_body(json!({
             "droplet_id": id,
         }));
@@ -74,7 +83,7 @@ impl FloatingIp {
             .expect(STATIC_URL_ERROR)
             .push(FLOATING_IP_SEGMENT);
 
-        let mut req = Request::new(url);
+        let mut req = Request::new(url).validator(validate_create);
         req.set_body(json!({
             "region": id,
         }));
@@ -92,7 +101,7 @@ impl FloatingIp {
         Request::new(url)
     }
 
-    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#retrieve-an-existing-floating-ip)
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/floatingIPs_delete)
     pub fn delete<I: Into<IpAddr>>(id: I) -> FloatingIpRequest<Delete, ()> {
         let mut url = ROOT_URL.clone();
         url.path_segments_mut()
@@ -104,6 +113,57 @@ impl FloatingIp {
     }
 }
 
+/// Catches Floating IP creates the API would refuse.
+fn validate_create(body: &Value) -> Result<(), Error> {
+    if !body["droplet_id"].is_null() && !body["region"].is_null() {
+        return Err(Error::InvalidRequest(
+            "`droplet_id` and `region` are mutually exclusive".to_string(),
+        ));
+    }
+
+    if body["droplet_id"].as_u64() == Some(0) {
+        return Err(Error::InvalidRequest(
+            "a Floating IP cannot be assigned to Droplet 0".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+impl FloatingIpRequest<List, Vec<FloatingIp>> {
+    /// Only keep the Floating IPs belonging to the project with the given id.
+    ///
+    /// **Note:** The API cannot filter Floating IPs by project (or by tag),
+    /// this is applied after all pages have been retrieved.
+    pub fn project_id<S: AsRef<str>>(self, id: S) -> Self {
+        let id = id.as_ref().to_string();
+        self.filter(move |floating_ip| floating_ip.project_id.as_deref() == Some(id.as_str()))
+    }
+}
+
+impl FloatingIpRequest<Get, FloatingIp> {
+    /// The URN of the Floating IP, in the form `do:floatingip:{ip}`. This is
+    /// how the Floating IP is referenced when assigning it to a project.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/projects_assign_resources)
+    pub fn urn(&self) -> String {
+        let ip = self
+            .url()
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .and_then(|ip| ip.parse().ok())
+            .expect(STATIC_URL_ERROR);
+
+        Urn::FloatingIp(ip).to_string()
+    }
+}
+
+impl fmt::Display for FloatingIp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "floating ip {}", self.ip)
+    }
+}
+
 /// Response type returned from Digital Ocean.
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct FloatingIpResponse {
@@ -123,10 +183,12 @@ impl HasValue for FloatingIpResponse {
 }
 
 /// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Getters)]
 pub struct FloatingIpListResponse {
     floating_ips: Vec<FloatingIp>,
+    #[get = "pub"]
     links: ApiLinks,
+    #[get = "pub"]
     meta: ApiMeta,
 }
 
@@ -147,3 +209,12 @@ impl HasValue for FloatingIpListResponse {
         self.floating_ips
     }
 }
+
+impl IntoIterator for FloatingIpListResponse {
+    type Item = FloatingIp;
+    type IntoIter = std::vec::IntoIter<FloatingIp>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.floating_ips.into_iter()
+    }
+}
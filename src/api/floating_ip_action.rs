@@ -1,8 +1,15 @@
 use super::floating_ip::FloatingIp;
 use super::Action;
+use crate::error::Error;
 use crate::method::{Create, Get, List};
+use crate::request::Executable;
 use crate::request::{FloatingIpActionRequest, FloatingIpRequest};
-use crate::STATIC_URL_ERROR;
+use crate::wait::poll;
+use crate::{DigitalOcean, STATIC_URL_ERROR};
+use std::future::{pending, Future};
+use std::time::Duration;
+#[cfg(feature = "cancellation")]
+use tokio_util::sync::CancellationToken;
 
 const FLOATING_IP_ACTIONS_SEGMENT: &str = "actions";
 
@@ -56,4 +63,73 @@ impl FloatingIpRequest<Get, FloatingIp> {
 
         self.transmute()
     }
+
+    /// Assign the Floating IP to a Droplet, fetch the assign action every
+    /// `interval` until it completes, then return the Floating IP once it is
+    /// confirmed to be assigned.
+    ///
+    /// Returns `Error::Failed` if the action errors or the Floating IP ends up
+    /// assigned elsewhere, and `Error::Timeout` if the action is still in
+    /// progress after `timeout`.
+    pub async fn assign_and_wait(
+        self,
+        instance: &DigitalOcean,
+        droplet_id: usize,
+        interval: Duration,
+        timeout: Duration,
+    ) -> Result<FloatingIp, Error> {
+        self.assign_and_wait_or(instance, droplet_id, interval, timeout, pending())
+            .await
+    }
+
+    /// Like [`assign_and_wait()`](#method.assign_and_wait), but returns
+    /// `Error::Cancelled` as soon as `token` is cancelled.
+    ///
+    /// **Note:** Cancelling only stops the wait, the assignment carries on.
+    #[cfg(feature = "cancellation")]
+    pub async fn assign_and_wait_cancellable(
+        self,
+        instance: &DigitalOcean,
+        droplet_id: usize,
+        interval: Duration,
+        timeout: Duration,
+        token: &CancellationToken,
+    ) -> Result<FloatingIp, Error> {
+        self.assign_and_wait_or(instance, droplet_id, interval, timeout, token.cancelled())
+            .await
+    }
+
+    async fn assign_and_wait_or<C: Future<Output = ()>>(
+        self,
+        instance: &DigitalOcean,
+        droplet_id: usize,
+        interval: Duration,
+        timeout: Duration,
+        cancelled: C,
+    ) -> Result<FloatingIp, Error> {
+        let ip = self
+            .url()
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .unwrap_or_default()
+            .to_string();
+        let action = self.clone().assign(droplet_id).execute(instance).await?;
+        let what = format!("assignment of floating IP {ip}");
+
+        poll(&what, interval, timeout, cancelled, || async {
+            let action = self.clone().action(*action.id()).execute(instance).await?;
+            match action.status().as_str() {
+                "completed" => Ok(Some(())),
+                "errored" => Err(Error::Failed(format!("Assignment of floating IP {ip}"))),
+                _ => Ok(None),
+            }
+        })
+        .await?;
+
+        let floating_ip = self.execute(instance).await?;
+        match floating_ip.droplet() {
+            Some(droplet) if *droplet.id() == droplet_id => Ok(floating_ip),
+            _ => Err(Error::Failed(format!("Assignment of floating IP {ip}"))),
+        }
+    }
 }
@@ -0,0 +1,391 @@
+use self::function_namespace_fields::{ScheduledDetails, ScheduledRuns};
+use super::{ApiLinks, ApiMeta};
+use super::{HasPagination, HasResponse, HasValue};
+use crate::method::{Create, Delete, Get, List};
+use crate::request::Request;
+use crate::request::{FunctionNamespaceRequest, FunctionTriggerRequest};
+use crate::{ROOT_URL, STATIC_URL_ERROR};
+use chrono::{DateTime, Utc};
+use getset::{Getters, Setters};
+use serde::Deserialize;
+use serde::Serialize;
+use std::fmt::Display;
+use url::Url;
+
+const FUNCTIONS_SEGMENT: &str = "functions";
+const NAMESPACES_SEGMENT: &str = "namespaces";
+const TRIGGERS_SEGMENT: &str = "triggers";
+
+/// Functions are deployed to namespaces, which hold the functions, their
+/// triggers and the credentials needed to manage them with `doctl
+/// serverless`.
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#tag/Functions)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct FunctionNamespace {
+    /// The id of the namespace, used to reference it in requests.
+    namespace: String,
+
+    /// The slug of the region the namespace is in.
+    region: String,
+
+    /// The name given to the namespace. Unique per account.
+    label: String,
+
+    /// The host functions of the namespace are invoked through.
+    api_host: String,
+
+    /// A unique identifier of the namespace.
+    uuid: String,
+
+    /// The key used to manage the namespace, such as deploying functions
+    /// with `doctl serverless`.
+    #[serde(default)]
+    key: Option<String>,
+
+    /// A time value given in ISO8601 combined date and time format that
+    /// represents when the namespace was created.
+    created_at: DateTime<Utc>,
+
+    /// A time value given in ISO8601 combined date and time format that
+    /// represents when the namespace was last updated.
+    #[serde(default)]
+    updated_at: Option<DateTime<Utc>>,
+}
+
+/// Triggers invoke a function of a namespace, for now on a schedule.
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/functions_list_triggers)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct FunctionTrigger {
+    /// The id of the namespace the trigger belongs to.
+    namespace: String,
+
+    /// The name of the trigger. Unique within its namespace.
+    name: String,
+
+    /// The name of the function invoked, including its package if it has
+    /// one, for example `package/function`.
+    function: String,
+
+    /// The type of trigger, currently always "SCHEDULED".
+    ///
+    /// *Note:* Since `type` is a keyword in Rust `kind` is used instead.
+    #[serde(rename = "type")]
+    kind: String,
+
+    /// Whether the function is invoked by the trigger.
+    is_enabled: bool,
+
+    /// When the function is invoked, and with which body.
+    scheduled_details: ScheduledDetails,
+
+    /// When the trigger last invoked the function and will next.
+    #[serde(default)]
+    scheduled_runs: Option<ScheduledRuns>,
+
+    /// A time value given in ISO8601 combined date and time format that
+    /// represents when the trigger was created.
+    created_at: DateTime<Utc>,
+
+    /// A time value given in ISO8601 combined date and time format that
+    /// represents when the trigger was last updated.
+    #[serde(default)]
+    updated_at: Option<DateTime<Utc>>,
+}
+
+/// Fields which exists inside function triggers.
+pub mod function_namespace_fields {
+    use chrono::{DateTime, Utc};
+    use serde::Deserialize;
+    use serde::Serialize;
+    use serde_json::Value;
+
+    /// The schedule of a trigger.
+    #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+    pub struct ScheduledDetails {
+        /// A cron expression, such as `*/5 * * * *`.
+        pub cron: String,
+        /// The body the function is invoked with.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub body: Option<Value>,
+    }
+
+    /// The runs of a trigger.
+    #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct ScheduledRuns {
+        #[serde(default)]
+        pub last_run_at: Option<DateTime<Utc>>,
+        #[serde(default)]
+        pub next_run_at: Option<DateTime<Utc>>,
+    }
+}
+
+impl FunctionNamespace {
+    /// `label` must be unique per account.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/functions_create_namespace)
+    pub fn create<S>(region: S, label: S) -> FunctionNamespaceRequest<Create, FunctionNamespace>
+    where
+        S: AsRef<str> + Serialize + Display,
+    {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(FUNCTIONS_SEGMENT)
+            .push(NAMESPACES_SEGMENT);
+
+        let mut req = Request::new(url);
+        req.set_body(json!({
+            "region": region,
+            "label": label,
+        }));
+        req
+    }
+
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/functions_list_namespaces)
+    pub fn list() -> FunctionNamespaceRequest<List, Vec<FunctionNamespace>> {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(FUNCTIONS_SEGMENT)
+            .push(NAMESPACES_SEGMENT);
+
+        Request::new(url)
+    }
+
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/functions_get_namespace)
+    pub fn get<S: AsRef<str> + Display>(
+        namespace: S,
+    ) -> FunctionNamespaceRequest<Get, FunctionNamespace> {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(FUNCTIONS_SEGMENT)
+            .push(NAMESPACES_SEGMENT)
+            .push(namespace.as_ref());
+
+        Request::new(url)
+    }
+
+    /// Deletes the namespace along with its functions and triggers.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/functions_delete_namespace)
+    pub fn delete<S: AsRef<str> + Display>(namespace: S) -> FunctionNamespaceRequest<Delete, ()> {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(FUNCTIONS_SEGMENT)
+            .push(NAMESPACES_SEGMENT)
+            .push(namespace.as_ref());
+
+        Request::new(url)
+    }
+}
+
+impl FunctionNamespaceRequest<Get, FunctionNamespace> {
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/functions_list_triggers)
+    pub fn triggers(mut self) -> FunctionTriggerRequest<List, Vec<FunctionTrigger>> {
+        self.url_mut()
+            .path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(TRIGGERS_SEGMENT);
+
+        self.transmute()
+    }
+
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/functions_get_trigger)
+    pub fn trigger<S: AsRef<str> + Display>(
+        mut self,
+        name: S,
+    ) -> FunctionTriggerRequest<Get, FunctionTrigger> {
+        self.url_mut()
+            .path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(TRIGGERS_SEGMENT)
+            .push(name.as_ref());
+
+        self.transmute()
+    }
+
+    /// Invoke `function` on the schedule given by the cron expression `cron`.
+    /// The trigger is enabled unless `is_enabled(false)` is set.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/functions_create_trigger)
+    pub fn create_trigger<S>(
+        mut self,
+        name: S,
+        function: S,
+        cron: S,
+    ) -> FunctionTriggerRequest<Create, FunctionTrigger>
+    where
+        S: AsRef<str> + Serialize + Display,
+    {
+        self.url_mut()
+            .path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(TRIGGERS_SEGMENT);
+
+        self.set_body(json!({
+            "name": name,
+            "function": function,
+            "type": "SCHEDULED",
+            "is_enabled": true,
+            "scheduled_details": { "cron": cron },
+        }));
+
+        self.transmute()
+    }
+
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/functions_delete_trigger)
+    pub fn delete_trigger<S: AsRef<str> + Display>(
+        mut self,
+        name: S,
+    ) -> FunctionTriggerRequest<Delete, ()> {
+        self.url_mut()
+            .path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(TRIGGERS_SEGMENT)
+            .push(name.as_ref());
+
+        self.transmute()
+    }
+}
+
+impl FunctionTriggerRequest<Create, FunctionTrigger> {
+    /// Whether the trigger invokes the function. Defaults to `true`.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/functions_create_trigger)
+    pub fn is_enabled(mut self, val: bool) -> Self {
+        self.body_mut()["is_enabled"] = json!(val);
+        self
+    }
+
+    /// The body the function is invoked with.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/functions_create_trigger)
+    pub fn invoke_with<T: Serialize>(mut self, val: T) -> Self {
+        self.body_mut()["scheduled_details"]["body"] = json!(val);
+        self
+    }
+}
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FunctionNamespaceResponse {
+    namespace: FunctionNamespace,
+}
+
+impl HasResponse for FunctionNamespace {
+    type Response = FunctionNamespaceResponse;
+}
+
+impl HasValue for FunctionNamespaceResponse {
+    type Value = FunctionNamespace;
+
+    fn value(self) -> FunctionNamespace {
+        self.namespace
+    }
+}
+
+/// Response type returned from Digital Ocean.
+///
+/// **Note:** Namespaces are not paginated, so `links` and `meta` are empty.
+#[derive(Deserialize, Serialize, Debug, Clone, Getters)]
+pub struct FunctionNamespaceListResponse {
+    namespaces: Vec<FunctionNamespace>,
+    #[serde(default)]
+    #[get = "pub"]
+    links: ApiLinks,
+    #[serde(default)]
+    #[get = "pub"]
+    meta: ApiMeta,
+}
+
+impl HasResponse for Vec<FunctionNamespace> {
+    type Response = FunctionNamespaceListResponse;
+}
+
+impl HasPagination for FunctionNamespaceListResponse {
+    fn next_page(&self) -> Option<Url> {
+        self.links.next()
+    }
+}
+
+impl HasValue for FunctionNamespaceListResponse {
+    type Value = Vec<FunctionNamespace>;
+
+    fn value(self) -> Vec<FunctionNamespace> {
+        self.namespaces
+    }
+}
+
+impl IntoIterator for FunctionNamespaceListResponse {
+    type Item = FunctionNamespace;
+    type IntoIter = std::vec::IntoIter<FunctionNamespace>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.namespaces.into_iter()
+    }
+}
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FunctionTriggerResponse {
+    trigger: FunctionTrigger,
+}
+
+impl HasResponse for FunctionTrigger {
+    type Response = FunctionTriggerResponse;
+}
+
+impl HasValue for FunctionTriggerResponse {
+    type Value = FunctionTrigger;
+
+    fn value(self) -> FunctionTrigger {
+        self.trigger
+    }
+}
+
+/// Response type returned from Digital Ocean.
+///
+/// **Note:** Triggers are not paginated, so `links` and `meta` are empty.
+#[derive(Deserialize, Serialize, Debug, Clone, Getters)]
+pub struct FunctionTriggerListResponse {
+    triggers: Vec<FunctionTrigger>,
+    #[serde(default)]
+    #[get = "pub"]
+    links: ApiLinks,
+    #[serde(default)]
+    #[get = "pub"]
+    meta: ApiMeta,
+}
+
+impl HasResponse for Vec<FunctionTrigger> {
+    type Response = FunctionTriggerListResponse;
+}
+
+impl HasPagination for FunctionTriggerListResponse {
+    fn next_page(&self) -> Option<Url> {
+        self.links.next()
+    }
+}
+
+impl HasValue for FunctionTriggerListResponse {
+    type Value = Vec<FunctionTrigger>;
+
+    fn value(self) -> Vec<FunctionTrigger> {
+        self.triggers
+    }
+}
+
+impl IntoIterator for FunctionTriggerListResponse {
+    type Item = FunctionTrigger;
+    type IntoIter = std::vec::IntoIter<FunctionTrigger>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.triggers.into_iter()
+    }
+}
@@ -1,14 +1,16 @@
 use super::{ApiLinks, ApiMeta};
 use super::{HasPagination, HasResponse, HasValue};
+use super::{Region, RegionFeature};
+use crate::error::Error;
 use crate::method::{Delete, Get, List, Update};
 use crate::request::ImageRequest;
-use crate::request::Request;
-use crate::{ROOT_URL, STATIC_URL_ERROR};
+use crate::request::{Executable, Request};
+use crate::{DigitalOcean, ROOT_URL, STATIC_URL_ERROR};
 use chrono::{DateTime, Utc};
 use getset::{Getters, Setters};
 use serde::Deserialize;
 use serde::Serialize;
-use std::fmt::Display;
+use std::fmt::{self, Display};
 use url::Url;
 
 const IMAGES_SEGMENT: &str = "images";
@@ -38,11 +40,15 @@ pub struct Image {
     ///
     /// *Note:* Since `type` is a keyword in Rust `kind` is used instead.
     #[serde(rename = "type")]
-    kind: String,
+    kind: ImageType,
     // 'type' is reserved in Rust.
     /// This attribute describes the base distribution used for this image.
     distribution: String,
 
+    /// The current state of the image.
+    #[serde(default)]
+    status: Option<ImageStatus>,
+
     /// A uniquely identifying string that is associated with each of the
     /// DigitalOcean-provided public images. These can be used to reference
     /// a public image as an alternative to the numeric id.
@@ -68,6 +74,95 @@ pub struct Image {
     created_at: DateTime<Utc>,
 }
 
+/// The `type` of an image.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageType {
+    /// A snapshot taken of a Droplet or volume.
+    Snapshot,
+    /// An automatic backup of a Droplet.
+    Backup,
+    /// A custom image imported by the user.
+    Custom,
+    /// A public Linux distribution.
+    Distribution,
+    /// A public one-click application image.
+    Application,
+    /// A type this crate does not know about yet.
+    #[serde(other)]
+    Unknown,
+}
+
+/// The public images `image_type()` can restrict an image list to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageListType {
+    /// Public Linux distributions.
+    Distribution,
+    /// Public one-click application images.
+    Application,
+}
+
+impl ImageListType {
+    /// The value of the `type` query parameter.
+    fn as_str(self) -> &'static str {
+        match self {
+            ImageListType::Distribution => "distribution",
+            ImageListType::Application => "application",
+        }
+    }
+}
+
+/// The `status` of an image.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageStatus {
+    /// The image has just been created and is not yet being processed.
+    #[serde(rename = "NEW")]
+    New,
+    /// The image can be used to create Droplets.
+    Available,
+    /// The image is still being imported or processed.
+    Pending,
+    /// The image has been deleted.
+    Deleted,
+    /// The image has been retired and can no longer be used.
+    Retired,
+    /// A status this crate does not know about yet.
+    #[serde(other)]
+    Unknown,
+}
+
+/// Identifies the image a Droplet is created from.
+///
+/// Public images are usually referenced by their slug, while snapshots,
+/// backups and custom images can only be referenced by their numeric id.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum ImageSource {
+    /// The slug of a public image, for example "ubuntu-20-04-x64".
+    Slug(String),
+    /// The id of an image, snapshot or backup.
+    Id(usize),
+}
+
+impl From<usize> for ImageSource {
+    fn from(id: usize) -> Self {
+        ImageSource::Id(id)
+    }
+}
+
+impl From<String> for ImageSource {
+    fn from(slug: String) -> Self {
+        ImageSource::Slug(slug)
+    }
+}
+
+impl From<&str> for ImageSource {
+    fn from(slug: &str) -> Self {
+        ImageSource::Slug(slug.to_string())
+    }
+}
+
 impl Image {
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-all-images)
     pub fn list() -> ImageRequest<List, Vec<Image>> {
@@ -81,26 +176,12 @@ impl Image {
 
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-all-distribution-images)
     pub fn distributions() -> ImageRequest<List, Vec<Image>> {
-        let mut url = ROOT_URL.clone();
-        url.path_segments_mut()
-            .expect(STATIC_URL_ERROR)
-            .push(IMAGES_SEGMENT);
-
-        url.query_pairs_mut().append_pair("type", "distribution");
-
-        Request::new(url)
+        Image::list().image_type(ImageListType::Distribution)
     }
 
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-all-application-images)
     pub fn applications() -> ImageRequest<List, Vec<Image>> {
-        let mut url = ROOT_URL.clone();
-        url.path_segments_mut()
-            .expect(STATIC_URL_ERROR)
-            .push(IMAGES_SEGMENT);
-
-        url.query_pairs_mut().append_pair("type", "application");
-
-        Request::new(url)
+        Image::list().image_type(ImageListType::Application)
     }
 
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-a-user-s-images)
@@ -115,6 +196,30 @@ impl Image {
         Request::new(url)
     }
 
+    /// Check whether the image can be transferred to the region `region_slug`
+    /// before starting a [`transfer()`](../request/type.ImageRequest.html#method.transfer).
+    ///
+    /// This is the case when one of the regions the image is in offers
+    /// `image_transfer` and the destination region exists and is available.
+    pub async fn can_transfer_to<S: AsRef<str>>(
+        instance: &DigitalOcean,
+        image_id: usize,
+        region_slug: S,
+    ) -> Result<bool, Error> {
+        let image = Image::get(image_id).execute(instance).await?;
+        let regions = Region::list().execute(instance).await?;
+
+        let source_supports_transfer = regions.iter().any(|region| {
+            image.regions.contains(region.slug())
+                && region.has_feature(RegionFeature::ImageTransfer)
+        });
+        let destination_available = regions
+            .iter()
+            .any(|region| region.slug() == region_slug.as_ref() && *region.available());
+
+        Ok(source_supports_transfer && destination_available)
+    }
+
     /// `id` is either an `id` (numeric) or a `slug` (string).
     ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#retrieve-an-existing-image-by-id)
@@ -155,6 +260,32 @@ impl Image {
     }
 }
 
+impl ImageRequest<List, Vec<Image>> {
+    /// Only list public images of the given type. Applied by the API, so it
+    /// composes with `limit()` and pagination.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/images_list)
+    pub fn image_type(mut self, kind: ImageListType) -> Self {
+        self.url_mut()
+            .query_pairs_mut()
+            .append_pair("type", kind.as_str());
+
+        self
+    }
+
+    /// Only list the images with the given tag. Applied by the API, so it
+    /// composes with `limit()` and pagination.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/images_list)
+    pub fn tag<S: AsRef<str>>(mut self, name: S) -> Self {
+        self.url_mut()
+            .query_pairs_mut()
+            .append_pair("tag_name", name.as_ref());
+
+        self
+    }
+}
+
 impl ImageRequest<Update, Image> {
     /// The new name that you would like to use for the image.
     ///
@@ -165,6 +296,12 @@ impl ImageRequest<Update, Image> {
     }
 }
 
+impl fmt::Display for Image {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "image {:?} ({})", self.name, self.id)
+    }
+}
+
 /// Response type returned from Digital Ocean.
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ImageResponse {
@@ -184,10 +321,12 @@ impl HasValue for ImageResponse {
 }
 
 /// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Getters)]
 pub struct ImageListResponse {
     images: Vec<Image>,
+    #[get = "pub"]
     links: ApiLinks,
+    #[get = "pub"]
     meta: ApiMeta,
 }
 
@@ -208,3 +347,12 @@ impl HasValue for ImageListResponse {
         self.images
     }
 }
+
+impl IntoIterator for ImageListResponse {
+    type Item = Image;
+    type IntoIter = std::vec::IntoIter<Image>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.images.into_iter()
+    }
+}
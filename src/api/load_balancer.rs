@@ -1,23 +1,58 @@
-use self::load_balancer_fields::{ForwardingRule, HealthCheck, StickySessions};
-use super::Region;
-use super::{ApiLinks, ApiMeta};
+use self::load_balancer_fields::{
+    ForwardingRule, HealthCheck, LbAlgorithm, LbSize, StickySessions,
+};
+use super::{ApiLinks, ApiMeta, BatchResult};
+use super::{Certificate, Region};
 use super::{HasPagination, HasResponse, HasValue};
+use crate::error::Error;
 use crate::method::{Create, Delete, Get, List, Update};
 use crate::request::LoadBalancerRequest;
-use crate::request::Request;
-use crate::{ROOT_URL, STATIC_URL_ERROR};
-use chrono::{DateTime, Utc};
+use crate::request::{Executable, Request};
+use crate::{DigitalOcean, ROOT_URL, STATIC_URL_ERROR};
+use chrono::{DateTime, TimeDelta, Utc};
 use getset::{Getters, Setters};
 use serde::Deserialize;
 use serde::Serialize;
-use std::fmt::Display;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
 use std::net::IpAddr;
+use std::time::Duration;
 use url::Url;
 
 const LOAD_BALANCERS_SEGMENT: &str = "load_balancers";
 const DROPLETS_SEGMENT: &str = "droplets";
 const FORWARDING_RULES_SEGMENT: &str = "forwarding_rules";
 
+/// The attributes a Load Balancer update replaces, carried over by `patch()`
+/// so that they are not reset. `region` is handled separately.
+const UPDATE_ATTRIBUTES: &[&str] = &[
+    "name",
+    "size_unit",
+    "size",
+    "algorithm",
+    "forwarding_rules",
+    "health_check",
+    "sticky_sessions",
+    "redirect_http_to_https",
+    "enable_proxy_protocol",
+    "enable_backend_keepalive",
+    "http_idle_timeout_seconds",
+    "vpc_uuid",
+    "project_id",
+    "disable_lets_encrypt_dns_records",
+    "firewall",
+    "droplet_ids",
+    "tag",
+    "network",
+    "network_stack",
+    "type",
+    "domains",
+    "glb_settings",
+    "target_load_balancer_ids",
+    "tls_cipher_policy",
+];
+
 /// Load Balancers provide a way to distribute traffic across multiple
 /// Droplets.
 ///
@@ -224,6 +259,27 @@ pub mod load_balancer_fields {
         /// the sticky sessions type.
         pub cookie_ttl_seconds: Option<String>,
     }
+
+    /// The load balancing algorithm used to determine which backend Droplet
+    /// will be selected by a client.
+    #[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+    #[serde(rename_all = "snake_case")]
+    pub enum LbAlgorithm {
+        RoundRobin,
+        LeastConnections,
+    }
+
+    /// The fixed size of a Load Balancer. Newer Load Balancers are sized in
+    /// nodes with `size_unit` instead.
+    #[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum LbSize {
+        #[serde(rename = "lb-small")]
+        Small,
+        #[serde(rename = "lb-medium")]
+        Medium,
+        #[serde(rename = "lb-large")]
+        Large,
+    }
 }
 
 impl LoadBalancer {
@@ -306,16 +362,80 @@ impl LoadBalancer {
     }
 }
 
+impl LoadBalancer {
+    /// Find the certificates used by Load Balancers which expire within
+    /// `within` from now, including those which have already expired.
+    ///
+    /// Every Load Balancer and expiring certificate it uses is reported as a
+    /// succeeded pair. Each certificate is only fetched once, one which cannot
+    /// be fetched, for example because it was deleted meanwhile, is reported
+    /// as failed by its id without stopping the audit.
+    pub async fn audit_certificates(
+        instance: &DigitalOcean,
+        within: Duration,
+    ) -> Result<BatchResult<(LoadBalancer, Certificate), String>, Error> {
+        let load_balancers = LoadBalancer::list().execute(instance).await?;
+        let now = Utc::now();
+        let deadline = TimeDelta::from_std(within)
+            .ok()
+            .and_then(|within| now.checked_add_signed(within))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+
+        let mut batch = BatchResult::default();
+        let mut certificates = BTreeMap::new();
+        for id in load_balancers
+            .iter()
+            .flat_map(LoadBalancer::certificate_ids)
+        {
+            if !certificates.contains_key(id) {
+                let certificate = Certificate::get(id).execute(instance).await;
+                match certificate {
+                    Ok(certificate) => {
+                        certificates.insert(id.to_string(), Some(certificate));
+                    }
+                    Err(e) => {
+                        certificates.insert(id.to_string(), None);
+                        batch.failed.push((id.to_string(), e));
+                    }
+                }
+            }
+        }
+
+        for load_balancer in &load_balancers {
+            let mut ids: Vec<&str> = load_balancer.certificate_ids().collect();
+            ids.sort_unstable();
+            ids.dedup();
+
+            for id in ids {
+                if let Some(certificate) = &certificates[id]
+                    && *certificate.not_after() <= deadline
+                {
+                    batch
+                        .succeeded
+                        .push((load_balancer.clone(), certificate.clone()));
+                }
+            }
+        }
+
+        Ok(batch)
+    }
+
+    /// The ids of the certificates referenced by the forwarding rules.
+    fn certificate_ids(&self) -> impl Iterator<Item = &str> {
+        self.forwarding_rules
+            .iter()
+            .filter_map(|rule| rule.certificate_id.as_deref())
+            .filter(|id| !id.is_empty())
+    }
+}
+
 impl LoadBalancerRequest<Create, LoadBalancer> {
     /// The load balancing algorithm used to determine which backend Droplet
-    /// will be selected by a client. It must be either "round_robin" or
-    /// "least_connections". The default value is "round_robin".
+    /// will be selected by a client. The default value is
+    /// `LbAlgorithm::RoundRobin`.
     ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-load-balancer)
-    pub fn algorithm<S>(mut self, val: S) -> LoadBalancerRequest<Create, LoadBalancer>
-    where
-        S: Display + Serialize,
-    {
+    pub fn algorithm(mut self, val: LbAlgorithm) -> LoadBalancerRequest<Create, LoadBalancer> {
         self.body_mut()["algorithm"] = json!(val);
         self
     }
@@ -428,6 +548,49 @@ impl LoadBalancerRequest<Create, LoadBalancer> {
         self
     }
 
+    /// The size of the Load Balancer.
+    ///
+    /// **Note:** Not intended to be used alongside the `size_unit` function.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/loadBalancers_create)
+    pub fn size(mut self, val: LbSize) -> LoadBalancerRequest<Create, LoadBalancer> {
+        self.body_mut()["size"] = json!(val);
+        self
+    }
+
+    /// The number of nodes in the Load Balancer, between 1 and 100.
+    ///
+    /// **Note:** Not intended to be used alongside the `size` function.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/loadBalancers_create)
+    pub fn size_unit(mut self, val: usize) -> LoadBalancerRequest<Create, LoadBalancer> {
+        self.body_mut()["size_unit"] = json!(val);
+        self
+    }
+
+    /// A boolean value indicating whether PROXY Protocol is in use. Default
+    /// value is false.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/loadBalancers_create)
+    pub fn enable_proxy_protocol(
+        mut self,
+        setting: bool,
+    ) -> LoadBalancerRequest<Create, LoadBalancer> {
+        self.body_mut()["enable_proxy_protocol"] = json!(setting);
+        self
+    }
+
+    /// The ID of the VPC where the Load Balancer will be located.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/loadBalancers_create)
+    pub fn vpc_uuid<S>(mut self, val: S) -> LoadBalancerRequest<Create, LoadBalancer>
+    where
+        S: AsRef<str> + Display + Serialize,
+    {
+        self.body_mut()["vpc_uuid"] = json!(val);
+        self
+    }
+
     /// The IDs of the Droplets to be assigned to the Load Balancer.
     ///
     /// **Note:** Not intended to be used alongside the `tag` function.
@@ -477,14 +640,11 @@ impl LoadBalancerRequest<Update, LoadBalancer> {
     }
 
     /// The load balancing algorithm used to determine which backend Droplet
-    /// will be selected by a client. It must be either "round_robin" or
-    /// "least_connections". The default value is "round_robin".
+    /// will be selected by a client. The default value is
+    /// `LbAlgorithm::RoundRobin`.
     ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#update-a-load-balancer)
-    pub fn algorithm<S>(mut self, val: S) -> LoadBalancerRequest<Update, LoadBalancer>
-    where
-        S: Display + Serialize,
-    {
+    pub fn algorithm(mut self, val: LbAlgorithm) -> LoadBalancerRequest<Update, LoadBalancer> {
         self.body_mut()["algorithm"] = json!(val);
         self
     }
@@ -623,6 +783,85 @@ impl LoadBalancerRequest<Update, LoadBalancer> {
 }
 
 impl LoadBalancerRequest<Get, LoadBalancer> {
+    /// Update the Load Balancer without resetting the attributes which are
+    /// not changed. The current configuration is fetched and prefilled into
+    /// an update, `f` then changes it with the usual setters before it is
+    /// sent.
+    ///
+    /// Setters add to the prefilled values where they would add to an empty
+    /// update, `forwarding_rule()` appends a rule to the existing ones. When
+    /// `f` changes the `droplets()` or the `tag()`, the other one is dropped
+    /// as they are mutually exclusive.
+    ///
+    /// **Note:** The current configuration is fetched as raw JSON, so every
+    /// attribute an update accepts is carried over, including those not
+    /// modelled on [`LoadBalancer`](../api/struct.LoadBalancer.html). The
+    /// update is not atomic, a change made by someone else between the fetch
+    /// and the update is lost.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#update-a-load-balancer)
+    pub async fn patch<F>(self, instance: &DigitalOcean, f: F) -> Result<LoadBalancer, Error>
+    where
+        F: FnOnce(
+            LoadBalancerRequest<Update, LoadBalancer>,
+        ) -> LoadBalancerRequest<Update, LoadBalancer>,
+    {
+        let mut update: LoadBalancerRequest<Update, LoadBalancer> =
+            Request::new(self.url().clone());
+        let raw = self.execute_raw(instance).await?;
+        let current = &raw["load_balancer"];
+
+        let mut body = serde_json::Map::new();
+        for attribute in UPDATE_ATTRIBUTES {
+            match current.get(*attribute) {
+                Some(Value::Null) | None => {}
+                Some(value) => {
+                    body.insert(attribute.to_string(), value.clone());
+                }
+            }
+        }
+
+        // The region is returned as an object but updated by its slug.
+        match &current["region"] {
+            Value::Object(region) => {
+                if let Some(slug) = region.get("slug") {
+                    body.insert("region".to_string(), slug.clone());
+                }
+            }
+            Value::Null => {}
+            slug => {
+                body.insert("region".to_string(), slug.clone());
+            }
+        }
+
+        // `size` is deprecated in favour of `size_unit`, both are returned
+        // but only one may be set.
+        if body.contains_key("size_unit") {
+            body.remove("size");
+        }
+
+        if current["tag"].as_str().unwrap_or_default().is_empty() {
+            body.remove("tag");
+        } else {
+            body.remove("droplet_ids");
+        }
+
+        update.set_body(Value::Object(body));
+
+        let before = update.body().clone();
+        let mut update = f(update);
+
+        if let Some(body) = update.body_mut().as_object_mut() {
+            if body.get("droplet_ids") != before.get("droplet_ids") {
+                body.remove("tag");
+            } else if body.get("tag") != before.get("tag") {
+                body.remove("droplet_ids");
+            }
+        }
+
+        update.execute(instance).await
+    }
+
     /// Add droplets (by id) to the load balancer.
     ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#add-droplets-to-a-load-balancer)
@@ -722,6 +961,12 @@ impl LoadBalancerRequest<Get, LoadBalancer> {
     }
 }
 
+impl fmt::Display for LoadBalancer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "load balancer {:?} ({})", self.name, self.id)
+    }
+}
+
 /// Response type returned from Digital Ocean.
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct LoadBalancerResponse {
@@ -741,10 +986,12 @@ impl HasValue for LoadBalancerResponse {
 }
 
 /// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Getters)]
 pub struct LoadBalancerListResponse {
     load_balancers: Vec<LoadBalancer>,
+    #[get = "pub"]
     links: ApiLinks,
+    #[get = "pub"]
     meta: ApiMeta,
 }
 
@@ -765,3 +1012,12 @@ impl HasValue for LoadBalancerListResponse {
         self.load_balancers
     }
 }
+
+impl IntoIterator for LoadBalancerListResponse {
+    type Item = LoadBalancer;
+    type IntoIter = std::vec::IntoIter<LoadBalancer>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.load_balancers.into_iter()
+    }
+}
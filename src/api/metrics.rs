@@ -0,0 +1,109 @@
+use self::metrics_fields::Series;
+use super::{HasResponse, HasValue};
+use crate::method::Get;
+use crate::request::MetricsRequest;
+use crate::request::Request;
+use crate::{ROOT_URL, STATIC_URL_ERROR};
+use chrono::{DateTime, TimeDelta, Utc};
+use getset::{Getters, Setters};
+use serde::Deserialize;
+use serde::Serialize;
+use std::time::Duration;
+
+const MONITORING_SEGMENT: &str = "monitoring";
+const METRICS_SEGMENT: &str = "metrics";
+const DROPLET_SEGMENT: &str = "droplet";
+const CPU_SEGMENT: &str = "cpu";
+
+/// Time series collected by the monitoring agent of a Droplet.
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#tag/Monitoring)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct Metrics {
+    /// The shape of `result`, "matrix" for every Droplet metric.
+    #[serde(rename = "resultType")]
+    result_type: String,
+
+    /// One series for each combination of labels, such as each CPU mode.
+    result: Vec<Series>,
+}
+
+/// Fields which exists inside Metrics.
+pub mod metrics_fields {
+    use serde::Deserialize;
+    use serde::Serialize;
+    use std::collections::BTreeMap;
+
+    /// This exists in the `result` field of metrics.
+    #[derive(Deserialize, Serialize, Debug, Clone)]
+    pub struct Series {
+        /// The labels identifying the series, for example `mode`.
+        pub metric: BTreeMap<String, String>,
+        /// Pairs of a unix timestamp and the value sampled at that time.
+        pub values: Vec<(f64, String)>,
+    }
+}
+
+impl Metrics {
+    /// The CPU usage of the Droplet `host_id` between `start` and `end`.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/monitoring_get_DropletCpuMetrics)
+    pub fn droplet_cpu(
+        host_id: usize,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> MetricsRequest<Get, Metrics> {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(MONITORING_SEGMENT)
+            .push(METRICS_SEGMENT)
+            .push(DROPLET_SEGMENT)
+            .push(CPU_SEGMENT);
+
+        url.query_pairs_mut()
+            .append_pair("host_id", &host_id.to_string())
+            .append_pair("start", &start.timestamp().to_string())
+            .append_pair("end", &end.timestamp().to_string());
+
+        Request::new(url)
+    }
+
+    /// The CPU usage of the Droplet `host_id` over the last `duration`, up to
+    /// now.
+    pub fn droplet_cpu_last(host_id: usize, duration: Duration) -> MetricsRequest<Get, Metrics> {
+        let (start, end) = last(duration);
+        Metrics::droplet_cpu(host_id, start, end)
+    }
+}
+
+/// The bounds of the window of `duration` ending now.
+fn last(duration: Duration) -> (DateTime<Utc>, DateTime<Utc>) {
+    let end = Utc::now();
+    let start = TimeDelta::from_std(duration)
+        .ok()
+        .and_then(|duration| end.checked_sub_signed(duration))
+        .unwrap_or(DateTime::<Utc>::MIN_UTC);
+
+    (start, end)
+}
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct MetricsResponse {
+    status: String,
+    data: Metrics,
+}
+
+impl HasResponse for Metrics {
+    type Response = MetricsResponse;
+}
+
+impl HasValue for MetricsResponse {
+    type Value = Metrics;
+
+    fn value(self) -> Metrics {
+        self.data
+    }
+}
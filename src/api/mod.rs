@@ -2,25 +2,35 @@
 
 mod account;
 mod action;
+mod batch;
 mod certificate;
+mod cost;
 mod custom_image;
 mod domain;
 mod domain_record;
 mod droplet;
 mod droplet_action;
+mod firewall;
 mod floating_ip;
 mod floating_ip_action;
+mod function_namespace;
 mod image;
 mod image_action;
 mod load_balancer;
+mod metrics;
+mod project;
 mod region;
 mod size;
+mod slug;
 mod snapshot;
+mod spaces_key;
 mod ssh_key;
 mod tag;
+mod urn;
 mod volume;
 mod volume_action;
 
+use getset::Getters;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use url::Url;
@@ -54,28 +64,52 @@ mod url_option_serde {
     }
 }
 
-pub use self::account::Account;
+pub use self::account::{account_fields, Account};
 pub use self::action::Action;
-pub use self::certificate::Certificate;
+pub use self::batch::BatchResult;
+pub use self::certificate::{Certificate, CertificateType};
+pub use self::cost::{LOAD_BALANCER_PRICE, VOLUME_PRICE_PER_GIB};
 pub use self::custom_image::CustomImage;
 pub use self::domain::Domain;
-pub use self::domain_record::DomainRecord;
-pub use self::droplet::{droplet_fields, Droplet};
+pub use self::domain_record::{diff_records, DomainRecord, NewDomainRecord, RecordDiff};
+pub use self::droplet::{droplet_fields, Droplet, DropletFeature, DropletSummary};
+pub use self::firewall::{firewall_fields, Firewall};
 pub use self::floating_ip::FloatingIp;
-pub use self::image::Image;
+pub use self::function_namespace::{function_namespace_fields, FunctionNamespace, FunctionTrigger};
+pub use self::image::{Image, ImageListType, ImageSource, ImageStatus, ImageType};
 pub use self::load_balancer::{load_balancer_fields, LoadBalancer};
-pub use self::region::Region;
-pub use self::size::Size;
+pub use self::metrics::{metrics_fields, Metrics};
+pub use self::project::Project;
+pub use self::region::{Region, RegionFeature, RegionInventory};
+pub use self::size::{size_fields, Size};
+pub use self::slug::{RegionSlug, SizeSlug, SlugCatalog};
 pub use self::snapshot::Snapshot;
-pub use self::ssh_key::SshKey;
+pub use self::spaces_key::{spaces_key_fields, SpacesKey};
+pub use self::ssh_key::{SshKey, SshKeyRef};
 pub use self::tag::Tag;
-pub use self::volume::Volume;
+pub use self::urn::{HasUrn, ResolvedResource, Urn};
+pub use self::volume::{volume_fields, Volume};
 
 // Defined in https://developers.digitalocean.com/documentation/v2/#links
 pub const MAX_PER_PAGE: usize = 200;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
-struct ApiLinks {
+/// The most resources tagged or untagged in a single request by
+/// `add_resources()` and `remove_resources()`.
+///
+/// The API reference does not document the largest batch it accepts, but
+/// large batches are refused with `422 Unprocessable Entity`, so resources
+/// are sent in conservatively small chunks.
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/tags_assign_resources)
+pub const MAX_TAG_RESOURCES: usize = 50;
+
+/// The `links` of a list response, pointing at the other pages of the list.
+///
+/// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#links)
+#[derive(Deserialize, Serialize, Debug, Clone, Default, Getters)]
+#[get = "pub"]
+pub struct ApiLinks {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pages: Option<ApiPages>,
 }
 
@@ -88,26 +122,67 @@ impl ApiLinks {
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
-struct ApiPages {
-    #[serde(with = "url_option_serde", default)]
+/// The urls of the pages around the current one. Only the pages which exist
+/// are present, the first page has no `prev` and the last one no `next`.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, Getters)]
+#[get = "pub"]
+pub struct ApiPages {
+    #[serde(
+        with = "url_option_serde",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     prev: Option<Url>,
 
-    #[serde(with = "url_option_serde", default)]
+    #[serde(
+        with = "url_option_serde",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     first: Option<Url>,
 
-    #[serde(with = "url_option_serde", default)]
+    #[serde(
+        with = "url_option_serde",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     next: Option<Url>,
 
-    #[serde(with = "url_option_serde", default)]
+    #[serde(
+        with = "url_option_serde",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     last: Option<Url>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
-struct ApiMeta {
+/// The `meta` of a list response.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, Getters)]
+#[get = "pub"]
+pub struct ApiMeta {
+    /// The number of items in the whole list, across every page.
     total: usize,
 }
 
+/// Join the pages of a list walked by hand, such as with
+/// [`cursor()`](../request/struct.Request.html#method.cursor), keeping their
+/// order.
+pub fn merge_pages<V>(pages: Vec<Vec<V>>) -> Vec<V> {
+    pages.into_iter().flatten().collect()
+}
+
+/// Join separate lists, such as the Droplets of several tags, along with
+/// their `meta`. The totals of the lists are summed.
+pub fn concat<V>(lists: Vec<(Vec<V>, ApiMeta)>) -> (Vec<V>, ApiMeta) {
+    let mut meta = ApiMeta::default();
+    let mut pages = Vec::with_capacity(lists.len());
+    for (values, list_meta) in lists {
+        meta.total += list_meta.total;
+        pages.push(values);
+    }
+    (merge_pages(pages), meta)
+}
+
 pub trait HasPagination {
     fn next_page(&self) -> Option<Url>;
 }
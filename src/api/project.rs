@@ -0,0 +1,92 @@
+use super::{HasResponse, HasValue};
+use crate::error::Error;
+use crate::method::Get;
+use crate::request::{Executable, ProjectRequest, Request};
+use crate::DigitalOcean;
+use crate::{ROOT_URL, STATIC_URL_ERROR};
+use chrono::{DateTime, Utc};
+use getset::{Getters, Setters};
+use serde::Deserialize;
+use serde::Serialize;
+
+const PROJECTS_SEGMENT: &str = "projects";
+const DEFAULT_SEGMENT: &str = "default";
+
+/// Projects group resources, new resources are assigned to the default
+/// project unless another one is given.
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#tag/Projects)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct Project {
+    /// The unique universal identifier of the project.
+    id: String,
+
+    /// The unique universal identifier of the owner of the project.
+    owner_uuid: String,
+
+    /// The id of the owner of the project.
+    owner_id: usize,
+
+    /// The name of the project. Unique per account.
+    name: String,
+
+    /// The description of the project.
+    #[serde(default)]
+    description: String,
+
+    /// The purpose of the project, such as "Web Application".
+    #[serde(default)]
+    purpose: String,
+
+    /// The environment of the project, one of "Development", "Staging" or
+    /// "Production".
+    #[serde(default)]
+    environment: Option<String>,
+
+    /// Whether the project is the default one of the account.
+    is_default: bool,
+
+    /// A time value given in ISO8601 combined date and time format that
+    /// represents when the project was created.
+    created_at: DateTime<Utc>,
+
+    /// A time value given in ISO8601 combined date and time format that
+    /// represents when the project was last updated.
+    updated_at: DateTime<Utc>,
+}
+
+impl Project {
+    /// Retrieve the default project of the account, without listing every
+    /// project to find it.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/projects_get_default)
+    pub async fn default(instance: &DigitalOcean) -> Result<Project, Error> {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(PROJECTS_SEGMENT)
+            .push(DEFAULT_SEGMENT);
+
+        let request: ProjectRequest<Get, Project> = Request::new(url);
+        request.execute(instance).await
+    }
+}
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ProjectResponse {
+    project: Project,
+}
+
+impl HasResponse for Project {
+    type Response = ProjectResponse;
+}
+
+impl HasValue for ProjectResponse {
+    type Value = Project;
+
+    fn value(self) -> Project {
+        self.project
+    }
+}
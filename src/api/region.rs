@@ -1,9 +1,12 @@
 use super::{ApiLinks, ApiMeta};
+use super::{Droplet, FloatingIp, LoadBalancer, Volume};
 use super::{HasPagination, HasResponse, HasValue};
+use crate::error::Error;
 use crate::method::List;
 use crate::request::RegionRequest;
-use crate::request::Request;
-use crate::{ROOT_URL, STATIC_URL_ERROR};
+use crate::request::{Executable, Request};
+use crate::{DigitalOcean, ROOT_URL, STATIC_URL_ERROR};
+use futures_util::future::try_join4;
 use getset::{Getters, Setters};
 use serde::Deserialize;
 use serde::Serialize;
@@ -43,7 +46,43 @@ pub struct Region {
     features: Vec<String>,
 }
 
+/// Everything deployed in a region, see
+/// [`Region::inventory()`](struct.Region.html#method.inventory).
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct RegionInventory {
+    droplets: Vec<Droplet>,
+    volumes: Vec<Volume>,
+    load_balancers: Vec<LoadBalancer>,
+    floating_ips: Vec<FloatingIp>,
+}
+
+/// A feature a region may offer, as listed in its `features`.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RegionFeature {
+    Backups,
+    Ipv6,
+    Metadata,
+    InstallAgent,
+    Storage,
+    ImageTransfer,
+    PrivateNetworking,
+    ServerId,
+    ManagementNetworking,
+    /// A feature this crate does not know about yet.
+    #[serde(other)]
+    Unknown,
+}
+
 impl Region {
+    /// Whether the region offers `feature`. Never true for
+    /// `RegionFeature::Unknown`.
+    pub fn has_feature(&self, feature: RegionFeature) -> bool {
+        let name = json!(feature);
+        self.features.iter().any(|f| name == *f)
+    }
+
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-all-regions)
     pub fn list() -> RegionRequest<List, Vec<Region>> {
         let mut url = ROOT_URL.clone();
@@ -53,13 +92,84 @@ impl Region {
 
         Request::new(url)
     }
+
+    /// Find the region with the given slug, or `None` if there is no such
+    /// region.
+    ///
+    /// **Note:** The API cannot retrieve a single region, so this lists every
+    /// region and searches the result.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-all-regions)
+    pub async fn get<S: AsRef<str>>(
+        instance: &DigitalOcean,
+        slug: S,
+    ) -> Result<Option<Region>, Error> {
+        let regions = Region::list().execute(instance).await?;
+
+        Ok(regions
+            .into_iter()
+            .find(|region| region.slug == slug.as_ref()))
+    }
+
+    /// List the Droplets, volumes, Load Balancers and Floating IPs in the
+    /// region `slug`. The lists are fetched concurrently.
+    ///
+    /// **Note:** Only volumes can be filtered by region by the API, the other
+    /// resources are listed in full and filtered afterwards. Databases are
+    /// not included as this crate does not support them yet.
+    pub async fn inventory<S: AsRef<str>>(
+        instance: &DigitalOcean,
+        slug: S,
+    ) -> Result<RegionInventory, Error> {
+        let slug = slug.as_ref();
+
+        let droplets = {
+            let slug = slug.to_string();
+            Droplet::list().filter(move |droplet| droplet.region().slug == slug)
+        };
+        let load_balancers = {
+            let slug = slug.to_string();
+            LoadBalancer::list().filter(move |lb| lb.region().slug == slug)
+        };
+        let floating_ips = {
+            let slug = slug.to_string();
+            FloatingIp::list().filter(move |ip| ip.region().slug == slug)
+        };
+
+        let (droplets, volumes, load_balancers, floating_ips) = try_join4(
+            droplets.execute(instance),
+            Volume::list().region(slug).execute(instance),
+            load_balancers.execute(instance),
+            floating_ips.execute(instance),
+        )
+        .await?;
+
+        Ok(RegionInventory {
+            droplets,
+            volumes,
+            load_balancers,
+            floating_ips,
+        })
+    }
+}
+
+impl RegionRequest<List, Vec<Region>> {
+    /// Only keep the regions where new Droplets can currently be created.
+    ///
+    /// **Note:** The API does not filter regions by availability, this is
+    /// applied after all pages have been retrieved.
+    pub fn only_available(self) -> Self {
+        self.filter(|region| region.available)
+    }
 }
 
 /// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Getters)]
 pub struct RegionListResponse {
     regions: Vec<Region>,
+    #[get = "pub"]
     links: ApiLinks,
+    #[get = "pub"]
     meta: ApiMeta,
 }
 
@@ -80,3 +190,12 @@ impl HasValue for RegionListResponse {
         self.regions
     }
 }
+
+impl IntoIterator for RegionListResponse {
+    type Item = Region;
+    type IntoIter = std::vec::IntoIter<Region>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.regions.into_iter()
+    }
+}
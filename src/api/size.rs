@@ -1,3 +1,4 @@
+use self::size_fields::{DiskInfo, GpuInfo};
 use super::{ApiLinks, ApiMeta};
 use super::{HasPagination, HasResponse, HasValue};
 use crate::method::List;
@@ -59,6 +60,54 @@ pub struct Size {
     /// An array containing the region slugs where this size is available for
     /// Droplet creates.
     regions: Vec<String>,
+
+    /// Details about the GPUs available to Droplets of this size. Only present
+    /// on GPU-capable sizes.
+    #[serde(default)]
+    gpu_info: Option<GpuInfo>,
+
+    /// Details about the disks available to Droplets of this size.
+    #[serde(default)]
+    disk_info: Vec<DiskInfo>,
+}
+
+/// Fields which exists inside Sizes.
+pub mod size_fields {
+    use serde::Deserialize;
+    use serde::Serialize;
+
+    /// This exists in the `gpu_info` field of a size.
+    #[derive(Deserialize, Serialize, Debug, Clone)]
+    pub struct GpuInfo {
+        /// The number of GPUs allocated to Droplets of this size.
+        pub count: usize,
+
+        /// The model of the GPU, for example "nvidia_h100".
+        pub model: String,
+
+        /// The amount of video RAM available on each GPU.
+        pub vram: Amount,
+    }
+
+    /// This exists in the `disk_info` field of a size.
+    #[derive(Deserialize, Serialize, Debug, Clone)]
+    pub struct DiskInfo {
+        /// The type of disk, either "local" or "scratch".
+        ///
+        /// *Note:* Since `type` is a keyword in Rust `kind` is used instead.
+        #[serde(rename = "type")]
+        pub kind: String,
+
+        /// The size of the disk.
+        pub size: Amount,
+    }
+
+    /// A quantity paired with its unit, as used by `gpu_info` and `disk_info`.
+    #[derive(Deserialize, Serialize, Debug, Clone)]
+    pub struct Amount {
+        pub amount: usize,
+        pub unit: String,
+    }
 }
 
 impl Size {
@@ -73,13 +122,41 @@ impl Size {
     }
 }
 
+impl SizeRequest<List, Vec<Size>> {
+    /// Sort the sizes from the cheapest to the most expensive monthly price.
+    ///
+    /// **Note:** The API returns sizes unsorted, this is applied after all
+    /// pages have been retrieved. With a `limit()` only the retrieved sizes
+    /// are sorted.
+    pub fn sorted_by_price(self) -> Self {
+        self.post_process(|mut sizes: Vec<Size>| {
+            sizes.sort_by(|a, b| a.price_monthly.total_cmp(&b.price_monthly));
+            sizes
+        })
+    }
+
+    /// Sort the sizes from the least to the most memory.
+    ///
+    /// **Note:** The API returns sizes unsorted, this is applied after all
+    /// pages have been retrieved. With a `limit()` only the retrieved sizes
+    /// are sorted.
+    pub fn sorted_by_memory(self) -> Self {
+        self.post_process(|mut sizes: Vec<Size>| {
+            sizes.sort_by_key(|size| size.memory);
+            sizes
+        })
+    }
+}
+
 // There is no signular size return.
 
 /// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Getters)]
 pub struct SizeListResponse {
     sizes: Vec<Size>,
+    #[get = "pub"]
     links: ApiLinks,
+    #[get = "pub"]
     meta: ApiMeta,
 }
 
@@ -100,3 +177,12 @@ impl HasValue for SizeListResponse {
         self.sizes
     }
 }
+
+impl IntoIterator for SizeListResponse {
+    type Item = Size;
+    type IntoIter = std::vec::IntoIter<Size>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.sizes.into_iter()
+    }
+}
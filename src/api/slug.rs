@@ -0,0 +1,159 @@
+use super::{Region, Size};
+use crate::error::Error;
+use crate::request::Executable;
+use crate::DigitalOcean;
+use futures_util::future::try_join;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt;
+
+/// The slug of a region, such as `nyc1`. Usable wherever a region slug is
+/// taken, for example in `Droplet::create()`.
+///
+/// `TryFrom<&str>` only checks the shape of the slug, a name followed by a
+/// number, which catches typos like `nyc`. Use
+/// [`SlugCatalog::region()`](struct.SlugCatalog.html#method.region) to also
+/// check that the region exists.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct RegionSlug(String);
+
+impl TryFrom<&str> for RegionSlug {
+    type Error = Error;
+
+    fn try_from(slug: &str) -> Result<Self, Error> {
+        let digits = slug.trim_start_matches(|c: char| c.is_ascii_lowercase());
+        let is_region = digits.len() < slug.len()
+            && !digits.is_empty()
+            && digits.chars().all(|c| c.is_ascii_digit());
+
+        if is_region {
+            Ok(RegionSlug(slug.to_string()))
+        } else {
+            Err(Error::InvalidRequest(format!(
+                "`{slug}` is not a region slug"
+            )))
+        }
+    }
+}
+
+impl AsRef<str> for RegionSlug {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for RegionSlug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// The slug of a Droplet size, such as `s-1vcpu-1gb`. Usable wherever a size
+/// slug is taken, for example in `Droplet::create()`.
+///
+/// `TryFrom<&str>` only checks the shape of the slug, lowercase words
+/// separated by dashes. Use
+/// [`SlugCatalog::size()`](struct.SlugCatalog.html#method.size) to also
+/// check that the size exists.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct SizeSlug(String);
+
+impl TryFrom<&str> for SizeSlug {
+    type Error = Error;
+
+    fn try_from(slug: &str) -> Result<Self, Error> {
+        let is_size = slug.split('-').all(|word| {
+            !word.is_empty()
+                && word
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        });
+
+        if is_size {
+            Ok(SizeSlug(slug.to_string()))
+        } else {
+            Err(Error::InvalidRequest(format!(
+                "`{slug}` is not a size slug"
+            )))
+        }
+    }
+}
+
+impl AsRef<str> for SizeSlug {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for SizeSlug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// The region and size slugs available to an account, to check slugs against
+/// before creating resources rather than have the API refuse them.
+///
+/// The catalog is a plain value fetched with the token of one client, keep it
+/// around to avoid fetching it again for every check.
+#[derive(Debug, Clone, Default)]
+pub struct SlugCatalog {
+    regions: HashSet<String>,
+    sizes: HashSet<String>,
+}
+
+impl SlugCatalog {
+    /// Fetch every region and size. The lists are fetched concurrently.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/regions_list)
+    pub async fn fetch(instance: &DigitalOcean) -> Result<Self, Error> {
+        let (regions, sizes) = try_join(
+            Region::list().execute(instance),
+            Size::list().execute(instance),
+        )
+        .await?;
+
+        Ok(SlugCatalog::new(
+            regions.iter().map(Region::slug),
+            sizes.iter().map(Size::slug),
+        ))
+    }
+
+    /// A catalog of the given slugs, for example to restrict which regions
+    /// and sizes may be used.
+    pub fn new<R, S>(regions: R, sizes: S) -> Self
+    where
+        R: IntoIterator,
+        R::Item: AsRef<str>,
+        S: IntoIterator,
+        S::Item: AsRef<str>,
+    {
+        SlugCatalog {
+            regions: regions
+                .into_iter()
+                .map(|s| s.as_ref().to_string())
+                .collect(),
+            sizes: sizes.into_iter().map(|s| s.as_ref().to_string()).collect(),
+        }
+    }
+
+    /// The region with the given slug, or `Error::InvalidRequest` when the
+    /// catalog does not list it.
+    pub fn region(&self, slug: &str) -> Result<RegionSlug, Error> {
+        let region = RegionSlug::try_from(slug)?;
+        if !self.regions.contains(slug) {
+            return Err(Error::InvalidRequest(format!("unknown region `{slug}`")));
+        }
+        Ok(region)
+    }
+
+    /// The size with the given slug, or `Error::InvalidRequest` when the
+    /// catalog does not list it.
+    pub fn size(&self, slug: &str) -> Result<SizeSlug, Error> {
+        let size = SizeSlug::try_from(slug)?;
+        if !self.sizes.contains(slug) {
+            return Err(Error::InvalidRequest(format!("unknown size `{slug}`")));
+        }
+        Ok(size)
+    }
+}
@@ -1,13 +1,18 @@
+use super::BatchResult;
+use super::Image;
 use super::{ApiLinks, ApiMeta};
 use super::{HasPagination, HasResponse, HasValue};
-use crate::method::{Delete, Get, List};
-use crate::request::Request;
+use crate::error::Error;
+use crate::method::{Create, Delete, Get, List};
 use crate::request::SnapshotRequest;
-use crate::{ROOT_URL, STATIC_URL_ERROR};
+use crate::request::{Executable, Request};
+use crate::{DigitalOcean, ROOT_URL, STATIC_URL_ERROR};
 use chrono::{DateTime, Utc};
+use futures_util::stream::{self, StreamExt};
 use getset::{Getters, Setters};
 use serde::Deserialize;
 use serde::Serialize;
+use std::fmt;
 use url::Url;
 
 const SNAPSHOT_SEGMENT: &str = "snapshots";
@@ -48,6 +53,10 @@ pub struct Snapshot {
 
     /// The billable size of the snapshot in gigabytes.
     size_gigabytes: f64,
+
+    /// The tags applied to the snapshot.
+    #[serde(default)]
+    tags: Vec<String>,
 }
 
 impl Snapshot {
@@ -99,21 +108,104 @@ impl Snapshot {
 
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#delete-a-snapshot)
     pub fn delete(id: usize) -> SnapshotRequest<Delete, ()> {
+        Snapshot::delete_by_id(&id.to_string())
+    }
+
+    /// Volume snapshots have string ids, so `delete()` cannot address them.
+    fn delete_by_id(id: &str) -> SnapshotRequest<Delete, ()> {
         let mut url = ROOT_URL.clone();
         url.path_segments_mut()
             .expect(STATIC_URL_ERROR)
             .push(SNAPSHOT_SEGMENT)
-            .push(&id.to_string());
+            .push(id);
 
         Request::new(url)
     }
+
+    /// Delete the snapshots with the given `ids`, with at most `concurrency`
+    /// requests in flight at once.
+    ///
+    /// Every snapshot is deleted by its own request, a failure does not stop
+    /// the others. The ids which were deleted are reported as succeeded.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#delete-a-snapshot)
+    pub async fn delete_many(
+        instance: &DigitalOcean,
+        ids: Vec<String>,
+        concurrency: usize,
+    ) -> BatchResult<String> {
+        let results: Vec<_> = stream::iter(ids)
+            .map(|id| async move {
+                let result = Snapshot::delete_by_id(&id).execute(instance).await;
+                (id, result)
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+
+        let mut batch = BatchResult::default();
+        for (id, result) in results {
+            match result {
+                Ok(()) => batch.succeeded.push(id),
+                Err(e) => batch.failed.push((id, e)),
+            }
+        }
+        batch
+    }
+
+    /// Fetch the image of a Droplet snapshot, which is what Droplets are
+    /// created from. Droplet snapshots are images sharing the snapshot's id,
+    /// while volume snapshots are not images at all, for those
+    /// `Error::InvalidRequest` is returned.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#retrieve-an-existing-image-by-id)
+    pub async fn as_image(&self, instance: &DigitalOcean) -> Result<Image, Error> {
+        if self.resource_type != "droplet" {
+            return Err(Error::InvalidRequest(format!(
+                "snapshot {} of a {} is not an image",
+                self.id, self.resource_type
+            )));
+        }
+
+        Image::get(&self.id).execute(instance).await
+    }
+}
+
+impl SnapshotRequest<List, Vec<Snapshot>> {
+    /// Only keep the snapshots with the given tag.
+    ///
+    /// **Note:** Unlike images, the API cannot filter snapshots by tag, this
+    /// is applied after all pages have been retrieved.
+    pub fn tag<S: AsRef<str>>(self, name: S) -> Self {
+        let name = name.as_ref().to_string();
+        self.filter(move |snapshot| snapshot.tags.contains(&name))
+    }
+}
+
+impl SnapshotRequest<Create, Snapshot> {
+    /// A flat array of tag names as strings to apply to the snapshot after it
+    /// is created. Tag names can either be existing or new tags.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/volumeSnapshots_create)
+    pub fn tags(mut self, val: Vec<String>) -> Self {
+        self.body_mut()["tags"] = json!(val);
+        self
+    }
+}
+
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "snapshot {:?} ({})", self.name, self.id)
+    }
 }
 
 /// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Getters)]
 pub struct SnapshotListResponse {
     snapshots: Vec<Snapshot>,
+    #[get = "pub"]
     links: ApiLinks,
+    #[get = "pub"]
     meta: ApiMeta,
 }
 
@@ -135,6 +227,15 @@ impl HasValue for SnapshotListResponse {
     }
 }
 
+impl IntoIterator for SnapshotListResponse {
+    type Item = Snapshot;
+    type IntoIter = std::vec::IntoIter<Snapshot>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.snapshots.into_iter()
+    }
+}
+
 /// Response type returned from Digital Ocean.
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct SnapshotResponse {
@@ -0,0 +1,105 @@
+use self::spaces_key_fields::Grant;
+use super::{HasResponse, HasValue};
+use crate::method::Create;
+use crate::request::Request;
+use crate::request::SpacesKeyRequest;
+use crate::{ROOT_URL, STATIC_URL_ERROR};
+use chrono::{DateTime, Utc};
+use getset::{Getters, Setters};
+use serde::Deserialize;
+use serde::Serialize;
+use std::fmt::Display;
+
+const SPACES_SEGMENT: &str = "spaces";
+const KEYS_SEGMENT: &str = "keys";
+
+/// Spaces access keys authenticate requests to the S3-compatible Spaces API.
+/// Each key is scoped to specific buckets by its grants.
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#tag/Spaces-Keys)
+#[derive(Deserialize, Serialize, Debug, Clone, Getters, Setters)]
+#[get = "pub"]
+pub struct SpacesKey {
+    /// The human-readable name of the key.
+    name: String,
+
+    /// The access key id used to sign requests to Spaces.
+    access_key: String,
+
+    /// The secret part of the key. It is only returned when the key is
+    /// created.
+    #[serde(default)]
+    secret_key: Option<String>,
+
+    /// The buckets the key may access, and how.
+    grants: Vec<Grant>,
+
+    /// A time value given in ISO8601 combined date and time format that
+    /// represents when the key was created.
+    created_at: DateTime<Utc>,
+}
+
+/// Fields which exists inside Spaces keys.
+pub mod spaces_key_fields {
+    use serde::Deserialize;
+    use serde::Serialize;
+
+    /// Scopes a key to a bucket.
+    #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct Grant {
+        /// The name of the bucket. Empty for `FullAccess` grants, which
+        /// cover every bucket.
+        pub bucket: String,
+        pub permission: SpacesPermission,
+    }
+
+    /// What a key may do with the bucket of a grant.
+    #[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SpacesPermission {
+        #[serde(rename = "read")]
+        Read,
+        #[serde(rename = "readwrite")]
+        ReadWrite,
+        #[serde(rename = "fullaccess")]
+        FullAccess,
+    }
+}
+
+impl SpacesKey {
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/spacesKey_create)
+    pub fn create<S>(name: S, grants: Vec<Grant>) -> SpacesKeyRequest<Create, SpacesKey>
+    where
+        S: AsRef<str> + Serialize + Display,
+    {
+        let mut url = ROOT_URL.clone();
+        url.path_segments_mut()
+            .expect(STATIC_URL_ERROR)
+            .push(SPACES_SEGMENT)
+            .push(KEYS_SEGMENT);
+
+        let mut req = Request::new(url);
+        req.set_body(json!({
+            "name": name,
+            "grants": grants,
+        }));
+        req
+    }
+}
+
+/// Response type returned from Digital Ocean.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SpacesKeyResponse {
+    key: SpacesKey,
+}
+
+impl HasResponse for SpacesKey {
+    type Response = SpacesKeyResponse;
+}
+
+impl HasValue for SpacesKeyResponse {
+    type Value = SpacesKey;
+
+    fn value(self) -> SpacesKey {
+        self.key
+    }
+}
@@ -1,13 +1,14 @@
 use super::{ApiLinks, ApiMeta};
 use super::{HasPagination, HasResponse, HasValue};
+use crate::error::Error;
 use crate::method::{Create, Delete, Get, List, Update};
-use crate::request::Request;
 use crate::request::SshKeyRequest;
-use crate::{ROOT_URL, STATIC_URL_ERROR};
+use crate::request::{Executable, Request};
+use crate::{DigitalOcean, ROOT_URL, STATIC_URL_ERROR};
 use getset::{Getters, Setters};
 use serde::Deserialize;
 use serde::Serialize;
-use std::fmt::Display;
+use std::fmt::{self, Display};
 use url::Url;
 
 const ACCOUNT_SEGMENT: &str = "account";
@@ -44,6 +45,36 @@ pub struct SshKey {
     name: String,
 }
 
+/// References an SSH key to embed in a Droplet, by id or by fingerprint.
+/// Both kinds may be mixed in a single request.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum SshKeyRef {
+    /// The unique identification number of the key.
+    Id(usize),
+    /// The fingerprint of the key, for example
+    /// "3b:16:bf:e4:8b:00:8b:b8:59:8c:a9:d3:f0:19:45:fa".
+    Fingerprint(String),
+}
+
+impl From<usize> for SshKeyRef {
+    fn from(id: usize) -> Self {
+        SshKeyRef::Id(id)
+    }
+}
+
+impl From<String> for SshKeyRef {
+    fn from(fingerprint: String) -> Self {
+        SshKeyRef::Fingerprint(fingerprint)
+    }
+}
+
+impl From<&str> for SshKeyRef {
+    fn from(fingerprint: &str) -> Self {
+        SshKeyRef::Fingerprint(fingerprint.to_string())
+    }
+}
+
 impl SshKey {
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#create-a-new-key)
     pub fn create<N>(name: N, public_key: N) -> SshKeyRequest<Create, SshKey>
@@ -75,6 +106,28 @@ impl SshKey {
         Request::new(url)
     }
 
+    /// Retrieve every key on the account, following all pages.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-all-keys)
+    pub async fn list_all(instance: &DigitalOcean) -> Result<Vec<SshKey>, Error> {
+        SshKey::list().execute(instance).await
+    }
+
+    /// Find the key with the given name, or `None` if there is no such key.
+    ///
+    /// **Note:** The API cannot look keys up by name, so this lists every key
+    /// and searches the result.
+    ///
+    /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-all-keys)
+    pub async fn find_by_name<S: AsRef<str>>(
+        instance: &DigitalOcean,
+        name: S,
+    ) -> Result<Option<SshKey>, Error> {
+        let keys = SshKey::list_all(instance).await?;
+
+        Ok(keys.into_iter().find(|key| key.name == name.as_ref()))
+    }
+
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#retrieve-an-existing-key)
     pub fn get<S: Serialize + Display>(id: S) -> SshKeyRequest<Get, SshKey> {
         let mut url = ROOT_URL.clone();
@@ -122,11 +175,19 @@ impl SshKeyRequest<Update, SshKey> {
     }
 }
 
+impl fmt::Display for SshKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ssh key {:?} ({})", self.name, self.id)
+    }
+}
+
 /// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Getters)]
 pub struct SshKeyListResponse {
     ssh_keys: Vec<SshKey>,
+    #[get = "pub"]
     links: ApiLinks,
+    #[get = "pub"]
     meta: ApiMeta,
 }
 
@@ -148,6 +209,15 @@ impl HasValue for SshKeyListResponse {
     }
 }
 
+impl IntoIterator for SshKeyListResponse {
+    type Item = SshKey;
+    type IntoIter = std::vec::IntoIter<SshKey>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ssh_keys.into_iter()
+    }
+}
+
 /// Response type returned from Digital Ocean.
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct SshKeyResponse {
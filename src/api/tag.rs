@@ -1,9 +1,10 @@
+use super::BatchResult;
 use super::{ApiLinks, ApiMeta};
-use super::{HasPagination, HasResponse, HasValue};
-use crate::method::{Create, Delete, Get, List};
-use crate::request::Request;
+use super::{HasPagination, HasResponse, HasValue, MAX_TAG_RESOURCES};
+use crate::method::{Create, Delete, Get, List, Method};
 use crate::request::TagRequest;
-use crate::{ROOT_URL, STATIC_URL_ERROR};
+use crate::request::{Executable, Request};
+use crate::{DigitalOcean, ROOT_URL, STATIC_URL_ERROR};
 use getset::{Getters, Setters};
 use serde::Deserialize;
 use serde::Serialize;
@@ -85,47 +86,79 @@ impl Tag {
 impl TagRequest<Get, Tag> {
     /// Accepts tuples matching `(id, type)`. Currently the only `type` is `"droplet"`.
     ///
+    /// The resources are tagged in chunks of
+    /// [`MAX_TAG_RESOURCES`](../api/constant.MAX_TAG_RESOURCES.html), one
+    /// request after the other, as the API refuses overly large batches. A
+    /// failed request does not stop the following ones. The resources of each
+    /// failed request are returned with its error, to be retried.
+    ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#tag-a-resource)
-    pub fn add_resources<S>(mut self, resources: Vec<(S, S)>) -> TagRequest<Create, ()>
+    pub async fn add_resources<S>(
+        self,
+        instance: &DigitalOcean,
+        resources: Vec<(S, S)>,
+    ) -> BatchResult<(String, String), Vec<(String, String)>>
     where
-        S: AsRef<str> + Serialize + Display,
+        S: AsRef<str>,
     {
-        self.url_mut()
-            .path_segments_mut()
-            .expect(STATIC_URL_ERROR)
-            .push(RESOURCES_SEGMENT);
-
-        let resources = resources
-            .into_iter()
-            .map(|(id, kind)| {
-                json!({
-                    "resource_id": id,
-                    "resource_type": kind,
-                })
-            })
-            .collect::<Vec<_>>();
-
-        self.set_body(json!({
-            "resources": resources,
-        }));
-
-        self.transmute()
+        self.chunked::<Create, S>(instance, resources).await
     }
 
     /// Accepts tuples matching `(id, type)`. Currently the only `type` is `"droplet"`.
     ///
+    /// The resources are untagged in chunks of
+    /// [`MAX_TAG_RESOURCES`](../api/constant.MAX_TAG_RESOURCES.html), like
+    /// [`add_resources()`](#method.add_resources) tags them.
+    ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#untag-a-resource)
-    pub fn remove_resources<S>(mut self, resources: Vec<(S, S)>) -> TagRequest<Delete, ()>
+    pub async fn remove_resources<S>(
+        self,
+        instance: &DigitalOcean,
+        resources: Vec<(S, S)>,
+    ) -> BatchResult<(String, String), Vec<(String, String)>>
+    where
+        S: AsRef<str>,
+    {
+        self.chunked::<Delete, S>(instance, resources).await
+    }
+
+    /// Execute one request on the resources of the tag per chunk of
+    /// `MAX_TAG_RESOURCES` resources, using the verb of `M`.
+    async fn chunked<M, S>(
+        self,
+        instance: &DigitalOcean,
+        resources: Vec<(S, S)>,
+    ) -> BatchResult<(String, String), Vec<(String, String)>>
     where
-        S: AsRef<str> + Serialize + Display,
+        M: Method,
+        S: AsRef<str>,
+        TagRequest<M, ()>: Executable<()>,
     {
+        let resources: Vec<(String, String)> = resources
+            .iter()
+            .map(|(id, kind)| (id.as_ref().to_string(), kind.as_ref().to_string()))
+            .collect();
+
+        let mut result = BatchResult::default();
+        for chunk in resources.chunks(MAX_TAG_RESOURCES) {
+            match self.clone().resources::<M>(chunk).execute(instance).await {
+                Ok(()) => result.succeeded.extend_from_slice(chunk),
+                Err(e) => result.failed.push((chunk.to_vec(), e)),
+            }
+        }
+
+        result
+    }
+
+    /// A request on the resources of the tag, sending `resources` as the body.
+    fn resources<M: Method>(mut self, resources: &[(String, String)]) -> TagRequest<M, ()> {
         self.url_mut()
             .path_segments_mut()
             .expect(STATIC_URL_ERROR)
             .push(RESOURCES_SEGMENT);
 
         let resources = resources
-            .into_iter()
+            .iter()
             .map(|(id, kind)| {
                 json!({
                     "resource_id": id,
@@ -161,10 +194,12 @@ impl HasResponse for Tag {
 }
 
 /// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Getters)]
 pub struct TagListResponse {
     tags: Vec<Tag>,
+    #[get = "pub"]
     links: ApiLinks,
+    #[get = "pub"]
     meta: ApiMeta,
 }
 
@@ -185,3 +220,12 @@ impl HasValue for TagListResponse {
         self.tags
     }
 }
+
+impl IntoIterator for TagListResponse {
+    type Item = Tag;
+    type IntoIter = std::vec::IntoIter<Tag>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tags.into_iter()
+    }
+}
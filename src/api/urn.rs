@@ -0,0 +1,166 @@
+use super::{Domain, Droplet, FloatingIp, Image, LoadBalancer, Volume};
+use crate::error::Error;
+use crate::request::Executable;
+use crate::DigitalOcean;
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+const URN_PREFIX: &str = "do";
+
+/// A Uniform Resource Name, which identifies a resource across resource
+/// types, for example `do:droplet:4126873`. URNs are how resources are
+/// referenced when they are assigned to a project.
+///
+/// Parse one with `str::parse()`, format one with `to_string()`.
+///
+/// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#tag/Project-Resources)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Urn {
+    /// `do:droplet:{id}`
+    Droplet(usize),
+    /// `do:volume:{id}`
+    Volume(String),
+    /// `do:floatingip:{ip}`
+    FloatingIp(IpAddr),
+    /// `do:loadbalancer:{id}`
+    LoadBalancer(String),
+    /// `do:domain:{name}`
+    Domain(String),
+    /// `do:image:{id}`
+    Image(usize),
+}
+
+/// Resources which can be referenced by a [`Urn`](enum.Urn.html), such as
+/// the resources assigned to a project.
+pub trait HasUrn {
+    /// The URN of the resource, for example `do:droplet:4126873`.
+    fn urn(&self) -> String;
+}
+
+impl HasUrn for Droplet {
+    fn urn(&self) -> String {
+        Urn::Droplet(*self.id()).to_string()
+    }
+}
+
+impl HasUrn for Volume {
+    fn urn(&self) -> String {
+        Urn::Volume(self.id().clone()).to_string()
+    }
+}
+
+impl HasUrn for FloatingIp {
+    fn urn(&self) -> String {
+        Urn::FloatingIp(*self.ip()).to_string()
+    }
+}
+
+impl HasUrn for LoadBalancer {
+    fn urn(&self) -> String {
+        Urn::LoadBalancer(self.id().clone()).to_string()
+    }
+}
+
+impl HasUrn for Domain {
+    fn urn(&self) -> String {
+        Urn::Domain(self.name().clone()).to_string()
+    }
+}
+
+impl HasUrn for Image {
+    fn urn(&self) -> String {
+        Urn::Image(*self.id()).to_string()
+    }
+}
+
+/// The resource a [`Urn`](enum.Urn.html) refers to, as returned by
+/// [`Urn::fetch()`](enum.Urn.html#method.fetch).
+#[derive(Debug, Clone)]
+pub enum ResolvedResource {
+    Droplet(Droplet),
+    Volume(Volume),
+    FloatingIp(FloatingIp),
+    LoadBalancer(LoadBalancer),
+    Domain(Domain),
+    Image(Image),
+}
+
+impl Urn {
+    /// Retrieve the resource the URN refers to.
+    pub async fn fetch(&self, instance: &DigitalOcean) -> Result<ResolvedResource, Error> {
+        let resource = match self {
+            Urn::Droplet(id) => {
+                ResolvedResource::Droplet(Droplet::get(*id).execute(instance).await?)
+            }
+            Urn::Volume(id) => ResolvedResource::Volume(Volume::get(id).execute(instance).await?),
+            Urn::FloatingIp(ip) => {
+                ResolvedResource::FloatingIp(FloatingIp::get(*ip).execute(instance).await?)
+            }
+            Urn::LoadBalancer(id) => {
+                ResolvedResource::LoadBalancer(LoadBalancer::get(id).execute(instance).await?)
+            }
+            Urn::Domain(name) => {
+                ResolvedResource::Domain(Domain::get(name).execute(instance).await?)
+            }
+            Urn::Image(id) => ResolvedResource::Image(Image::get(id).execute(instance).await?),
+        };
+
+        Ok(resource)
+    }
+}
+
+impl FromStr for Urn {
+    type Err = Error;
+
+    /// Parse a URN of the form `do:{kind}:{id}`. Unsupported kinds and
+    /// malformed ids are rejected with `Error::InvalidRequest`.
+    fn from_str(urn: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| Error::InvalidRequest(format!("urn {urn:?} {reason}"));
+
+        let mut parts = urn.splitn(3, ':');
+        let (prefix, kind, id) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(prefix), Some(kind), Some(id)) => (prefix, kind, id),
+            _ => return Err(invalid("is not of the form `do:{kind}:{id}`")),
+        };
+
+        if prefix != URN_PREFIX {
+            return Err(invalid("does not start with `do:`"));
+        }
+        if id.is_empty() {
+            return Err(invalid("has an empty id"));
+        }
+
+        match kind {
+            "droplet" => id
+                .parse()
+                .map(Urn::Droplet)
+                .map_err(|_| invalid("has a non numeric droplet id")),
+            "volume" => Ok(Urn::Volume(id.to_string())),
+            "floatingip" => id
+                .parse()
+                .map(Urn::FloatingIp)
+                .map_err(|_| invalid("has an invalid ip address")),
+            "loadbalancer" => Ok(Urn::LoadBalancer(id.to_string())),
+            "domain" => Ok(Urn::Domain(id.to_string())),
+            "image" => id
+                .parse()
+                .map(Urn::Image)
+                .map_err(|_| invalid("has a non numeric image id")),
+            _ => Err(invalid("has an unsupported resource type")),
+        }
+    }
+}
+
+impl fmt::Display for Urn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Urn::Droplet(id) => write!(f, "{URN_PREFIX}:droplet:{id}"),
+            Urn::Volume(id) => write!(f, "{URN_PREFIX}:volume:{id}"),
+            Urn::FloatingIp(ip) => write!(f, "{URN_PREFIX}:floatingip:{ip}"),
+            Urn::LoadBalancer(id) => write!(f, "{URN_PREFIX}:loadbalancer:{id}"),
+            Urn::Domain(name) => write!(f, "{URN_PREFIX}:domain:{name}"),
+            Urn::Image(id) => write!(f, "{URN_PREFIX}:image:{id}"),
+        }
+    }
+}
@@ -1,7 +1,9 @@
+use self::volume_fields::FsType;
 use super::region::Region;
 use super::snapshot::Snapshot;
 use super::{ApiLinks, ApiMeta};
 use super::{HasPagination, HasResponse, HasValue};
+use crate::error::Error;
 use crate::method::{Create, Delete, Get, List};
 use crate::request::Request;
 use crate::request::{SnapshotRequest, VolumeRequest};
@@ -10,7 +12,8 @@ use chrono::{DateTime, Utc};
 use getset::{Getters, Setters};
 use serde::Deserialize;
 use serde::Serialize;
-use std::fmt::Display;
+use serde_json::Value;
+use std::fmt::{self, Display};
 use url::Url;
 
 const VOLUME_SEGMENT: &str = "volumes";
@@ -56,6 +59,63 @@ pub struct Volume {
     created_at: DateTime<Utc>,
 }
 
+/// Fields which exists inside Volumes.
+pub mod volume_fields {
+    use serde::Deserialize;
+    use serde::Serialize;
+
+    /// The filesystem a volume can be pre-formatted with on creation.
+    #[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+    #[serde(rename_all = "lowercase")]
+    pub enum FsType {
+        Ext4,
+        Xfs,
+    }
+
+    impl FsType {
+        /// The longest filesystem label this filesystem accepts.
+        pub fn max_label_len(self) -> usize {
+            match self {
+                FsType::Ext4 => 16,
+                FsType::Xfs => 12,
+            }
+        }
+    }
+}
+
+/// Catches volume creates the API would refuse.
+fn validate_create(body: &Value) -> Result<(), Error> {
+    match (body["region"].is_null(), body["snapshot_id"].is_null()) {
+        (false, false) => {
+            return Err(Error::InvalidRequest(
+                "`region` and `snapshot_id` are mutually exclusive".to_string(),
+            ));
+        }
+        (true, true) => {
+            return Err(Error::InvalidRequest(
+                "either a region or a snapshot_id is required to create a volume".to_string(),
+            ));
+        }
+        _ => (),
+    }
+
+    if let Some(label) = body["filesystem_label"].as_str() {
+        let fs_type: FsType =
+            serde_json::from_value(body["filesystem_type"].clone()).map_err(|_| {
+                Error::InvalidRequest("`filesystem_label` requires a `filesystem_type`".to_string())
+            })?;
+
+        let max = fs_type.max_label_len();
+        if label.chars().count() > max {
+            return Err(Error::InvalidRequest(format!(
+                "filesystem label {label:?} is longer than {max} characters"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
 impl Volume {
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-all-block-storage-volumes)
     pub fn list() -> VolumeRequest<List, Vec<Volume>> {
@@ -77,7 +137,7 @@ impl Volume {
             .expect(STATIC_URL_ERROR)
             .push(VOLUME_SEGMENT);
 
-        let mut req = Request::new(url);
+        let mut req = Request::new(url).validator(validate_create);
         req.set_body(json!({
             "name": name,
             "size_gigabytes": size_gigabytes,
@@ -94,8 +154,14 @@ impl Volume {
 
         Request::new(url)
     }
+
+    /// Retrieve the volume with the given name in `region`.
+    ///
+    /// **Note:** This is a filtered list, so it yields an empty `Vec` rather
+    /// than an error when no volume matches.
+    ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#retrieve-an-existing-block-storage-volume-by-name)
-    pub fn get_by_name<S>(name: S, region: S) -> VolumeRequest<Get, Volume>
+    pub fn get_by_name<S>(name: S, region: S) -> VolumeRequest<List, Vec<Volume>>
     where
         S: AsRef<str> + Serialize + Display,
     {
@@ -139,6 +205,9 @@ impl Volume {
 }
 
 impl VolumeRequest<List, Vec<Volume>> {
+    /// Only list the volumes in the given region. Applied by the API, so it
+    /// composes with `limit()` and pagination.
+    ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#list-all-block-storage-volumes)
     pub fn region<S: AsRef<str> + Serialize + Display>(mut self, region: S) -> Self {
         self.url_mut()
@@ -147,6 +216,40 @@ impl VolumeRequest<List, Vec<Volume>> {
 
         self
     }
+
+    /// Only list the volumes with exactly the given name. Applied by the API,
+    /// so it composes with `limit()` and pagination.
+    ///
+    /// **Note:** The API does not match name prefixes, see
+    /// [`name_contains()`](#method.name_contains) for partial matches.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/volumes_list)
+    pub fn name<S: AsRef<str> + Serialize + Display>(mut self, name: S) -> Self {
+        self.url_mut()
+            .query_pairs_mut()
+            .append_pair("name", name.as_ref());
+
+        self
+    }
+
+    /// Only keep the volumes whose name contains `needle`.
+    ///
+    /// **Note:** The API cannot match partial names, this is applied after all
+    /// pages have been retrieved.
+    pub fn name_contains<S: AsRef<str>>(self, needle: S) -> Self {
+        let needle = needle.as_ref().to_string();
+        self.filter(move |volume| volume.name.contains(&needle))
+    }
+
+    /// Only keep the volumes whose name starts with `prefix`, for example to
+    /// clean up the volumes of a test run in one region.
+    ///
+    /// **Note:** The API cannot match name prefixes, this is applied after all
+    /// pages have been retrieved.
+    pub fn name_prefix<S: AsRef<str>>(self, prefix: S) -> Self {
+        let prefix = prefix.as_ref().to_string();
+        self.filter(move |volume| volume.name.starts_with(&prefix))
+    }
 }
 
 impl VolumeRequest<Get, Volume> {
@@ -190,7 +293,7 @@ impl VolumeRequest<Create, Volume> {
     /// you query a Block Storage volume, the entire region object will be
     /// returned.
     ///
-    /// **Note:** Should not be specified with a `snapshot_id`.
+    /// **Note:** Must not be specified with a `snapshot_id`.
     ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#block-storage)
     pub fn region<S: AsRef<str> + Serialize + Display>(mut self, val: S) -> Self {
@@ -201,20 +304,48 @@ impl VolumeRequest<Create, Volume> {
     /// The unique identifier for the volume snapshot from which to create the
     /// volume.
     ///
-    /// **Note:** Should not be specified with a `region_id`.
+    /// **Note:** Must not be specified with a `region`.
     ///
     /// [Digital Ocean Documentation.](https://developers.digitalocean.com/documentation/v2/#block-storage)
     pub fn snapshot_id<S: AsRef<str> + Serialize + Display>(mut self, val: S) -> Self {
         self.body_mut()["snapshot_id"] = json!(val);
         self
     }
+
+    /// The filesystem the volume will be formatted with, saving a manual
+    /// `mkfs` once it is attached.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/volumes_create)
+    pub fn filesystem_type(mut self, val: FsType) -> Self {
+        self.body_mut()["filesystem_type"] = json!(val);
+        self
+    }
+
+    /// The label applied to the filesystem. Labels may be up to 16 characters
+    /// long for ext4 and up to 12 characters long for xfs.
+    ///
+    /// **Note:** Must be specified along with a `filesystem_type`.
+    ///
+    /// [Digital Ocean Documentation.](https://docs.digitalocean.com/reference/api/api-reference/#operation/volumes_create)
+    pub fn filesystem_label<S: AsRef<str> + Serialize + Display>(mut self, val: S) -> Self {
+        self.body_mut()["filesystem_label"] = json!(val);
+        self
+    }
+}
+
+impl fmt::Display for Volume {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "volume {:?} ({})", self.name, self.id)
+    }
 }
 
 /// Response type returned from Digital Ocean.
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Getters)]
 pub struct VolumeListResponse {
     volumes: Vec<Volume>,
+    #[get = "pub"]
     links: ApiLinks,
+    #[get = "pub"]
     meta: ApiMeta,
 }
 
@@ -236,6 +367,15 @@ impl HasValue for VolumeListResponse {
     }
 }
 
+impl IntoIterator for VolumeListResponse {
+    type Item = Volume;
+    type IntoIter = std::vec::IntoIter<Volume>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.volumes.into_iter()
+    }
+}
+
 /// Response type returned from Digital Ocean.
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct VolumeResponse {
@@ -1,13 +1,17 @@
 pub use reqwest::Client;
 
-use crate::api::{HasPagination, HasResponse, HasValue, MAX_PER_PAGE};
+use crate::api::{HasPagination, HasResponse, HasValue};
 use crate::error::Error;
-use crate::method::{Create, Delete, Get, List, Update};
-use crate::request::Request;
-use crate::DigitalOcean;
-use log::info;
+use crate::method::{Create, Delete, Get, List, Method, Update};
+use crate::request::{Overrides, Request};
+use crate::{retry, DigitalOcean};
+use log::{debug, info, log_enabled, Level};
+use reqwest::header::{HeaderName, ACCEPT, CONTENT_LENGTH, CONTENT_TYPE, USER_AGENT};
 use reqwest::StatusCode;
 use reqwest::{RequestBuilder, Response};
+use serde_json::Value;
+use tokio::time::sleep;
+use url::Url;
 
 impl DigitalOcean {
     pub(crate) async fn get<V>(&self, request: Request<Get, V>) -> Result<V, Error>
@@ -15,17 +19,19 @@ impl DigitalOcean {
         V: HasResponse,
     {
         info!("GET {:?}", request.url());
-        let req = self.client.get(request.url().clone());
+        let req = with_headers(self.client.get(request.url().clone()), request.headers());
 
-        let response = self.fetch(req).await?;
+        let response = self.fetch(req, Get::VERB, request.overrides()).await?;
+        let request_id = request_id(&response);
+        let response = unavailable(response, &request_id).await?;
 
         match response.status() {
             // Successes
             StatusCode::OK => {}
             // Not Found
-            StatusCode::NOT_FOUND => Err(Error::NotFound)?,
+            StatusCode::NOT_FOUND => Err(Error::NotFound { request_id })?,
             // Errors
-            e => Err(Error::UnexpectedStatus(e))?,
+            status => Err(Error::UnexpectedStatus { status, request_id })?,
         };
 
         let deserialized: V::Response = response.json().await?;
@@ -40,103 +46,130 @@ impl DigitalOcean {
         info!("LIST {:?}", request.url());
         // This may be a paginated response. We need to buffer.
         let mut buffer = Vec::new();
-        let mut current_url = request.url().clone();
-
-        match request.method().0 {
-            Some(limit) if limit < MAX_PER_PAGE => {
-                current_url
-                    .query_pairs_mut()
-                    .append_pair("per_page", &limit.to_string());
-            }
-            _ => {
-                current_url
-                    .query_pairs_mut()
-                    .append_pair("per_page", &MAX_PER_PAGE.to_string());
-            }
-        };
+        let mut current_url = request.first_page();
 
         loop {
-            let req = self.client.get(current_url.clone());
-            let response = self.fetch(req).await?;
-
-            match response.status() {
-                StatusCode::OK => {}
-                // Not Found
-                StatusCode::NOT_FOUND => Err(Error::NotFound)?,
-                // Errors
-                e => Err(Error::UnexpectedStatus(e))?,
-            };
+            let (values, next_page) = self.page::<V>(current_url, request.overrides()).await?;
+            buffer.extend(values);
 
-            let deserialized: <Vec<V> as HasResponse>::Response = response.json().await?;
-
-            let next_page = deserialized.next_page();
-            buffer.extend(deserialized.value());
+            // Pages are offset by their size, so later pages keep the size of
+            // the first one and the surplus is dropped instead.
+            if let Some(limit) = request.method().0
+                && buffer.len() >= limit
+            {
+                buffer.truncate(limit);
+                break;
+            }
 
             current_url = match next_page {
                 Some(v) => v,
                 None => break,
             };
-
-            if let Some(limit) = request.method().0 {
-                let buffer_size = buffer.len();
-                let remaining = limit - buffer_size;
-
-                if buffer_size >= limit {
-                    break;
-                } else if remaining < MAX_PER_PAGE {
-                    current_url
-                        .query_pairs_mut()
-                        .append_pair("per_page", &remaining.to_string());
-                }
-            }
             info!("Fetching next page...")
         }
 
         Ok(buffer)
     }
 
+    /// Fetch a single page of a list, returning its values and the url of the
+    /// next page, if there is one.
+    pub(crate) async fn page<V>(
+        &self,
+        url: Url,
+        overrides: Overrides,
+    ) -> Result<(Vec<V>, Option<Url>), Error>
+    where
+        Vec<V>: HasResponse,
+        <Vec<V> as HasResponse>::Response: HasPagination,
+    {
+        let req = self.client.get(url);
+        let response = self.fetch(req, List::VERB, overrides).await?;
+        let request_id = request_id(&response);
+        let response = unavailable(response, &request_id).await?;
+
+        match response.status() {
+            StatusCode::OK => {}
+            // Not Found
+            StatusCode::NOT_FOUND => Err(Error::NotFound { request_id })?,
+            // Errors
+            status => Err(Error::UnexpectedStatus { status, request_id })?,
+        };
+
+        let deserialized: <Vec<V> as HasResponse>::Response = response.json().await?;
+
+        let next_page = deserialized.next_page();
+        Ok((deserialized.value(), next_page))
+    }
+
     // Delete requests do not return content.
     pub(crate) async fn delete<V>(&self, request: Request<Delete, V>) -> Result<(), Error> {
         info!("DELETE {:?}", request.url());
-        let req = self.client.delete(request.url().clone());
+        let mut req = with_headers(self.client.delete(request.url().clone()), request.headers());
 
-        let response = self.fetch(req).await?;
+        // Some deletes, such as untagging resources, say what to delete in the body.
+        if !request.body().is_null() {
+            req = self.with_body(req, request.body());
+        }
+
+        let response = self.fetch(req, Delete::VERB, request.overrides()).await?;
+        let request_id = request_id(&response);
+        let response = unavailable(response, &request_id).await?;
 
         match response.status() {
             // Successes
             StatusCode::NO_CONTENT => {} // Delete success
+            StatusCode::ACCEPTED => {}   // Delete success (async)
             // Errors
-            e => Err(Error::UnexpectedStatus(e))?,
+            status => Err(Error::UnexpectedStatus { status, request_id })?,
         };
 
         Ok(())
     }
 
     pub(crate) async fn post<V>(&self, request: Request<Create, V>) -> Result<V, Error>
+    where
+        V: HasResponse,
+    {
+        Ok(self.post_response(request).await?.value())
+    }
+
+    /// Like `post()`, but keeps the whole response, for the callers which
+    /// need more than the value.
+    pub(crate) async fn post_response<V>(
+        &self,
+        request: Request<Create, V>,
+    ) -> Result<V::Response, Error>
     where
         V: HasResponse,
     {
         info!("POST {:?}", request.url());
-        let req = self
-            .client
-            .post(request.url().clone())
-            .json(&request.body().clone());
+        let req = self.with_body(self.client.post(request.url().clone()), request.body());
+        let req = with_headers(req, request.headers());
 
-        let response = self.fetch(req).await?;
+        let response = self.fetch(req, Create::VERB, request.overrides()).await?;
+        let request_id = request_id(&response);
+        let response = unavailable(response, &request_id).await?;
 
         match response.status() {
             // Successes
             StatusCode::CREATED => {}  // Post Success
             StatusCode::ACCEPTED => {} // Post Success (async)
+            // Post Success without content, only expected for `()` values.
+            status @ StatusCode::NO_CONTENT => {
+                return serde_json::from_value(Value::Null)
+                    .map_err(|_| Error::UnexpectedStatus { status, request_id });
+            }
             // Errors
             StatusCode::UNPROCESSABLE_ENTITY => {
-                return Err(Error::UnprocessableEntity(response.json().await?))?;
+                return Err(Error::UnprocessableEntity {
+                    body: response.json().await?,
+                    request_id,
+                })?;
             }
-            e => Err(Error::UnexpectedStatus(e))?,
+            status => Err(Error::UnexpectedStatus { status, request_id })?,
         };
 
-        let deserialized: V::Response = response.json().await?;
-        Ok(deserialized.value())
+        Ok(response.json().await?)
     }
 
     pub(crate) async fn put<V>(&self, request: Request<Update, V>) -> Result<V, Error>
@@ -144,31 +177,184 @@ impl DigitalOcean {
         V: HasResponse,
     {
         info!("PUT {:?}", request.url());
-        let req = self
-            .client
-            .put(request.url().clone())
-            .json(&request.body().clone());
+        let req = self.with_body(self.client.put(request.url().clone()), request.body());
+        let req = with_headers(req, request.headers());
 
-        let response = self.fetch(req).await?;
+        let response = self.fetch(req, Update::VERB, request.overrides()).await?;
+        let request_id = request_id(&response);
+        let response = unavailable(response, &request_id).await?;
 
         match response.status() {
             // Successes
             StatusCode::OK => {} // Update success
             // Errors
             StatusCode::UNPROCESSABLE_ENTITY => {
-                return Err(Error::UnprocessableEntity(response.json().await?))?;
+                return Err(Error::UnprocessableEntity {
+                    body: response.json().await?,
+                    request_id,
+                })?;
             }
-            e => Err(Error::UnexpectedStatus(e))?,
+            status => Err(Error::UnexpectedStatus { status, request_id })?,
         };
 
         let deserialized: V::Response = response.json().await?;
         Ok(deserialized.value())
     }
 
-    async fn fetch(&self, dispatch: RequestBuilder) -> Result<Response, Error> {
-        let response = dispatch.bearer_auth(self.token.clone()).send().await?;
+    pub(crate) async fn raw<A: Method, V>(&self, request: Request<A, V>) -> Result<Value, Error> {
+        info!("{} {:?} (raw)", A::VERB, request.url());
+        let method = reqwest::Method::from_bytes(A::VERB.as_bytes()).expect("HTTP verbs are valid");
+        let mut req = with_headers(
+            self.client.request(method, request.url().clone()),
+            request.headers(),
+        );
+
+        if !request.body().is_null() || matches!(A::VERB, "POST" | "PUT") {
+            req = self.with_body(req, request.body());
+        }
+
+        let response = self.fetch(req, A::VERB, request.overrides()).await?;
+        let request_id = request_id(&response);
+        let response = unavailable(response, &request_id).await?;
+
+        match response.status() {
+            // Successes
+            StatusCode::NO_CONTENT => return Ok(Value::Null),
+            status if status.is_success() => {}
+            // Not Found
+            StatusCode::NOT_FOUND => Err(Error::NotFound { request_id })?,
+            // Errors
+            StatusCode::UNPROCESSABLE_ENTITY => {
+                return Err(Error::UnprocessableEntity {
+                    body: response.json().await?,
+                    request_id,
+                })?;
+            }
+            status => Err(Error::UnexpectedStatus { status, request_id })?,
+        };
+
+        Ok(response.json().await?)
+    }
+
+    async fn fetch(
+        &self,
+        dispatch: RequestBuilder,
+        verb: &str,
+        overrides: Overrides,
+    ) -> Result<Response, Error> {
+        let mut dispatch = dispatch
+            .bearer_auth(self.token.clone())
+            .header(USER_AGENT, self.user_agent.as_str());
+
+        if let Some(ref version) = self.api_version {
+            dispatch = dispatch.header(
+                ACCEPT,
+                format!("application/vnd.digitalocean.{version}+json"),
+            );
+        }
+        if let Some(timeout) = overrides.timeout {
+            dispatch = dispatch.timeout(timeout);
+        }
+
+        let budget = self.retry_budget.as_deref();
+        let max_retries = match (overrides.max_retries, budget) {
+            (Some(max_retries), _) => max_retries,
+            (None, Some(_)) => retry::MAX_RETRIES_PER_REQUEST,
+            (None, None) => 0,
+        };
+
+        let mut attempt = 0;
+        loop {
+            let retry = dispatch.try_clone();
+            let response = self.send(dispatch).await?;
+
+            dispatch = match retry {
+                Some(retry)
+                    if retry::is_retryable(verb, response.status())
+                        && attempt < max_retries
+                        && budget.is_none_or(retry::RetryBudget::try_take) =>
+                {
+                    retry
+                }
+                _ => return Ok(response),
+            };
+
+            let backoff = retry::backoff(&response, attempt, budget);
+            info!("Retrying in {:?}...", backoff);
+            sleep(backoff).await;
+            attempt += 1;
+        }
+    }
+
+    /// Attach the body of a POST or PUT. Without a body nothing is sent, with
+    /// an explicit `Content-Length: 0`, instead of a literal `null` which some
+    /// endpoints and proxies reject.
+    fn with_body(&self, req: RequestBuilder, body: &Value) -> RequestBuilder {
+        // Rendering copies the body, which may hold up to 64 KiB of user data.
+        if log_enabled!(Level::Debug)
+            && let Some(text) = self.body_logging.render(body)
+        {
+            debug!("Body: {}", text);
+        }
+
+        match body {
+            Value::Null => req.header(CONTENT_LENGTH, 0).body(Vec::new()),
+            body => req.json(body),
+        }
+    }
+
+    /// Send a request once, or answer it from the mocks when testing.
+    async fn send(&self, dispatch: RequestBuilder) -> Result<Response, Error> {
+        #[cfg(feature = "testing")]
+        if let Some(ref mocks) = self.mocks {
+            let response = mocks.answer(dispatch.build()?, self.client_config.compression)?;
+            info!("Response status: {:?}", response.status());
+            return Ok(response);
+        }
+
+        let response = dispatch.send().await?;
 
         info!("Response status: {:?}", response.status());
         Ok(response)
     }
 }
+
+/// Fail with `Error::ServiceUnavailable` on server errors which are not JSON,
+/// such as the HTML page DigitalOcean serves during maintenance, instead of
+/// with an obscure parse error once the body is read.
+async fn unavailable(response: Response, request_id: &Option<String>) -> Result<Response, Error> {
+    let status = response.status();
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.contains("json"));
+
+    if !status.is_server_error() || is_json {
+        return Ok(response);
+    }
+
+    Err(Error::ServiceUnavailable {
+        status,
+        body: response.text().await?,
+        request_id: request_id.clone(),
+    })
+}
+
+/// Attach the extra headers of a request.
+fn with_headers(req: RequestBuilder, headers: &[(String, String)]) -> RequestBuilder {
+    headers
+        .iter()
+        .fold(req, |req, (name, value)| req.header(name, value))
+}
+
+/// The header DigitalOcean uses to identify a request in support tickets.
+const REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+fn request_id(response: &Response) -> Option<String> {
+    response
+        .headers()
+        .get(REQUEST_ID)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
@@ -12,24 +12,77 @@ use serde_json;
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
 	/// The item does not exist or otherwise cannot be found.
-	#[error("Not Found")]
-	NotFound,
+	#[error("Not Found{}", request_id_suffix(.request_id))]
+	NotFound {
+		/// The `x-request-id` of the response, to be quoted in support tickets.
+		request_id: Option<String>,
+	},
 
 	/// The reqest's API key is invalid or not authorized to view this resource.
 	#[error("Unauthorized")]
 	Unauthorized,
 
 	/// An unexpected status code was returned from the API. Please raise a ticket.
-	#[error("Unexpected status code: {0}")]
-	UnexpectedStatus(reqwest::StatusCode),
+	#[error("Unexpected status code: {status}{}", request_id_suffix(.request_id))]
+	UnexpectedStatus {
+		status: reqwest::StatusCode,
+		/// The `x-request-id` of the response, to be quoted in support tickets.
+		request_id: Option<String>,
+	},
 
 	/// The item exists (possibly on another account), the limit on this item has been reached,
 	/// or this request is otherwise unprocessable.
-	#[error("Unprocessable entity: {0}")]
-	UnprocessableEntity(serde_json::Value),
+	#[error("Unprocessable entity: {body}{}", request_id_suffix(.request_id))]
+	UnprocessableEntity {
+		body: serde_json::Value,
+		/// The `x-request-id` of the response, to be quoted in support tickets.
+		request_id: Option<String>,
+	},
+
+	/// The API answered with a server error which is not JSON, such as the HTML page shown during
+	/// maintenance. The raw text of the response is kept in `body`.
+	#[error("Service unavailable: {status}{}", request_id_suffix(.request_id))]
+	ServiceUnavailable {
+		status: reqwest::StatusCode,
+		body: String,
+		/// The `x-request-id` of the response, to be quoted in support tickets.
+		request_id: Option<String>,
+	},
+
+	/// The request was rejected before being sent because it would be refused by the API.
+	#[error("Invalid request: {0}")]
+	InvalidRequest(String),
+
+	/// A resource which was to be created already exists.
+	#[error("{0} already exists")]
+	AlreadyExists(String),
+
+	/// The domain exists, but DigitalOcean returned no zone file for it.
+	#[error("Domain {0} has no zone file")]
+	EmptyZoneFile(String),
+
+	/// A resource did not reach the awaited state in time.
+	#[error("Timed out waiting for {0}")]
+	Timeout(String),
+
+	/// A resource which was being waited on ended up in a failed state.
+	#[error("{0} failed")]
+	Failed(String),
+
+	/// A wait was stopped through its cancellation token.
+	#[error("Cancelled")]
+	Cancelled,
 
 	/// There was a miscellaneous error processing the request. Please look at the documentation of
 	/// `reqwest` to learn more about how to handle these errors.
 	#[error("{0}")]
 	ReqwestError(#[from] reqwest::Error),
 }
+
+/// Formats the request id of a failed response for display after the message.
+fn request_id_suffix(request_id: &Option<String>) -> String {
+	match request_id {
+		Some(request_id) => format!(" (request id: {request_id})"),
+		None => String::new(),
+	}
+}
@@ -75,6 +75,15 @@ This crate uses the [`log`](https://doc.rust-lang.org/log/log/index.html) crate.
 RUST_LOG=digitalocean=debug cargo run
 ```
 
+At the `debug` level request bodies are logged as well, truncated and with secrets such as
+`private_key` and `user_data` redacted. See
+[`with_body_logging()`](struct.DigitalOcean.html#method.with_body_logging).
+
+## Testing
+
+Enabling the `testing` feature exposes `testing::MockDigitalOcean`, a client which answers
+requests from canned responses registered per method and path instead of calling the API.
+
 ## Development Status
 
 This crate is in a prototype state.
@@ -90,7 +99,7 @@ Please just open an issue or PR!
 */
 
 use lazy_static::lazy_static;
-use log::info;
+use log::{info, warn};
 
 #[macro_use]
 extern crate serde_json;
@@ -101,15 +110,25 @@ pub mod error;
 pub mod method;
 pub mod prelude;
 pub mod request;
+mod retry;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod wait;
 
-use crate::api::HasResponse;
+use crate::api::{HasPagination, HasResponse};
 use crate::error::Error;
 use crate::method::Method;
-use crate::request::{Executable, Request};
+use crate::request::{Executable, Overrides, Request};
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
 use url::Url;
 
 const STATIC_URL_ERROR: &str = "Staticly constructed DigitalOcean URL is malformed.";
 
+/// The `User-Agent` sent with every request unless overridden.
+pub const DEFAULT_USER_AGENT: &str = concat!("digitalocean-rs/", env!("CARGO_PKG_VERSION"));
+
 lazy_static! {
     static ref ROOT_URL: Url =
         Url::parse("https://api.digitalocean.com/v2").expect(STATIC_URL_ERROR);
@@ -119,7 +138,119 @@ lazy_static! {
 #[derive(Clone)]
 pub struct DigitalOcean {
     client: client::Client,
+    client_config: ClientConfig,
     token: String,
+    user_agent: String,
+    api_version: Option<String>,
+    body_logging: BodyLogging,
+    retry_budget: Option<Arc<retry::RetryBudget>>,
+    #[cfg(feature = "testing")]
+    mocks: Option<testing::Mocks>,
+}
+
+/// The fields whose values are never logged, at any depth of a body.
+const REDACTED_FIELDS: &[&str] = &["private_key", "user_data"];
+
+/// How request bodies are written to the `debug` log. Whichever is chosen,
+/// the values of secret fields such as `private_key` and `user_data` are
+/// replaced with `"[redacted]"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyLogging {
+    /// Do not log bodies.
+    None,
+    /// Log at most this many bytes of each body.
+    Truncated(usize),
+    /// Log bodies whole.
+    Full,
+}
+
+impl Default for BodyLogging {
+    fn default() -> Self {
+        BodyLogging::Truncated(1024)
+    }
+}
+
+impl BodyLogging {
+    /// The text logged for `body`, or `None` if nothing is to be logged.
+    pub fn render(&self, body: &Value) -> Option<String> {
+        if *self == BodyLogging::None || body.is_null() {
+            return None;
+        }
+
+        let text = redacted(body.clone()).to_string();
+
+        match *self {
+            BodyLogging::Truncated(limit) if text.len() > limit => {
+                let end = (0..=limit)
+                    .rev()
+                    .find(|&end| text.is_char_boundary(end))
+                    .unwrap_or(0);
+                Some(format!("{}... ({} bytes)", &text[..end], text.len()))
+            }
+            _ => Some(text),
+        }
+    }
+}
+
+/// `value` with the values of `REDACTED_FIELDS` replaced.
+fn redacted(value: Value) -> Value {
+    match value {
+        Value::Object(object) => Value::Object(
+            object
+                .into_iter()
+                .map(|(key, value)| {
+                    if REDACTED_FIELDS.contains(&key.as_str()) {
+                        (key, Value::from("[redacted]"))
+                    } else {
+                        (key, redacted(value))
+                    }
+                })
+                .collect(),
+        ),
+        Value::Array(values) => Value::Array(values.into_iter().map(redacted).collect()),
+        value => value,
+    }
+}
+
+/// The settings applied to the HTTP client, kept so that each `with_*`
+/// method can rebuild it without dropping the others.
+#[derive(Debug, Clone)]
+struct ClientConfig {
+    timeout: Option<Duration>,
+    pool: Option<(usize, Duration)>,
+    accept_invalid_certs: bool,
+    compression: bool,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        ClientConfig {
+            timeout: None,
+            pool: None,
+            accept_invalid_certs: false,
+            compression: true,
+        }
+    }
+}
+
+impl ClientConfig {
+    fn build(&self) -> Result<client::Client, Error> {
+        let mut builder = client::Client::builder().gzip(self.compression);
+
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some((max_idle_per_host, idle_timeout)) = self.pool {
+            builder = builder
+                .pool_max_idle_per_host(max_idle_per_host)
+                .pool_idle_timeout(idle_timeout);
+        }
+        if self.accept_invalid_certs {
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+
+        Ok(builder.build()?)
+    }
 }
 
 impl DigitalOcean {
@@ -128,10 +259,119 @@ impl DigitalOcean {
         info!("Created.");
         Ok(DigitalOcean {
             client: client::Client::new(),
+            client_config: ClientConfig::default(),
             token: token.into(),
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            api_version: None,
+            body_logging: BodyLogging::default(),
+            retry_budget: None,
+            #[cfg(feature = "testing")]
+            mocks: None,
         })
     }
 
+    /// Send `user_agent` as the `User-Agent` header instead of
+    /// [`DEFAULT_USER_AGENT`](constant.DEFAULT_USER_AGENT.html).
+    pub fn with_user_agent<S: Into<String>>(mut self, user_agent: S) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Pin the version of the API responses are in, by asking for the media
+    /// type `application/vnd.digitalocean.{version}+json` in the `Accept`
+    /// header of every request.
+    ///
+    /// By default no version is asked for and the API answers with its
+    /// current v2 responses.
+    pub fn with_api_version<S: Into<String>>(mut self, version: S) -> Self {
+        self.api_version = Some(version.into());
+        self
+    }
+
+    /// Choose how request bodies are written to the `debug` log, by default
+    /// truncated to 1024 bytes.
+    pub fn with_body_logging(mut self, body_logging: BodyLogging) -> Self {
+        self.body_logging = body_logging;
+        self
+    }
+
+    /// Retry requests answered with `429 Too Many Requests` or a server
+    /// error, spending at most `retries` retries in each window of `per`.
+    /// Creates and actions (POST requests) are only retried on `429`, as the
+    /// API may have acted on them before failing.
+    ///
+    /// The budget is shared by every clone of the client, so that during an
+    /// outage concurrent tasks do not all keep retrying at once. Once it is
+    /// spent requests fail straight away with the error of their response,
+    /// until the next window starts. A single request is retried at most 3
+    /// times, unless it sets its own
+    /// [`max_retries()`](request/struct.Request.html#method.max_retries),
+    /// waiting as long as `Retry-After` says or else backing off
+    /// exponentially from 250 milliseconds.
+    ///
+    /// By default requests are not retried.
+    pub fn with_retry_budget(mut self, retries: u32, per: Duration) -> Self {
+        self.retry_budget = Some(Arc::new(retry::RetryBudget::new(retries, per)));
+        self
+    }
+
+    /// Give up on requests which have not completed after `timeout`, unless
+    /// a request sets its own [`timeout()`](request/struct.Request.html#method.timeout).
+    ///
+    /// By default requests do not time out.
+    pub fn with_timeout(mut self, timeout: Duration) -> Result<Self, Error> {
+        self.client_config.timeout = Some(timeout);
+        self.client = self.client_config.build()?;
+        Ok(self)
+    }
+
+    /// Tune the connection pool of the underlying HTTP client, keeping at most
+    /// `max_idle_per_host` idle connections open, each for up to
+    /// `idle_timeout`.
+    ///
+    /// By default the number of idle connections is unbounded and they are
+    /// closed after 90 seconds. Long running provisioners issuing many
+    /// concurrent requests may want a bound close to their concurrency, and
+    /// a timeout below that of any proxy in between.
+    pub fn with_pool_config(
+        mut self,
+        max_idle_per_host: usize,
+        idle_timeout: Duration,
+    ) -> Result<Self, Error> {
+        self.client_config.pool = Some((max_idle_per_host, idle_timeout));
+        self.client = self.client_config.build()?;
+        Ok(self)
+    }
+
+    /// Ask for gzip compressed responses with `Accept-Encoding: gzip` and
+    /// decode them transparently.
+    ///
+    /// Compression pays off for large lists: a page of 200 Droplets is about
+    /// 330 KB of JSON, which the API sends as about 10 KB.
+    ///
+    /// By default responses are compressed.
+    pub fn with_compression(mut self, compression: bool) -> Result<Self, Error> {
+        self.client_config.compression = compression;
+        self.client = self.client_config.build()?;
+        Ok(self)
+    }
+
+    /// **Dangerous:** accept any TLS certificate from the server, including
+    /// self-signed, expired and mismatched ones, which leaves the token open
+    /// to anyone able to intercept the connection.
+    ///
+    /// Only meant for development against an intercepting proxy such as
+    /// mitmproxy. Certificates are verified unless this is called with
+    /// `true`.
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Result<Self, Error> {
+        if accept {
+            warn!("TLS certificate verification is disabled.");
+        }
+        self.client_config.accept_invalid_certs = accept;
+        self.client = self.client_config.build()?;
+        Ok(self)
+    }
+
     pub async fn execute<A: Method, V: HasResponse>(
         &self,
         request: Request<A, V>,
@@ -141,4 +381,31 @@ impl DigitalOcean {
     {
         request.execute(self).await
     }
+
+    /// Fetch a single page of a list from its url, such as a `next` url kept
+    /// from an earlier session, returning its values and the url of the page
+    /// after it, if there is one.
+    ///
+    /// Returns `Error::InvalidRequest` for urls outside of the DigitalOcean
+    /// API, the token is never sent anywhere else.
+    pub async fn get_page<V>(&self, url: Url) -> Result<(Vec<V>, Option<Url>), Error>
+    where
+        Vec<V>: HasResponse,
+        <Vec<V> as HasResponse>::Response: HasPagination,
+    {
+        let is_api = url.scheme() == ROOT_URL.scheme()
+            && url.host() == ROOT_URL.host()
+            && url.port_or_known_default() == ROOT_URL.port_or_known_default()
+            && url.username().is_empty()
+            && url.password().is_none()
+            && url.path().starts_with(&format!("{}/", ROOT_URL.path()));
+
+        if !is_api {
+            return Err(Error::InvalidRequest(format!(
+                "{url} is not a DigitalOcean API url"
+            )));
+        }
+
+        self.page(url, Overrides::default()).await
+    }
 }
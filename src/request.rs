@@ -2,15 +2,19 @@
 //!
 //!
 
-use crate::api::{HasPagination, HasResponse};
+use crate::api::{HasPagination, HasResponse, MAX_PER_PAGE};
 use crate::error::Error;
 use crate::method::{Create, Delete, Get, List, Method, Update};
 use crate::DigitalOcean;
 use async_trait::async_trait;
 use getset::{Getters, MutGetters, Setters};
+use log::info;
 use serde::Deserialize;
 use serde_json::Value;
+use std::fmt;
 use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
 use url::Url;
 
 mod url_serde {
@@ -49,10 +53,16 @@ pub type DomainRequest<M, V> = Request<M, V>;
 pub type DropletActionRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, Droplet>`](struct.Request.html) specific functions.
 pub type DropletRequest<M, V> = Request<M, V>;
+/// A type alias with [`Request<_, Firewall>`](struct.Request.html) specific functions.
+pub type FirewallRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, FloatingIpAction>`](struct.Request.html) specific functions.
 pub type FloatingIpActionRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, FloatingIp>`](struct.Request.html) specific functions.
 pub type FloatingIpRequest<M, V> = Request<M, V>;
+/// A type alias with [`Request<_, FunctionNamespace>`](struct.Request.html) specific functions.
+pub type FunctionNamespaceRequest<M, V> = Request<M, V>;
+/// A type alias with [`Request<_, FunctionTrigger>`](struct.Request.html) specific functions.
+pub type FunctionTriggerRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, ImageAction>`](struct.Request.html) specific functions.
 pub type ImageActionRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, Image>`](struct.Request.html) specific functions.
@@ -61,12 +71,18 @@ pub type ImageRequest<M, V> = Request<M, V>;
 pub type CustomImageRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, LoadBalancer>`](struct.Request.html) specific functions.
 pub type LoadBalancerRequest<M, V> = Request<M, V>;
+/// A type alias with [`Request<_, Metrics>`](struct.Request.html) specific functions.
+pub type MetricsRequest<M, V> = Request<M, V>;
+/// A type alias with [`Request<_, Project>`](struct.Request.html) specific functions.
+pub type ProjectRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, Region>`](struct.Request.html) specific functions.
 pub type RegionRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, Size>`](struct.Request.html) specific functions.
 pub type SizeRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, Snapshot>`](struct.Request.html) specific functions.
 pub type SnapshotRequest<M, V> = Request<M, V>;
+/// A type alias with [`Request<_, SpacesKey>`](struct.Request.html) specific functions.
+pub type SpacesKeyRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, SshKey>`](struct.Request.html) specific functions.
 pub type SshKeyRequest<M, V> = Request<M, V>;
 /// A type alias with [`Request<_, Tag>`](struct.Request.html) specific functions.
@@ -97,9 +113,54 @@ pub struct Request<A: Method, R> {
     #[get = "pub"]
     method: A,
 
+    /// Extra headers sent with the request, such as the confirmation some
+    /// destructive endpoints require.
+    #[get = "pub"]
+    #[serde(skip)]
+    headers: Vec<(String, String)>,
+
+    /// Settings of the client which are overridden for this request.
+    #[serde(skip)]
+    overrides: Overrides,
+
+    /// Applied to the value once it has been retrieved, for example to filter
+    /// list results the API cannot filter server side.
+    #[serde(skip)]
+    post_process: Option<PostProcess<R>>,
+
+    /// Checks the body before the request is sent.
+    #[serde(skip)]
+    validator: Option<Validator>,
+
     value: PhantomData<R>,
 }
 
+/// Settings of a single request which take precedence over those of the
+/// client.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Overrides {
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) max_retries: Option<usize>,
+}
+
+/// Checks the body of a request before it is sent.
+type Validator = fn(&Value) -> Result<(), Error>;
+
+/// A transformation applied to the value of an executed request.
+struct PostProcess<V>(Arc<dyn Fn(V) -> V + Send + Sync>);
+
+impl<V> Clone for PostProcess<V> {
+    fn clone(&self) -> Self {
+        PostProcess(self.0.clone())
+    }
+}
+
+impl<V> fmt::Debug for PostProcess<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("PostProcess")
+    }
+}
+
 impl<A: Method, V> Request<A, V> {
     /// Create a request pointing at the given url. `V` is the value ultimately
     /// returned when the call is executed.
@@ -108,23 +169,195 @@ impl<A: Method, V> Request<A, V> {
             url,
             body: Value::Null,
             method: A::default(),
+            headers: Vec::new(),
+            overrides: Overrides::default(),
+            post_process: None,
+            validator: None,
             value: PhantomData,
         }
     }
 
+    /// Queue a transformation of the value once the request has been executed.
+    /// Transformations run in the order they were added.
+    pub(crate) fn post_process<F>(mut self, f: F) -> Self
+    where
+        F: Fn(V) -> V + Send + Sync + 'static,
+        V: 'static,
+    {
+        self.post_process = Some(match self.post_process.take() {
+            Some(PostProcess(previous)) => PostProcess(Arc::new(move |value| f(previous(value)))),
+            None => PostProcess(Arc::new(f)),
+        });
+        self
+    }
+
+    /// Check the request for mistakes which would make the API refuse it.
+    /// This is done automatically when the request is executed.
+    pub fn validate(&self) -> Result<(), Error> {
+        match self.validator {
+            Some(validator) => validator(&self.body),
+            None => Ok(()),
+        }
+    }
+
+    /// Give up on this request once `timeout` has passed, instead of after the
+    /// timeout of the client, if it has one.
+    ///
+    /// For slow endpoints, such as importing a custom image.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.overrides.timeout = Some(timeout);
+        self
+    }
+
+    /// Retry this request at most `max_retries` times when it is rate
+    /// limited or meets a server error, instead of as often as the client
+    /// does. `0` disables retries.
+    ///
+    /// Retries still draw from the
+    /// [retry budget](../struct.DigitalOcean.html#method.with_retry_budget)
+    /// of the client if it has one. If it has none, this enables retries for
+    /// this request alone.
+    pub fn max_retries(mut self, max_retries: usize) -> Self {
+        self.overrides.max_retries = Some(max_retries);
+        self
+    }
+
+    pub(crate) fn overrides(&self) -> Overrides {
+        self.overrides
+    }
+
+    /// Send an extra header with the request.
+    pub(crate) fn header<N: Into<String>, S: Into<String>>(mut self, name: N, value: S) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Set the check run by `validate()`.
+    pub(crate) fn validator(mut self, validator: Validator) -> Self {
+        self.validator = Some(validator);
+        self
+    }
+
+    fn finish(post_process: Option<PostProcess<V>>, value: V) -> V {
+        match post_process {
+            Some(PostProcess(f)) => f(value),
+            None => value,
+        }
+    }
+
+    /// Execute the request, returning the JSON body of the response without
+    /// parsing it into `V`.
+    ///
+    /// This gives access to fields the crate does not model yet. List
+    /// requests only return the first page. Responses without content are
+    /// returned as `Value::Null`.
+    pub async fn execute_raw(self, instance: &DigitalOcean) -> Result<Value, Error> {
+        self.validate()?;
+        instance.raw(self).await
+    }
+
+    /// Change the method and value of the request. The url, body and headers
+    /// are moved, not copied.
     pub(crate) fn transmute<C: Method, D>(self) -> Request<C, D> {
         let mut req = Request::new(self.url);
         req.set_body(self.body);
+        req.headers = self.headers;
+        req.overrides = self.overrides;
         req
     }
 }
 
 impl<V> Request<List, V> {
     /// Impose a limit on the number of values which may be retrieved from a request.
+    ///
+    /// Without a limit, executing a list request follows the pagination links
+    /// until every page has been fetched. With one, pages stop being requested
+    /// as soon as enough values have been retrieved. Use
+    /// [`cursor()`](#method.cursor) to fetch pages on demand instead.
     pub fn limit(mut self, limit: Option<usize>) -> Self {
         self.method.0 = limit;
         self
     }
+
+    /// The url of the first page, sized according to the limit.
+    pub(crate) fn first_page(&self) -> Url {
+        let per_page = match self.method.0 {
+            Some(limit) if limit < MAX_PER_PAGE => limit,
+            _ => MAX_PER_PAGE,
+        };
+
+        let mut url = self.url.clone();
+        url.query_pairs_mut()
+            .append_pair("per_page", &per_page.to_string());
+        url
+    }
+}
+
+impl<V: 'static> Request<List, Vec<V>> {
+    /// Only keep the values matching `predicate` once they have been retrieved.
+    ///
+    /// **Note:** The filter is applied locally after pagination, so a request
+    /// with a `limit()` may return fewer values than the limit.
+    pub fn filter<F>(self, predicate: F) -> Self
+    where
+        F: Fn(&V) -> bool + Send + Sync + 'static,
+    {
+        self.post_process(move |values: Vec<V>| {
+            values.into_iter().filter(|v| predicate(v)).collect()
+        })
+    }
+}
+
+impl<V> Request<List, Vec<V>>
+where
+    Vec<V>: HasResponse,
+    <Vec<V> as HasResponse>::Response: HasPagination,
+{
+    /// Walk the pages of a list one at a time instead of buffering all of them.
+    ///
+    /// Pages are sized according to `limit()`, but the limit does not stop the
+    /// cursor. Filters are applied to each page individually.
+    pub fn cursor(mut self, instance: &DigitalOcean) -> PageCursor<'_, V> {
+        PageCursor {
+            instance,
+            next: Some(self.first_page()),
+            overrides: self.overrides,
+            post_process: self.post_process.take(),
+        }
+    }
+}
+
+/// Fetches the pages of a list request on demand.
+///
+/// Created by [`Request::cursor()`](struct.Request.html#method.cursor).
+pub struct PageCursor<'a, V> {
+    instance: &'a DigitalOcean,
+    next: Option<Url>,
+    overrides: Overrides,
+    post_process: Option<PostProcess<Vec<V>>>,
+}
+
+impl<V> PageCursor<'_, V>
+where
+    Vec<V>: HasResponse,
+    <Vec<V> as HasResponse>::Response: HasPagination,
+{
+    /// Fetch the next page, returning `None` once every page has been seen.
+    pub async fn next_page(&mut self) -> Result<Option<Vec<V>>, Error> {
+        let url = match self.next.take() {
+            Some(url) => url,
+            None => return Ok(None),
+        };
+
+        info!("PAGE {:?}", url);
+        let (values, next) = self.instance.page::<V>(url, self.overrides).await?;
+        self.next = next;
+
+        Ok(Some(Request::<List, Vec<V>>::finish(
+            self.post_process.clone(),
+            values,
+        )))
+    }
 }
 
 /// Describes an API call which can be executed.
@@ -134,6 +367,8 @@ pub trait Executable<T: HasResponse>: Sized {
     async fn execute(self, instance: &DigitalOcean) -> Result<T, Error>;
 }
 
+/// Fetches every page of the list, or only as many as needed to satisfy
+/// [`limit()`](struct.Request.html#method.limit).
 #[async_trait]
 impl<V> Executable<Vec<V>> for Request<List, Vec<V>>
 where
@@ -141,39 +376,48 @@ where
     <Vec<V> as HasResponse>::Response: HasPagination,
     V: std::marker::Send,
 {
-    async fn execute(self, instance: &DigitalOcean) -> Result<Vec<V>, Error> {
+    async fn execute(mut self, instance: &DigitalOcean) -> Result<Vec<V>, Error> {
+        self.validate()?;
+        let post_process = self.post_process.take();
         let response: Vec<V> = instance.list(self).await?;
-        Ok(response)
+        Ok(Self::finish(post_process, response))
     }
 }
 
 #[async_trait]
 impl<V: HasResponse + std::marker::Send> Executable<V> for Request<Create, V> {
-    async fn execute(self, instance: &DigitalOcean) -> Result<V, Error> {
+    async fn execute(mut self, instance: &DigitalOcean) -> Result<V, Error> {
+        self.validate()?;
+        let post_process = self.post_process.take();
         let response = instance.post(self).await?;
-        Ok(response)
+        Ok(Self::finish(post_process, response))
     }
 }
 
 #[async_trait]
 impl<V: HasResponse + std::marker::Send> Executable<V> for Request<Update, V> {
-    async fn execute(self, instance: &DigitalOcean) -> Result<V, Error> {
+    async fn execute(mut self, instance: &DigitalOcean) -> Result<V, Error> {
+        self.validate()?;
+        let post_process = self.post_process.take();
         let response = instance.put(self).await?;
-        Ok(response)
+        Ok(Self::finish(post_process, response))
     }
 }
 
 #[async_trait]
 impl<V: HasResponse + std::marker::Send> Executable<V> for Request<Get, V> {
-    async fn execute(self, instance: &DigitalOcean) -> Result<V, Error> {
+    async fn execute(mut self, instance: &DigitalOcean) -> Result<V, Error> {
+        self.validate()?;
+        let post_process = self.post_process.take();
         let response = instance.get(self).await?;
-        Ok(response)
+        Ok(Self::finish(post_process, response))
     }
 }
 
 #[async_trait]
 impl Executable<()> for Request<Delete, ()> {
     async fn execute(self, instance: &DigitalOcean) -> Result<(), Error> {
+        self.validate()?;
         instance.delete(self).await
     }
 }
@@ -0,0 +1,98 @@
+//! Retrying requests the API answered with a rate limit or a server error.
+
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Response, StatusCode};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// The most retries spent on a single request, however large the budget,
+/// unless the request sets its own `max_retries()`.
+pub(crate) const MAX_RETRIES_PER_REQUEST: usize = 3;
+
+/// The wait before the first retry, doubled for each further one, unless the
+/// response says how long to wait with `Retry-After`.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+
+/// The longest wait between retries when there is no budget window to bound
+/// it.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// A token bucket shared by every clone of a client, allowing at most
+/// `retries` retries in each window of `per`, across all the requests in
+/// flight.
+#[derive(Debug)]
+pub(crate) struct RetryBudget {
+    retries: u32,
+    per: Duration,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: u32,
+    window_start: Instant,
+}
+
+impl RetryBudget {
+    pub(crate) fn new(retries: u32, per: Duration) -> Self {
+        RetryBudget {
+            retries,
+            per,
+            bucket: Mutex::new(Bucket {
+                tokens: retries,
+                window_start: Instant::now(),
+            }),
+        }
+    }
+
+    /// Take a token for one retry, or `false` when the budget of the current
+    /// window is spent.
+    pub(crate) fn try_take(&self) -> bool {
+        let mut bucket = self.bucket.lock().expect("retry budget poisoned");
+
+        if bucket.window_start.elapsed() >= self.per {
+            bucket.tokens = self.retries;
+            bucket.window_start = Instant::now();
+        }
+
+        if bucket.tokens == 0 {
+            return false;
+        }
+        bucket.tokens -= 1;
+        true
+    }
+}
+
+/// How long to wait before retry number `attempt`, counting from zero.
+/// `Retry-After` is honoured, but never beyond the window of the budget.
+pub(crate) fn backoff(
+    response: &Response,
+    attempt: usize,
+    budget: Option<&RetryBudget>,
+) -> Duration {
+    let exponential = u32::try_from(attempt)
+        .ok()
+        .and_then(|attempt| 2u32.checked_pow(attempt))
+        .and_then(|factor| INITIAL_BACKOFF.checked_mul(factor))
+        .unwrap_or(MAX_BACKOFF);
+
+    response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(exponential)
+        .min(budget.map_or(MAX_BACKOFF, |budget| budget.per))
+}
+
+/// Whether a response is worth retrying: the rate limit was hit, or the API
+/// had a server error on a request which is safe to repeat.
+///
+/// A server error on a POST may come after the API already acted on it, so
+/// retrying could create a second Droplet or run an action twice. A request
+/// refused by the rate limit was not acted on and is always retried.
+pub(crate) fn is_retryable(verb: &str, status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || (status.is_server_error() && matches!(verb, "GET" | "PUT" | "DELETE"))
+}
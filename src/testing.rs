@@ -0,0 +1,271 @@
+//! An in-memory stand-in for the DigitalOcean API.
+//!
+//! Enabled by the `testing` feature. A [`MockDigitalOcean`](struct.MockDigitalOcean.html)
+//! dereferences to a [`DigitalOcean`](../struct.DigitalOcean.html) client, so requests are
+//! executed exactly as they would be against the real API. Instead of touching the network,
+//! each call is answered with a canned response registered for its method and path.
+
+use crate::error::Error;
+use crate::DigitalOcean;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use reqwest::header::HeaderMap;
+use reqwest::{Method, Request, Response, StatusCode};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::io::{Read, Write};
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use url::Url;
+
+/// The path prefix of the API root, stripped from request paths before lookup.
+const ROOT_PATH: &str = "/v2";
+
+/// A canned response waiting to be served.
+#[derive(Debug, Clone)]
+struct Canned {
+    status: StatusCode,
+    content_type: String,
+    body: Vec<u8>,
+    /// Whether `body` is gzip compressed.
+    gzip: bool,
+}
+
+/// Queued responses for each method and path.
+type Registry = HashMap<(Method, String), VecDeque<Canned>>;
+
+/// A request answered by a [`MockDigitalOcean`](struct.MockDigitalOcean.html).
+#[derive(Debug, Clone)]
+pub struct ReceivedRequest {
+    pub method: Method,
+    pub url: Url,
+    pub headers: HeaderMap,
+    /// The raw body sent, empty if there was none.
+    pub body: Vec<u8>,
+    /// The timeout set on the request itself, if any.
+    pub timeout: Option<Duration>,
+}
+
+/// Canned responses keyed by method and path, shared between clones of a client.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Mocks {
+    responses: Arc<Mutex<Registry>>,
+    received: Arc<Mutex<Vec<ReceivedRequest>>>,
+}
+
+impl Mocks {
+    fn register(&self, method: Method, path: String, canned: Canned) {
+        let mut responses = self.responses.lock().expect("mock registry poisoned");
+        responses
+            .entry((method, path))
+            .or_default()
+            .push_back(canned);
+    }
+
+    /// Answer a request with the next canned response for its method and path.
+    ///
+    /// Responses registered for the same key are served in order, the last
+    /// one is repeated once the others have been used up. Unknown requests
+    /// are answered with `404 Not Found`. Like the real API, every response
+    /// carries an `x-request-id`, which counts the requests answered so far:
+    /// `mock-1`, `mock-2` and so on.
+    ///
+    /// Compressed responses are decoded when `compression` is enabled, as
+    /// the HTTP client would, and served as they are otherwise.
+    pub(crate) fn answer(&self, request: Request, compression: bool) -> Result<Response, Error> {
+        let request_id = {
+            let mut received = self.received.lock().expect("mock registry poisoned");
+            received.push(ReceivedRequest {
+                method: request.method().clone(),
+                url: request.url().clone(),
+                headers: request.headers().clone(),
+                body: request
+                    .body()
+                    .and_then(|body| body.as_bytes())
+                    .map(<[u8]>::to_vec)
+                    .unwrap_or_default(),
+                timeout: request.timeout().copied(),
+            });
+            format!("mock-{}", received.len())
+        };
+
+        let path = request.url().path();
+        let path = path.strip_prefix(ROOT_PATH).unwrap_or(path).to_string();
+        let key = (request.method().clone(), path);
+
+        let canned = {
+            let mut responses = self.responses.lock().expect("mock registry poisoned");
+            match responses.get_mut(&key) {
+                Some(queue) if queue.len() > 1 => queue.pop_front(),
+                Some(queue) => queue.front().cloned(),
+                None => None,
+            }
+        };
+
+        let canned = canned.unwrap_or(Canned {
+            status: StatusCode::NOT_FOUND,
+            content_type: "application/json".to_string(),
+            body: Vec::new(),
+            gzip: false,
+        });
+
+        let mut response = http::Response::builder()
+            .status(canned.status)
+            .header(reqwest::header::CONTENT_TYPE, canned.content_type)
+            .header("x-request-id", request_id);
+
+        let body = match (canned.gzip, compression) {
+            (true, true) => {
+                let mut body = Vec::new();
+                GzDecoder::new(canned.body.as_slice())
+                    .read_to_end(&mut body)
+                    .expect("canned gzip bodies are always valid");
+                body
+            }
+            (true, false) => {
+                response = response.header(reqwest::header::CONTENT_ENCODING, "gzip");
+                canned.body
+            }
+            (false, _) => canned.body,
+        };
+
+        let response = response
+            .body(body)
+            .expect("canned responses are always valid");
+
+        Ok(Response::from(response))
+    }
+}
+
+/// A DigitalOcean client which never touches the network.
+///
+/// Register canned responses with [`respond()`](#method.respond) and pass the mock anywhere a
+/// `&DigitalOcean` is expected.
+#[derive(Clone)]
+pub struct MockDigitalOcean {
+    instance: DigitalOcean,
+    mocks: Mocks,
+}
+
+impl MockDigitalOcean {
+    /// Create a mock client without any registered responses.
+    pub fn new() -> Self {
+        let mocks = Mocks::default();
+        let mut instance = DigitalOcean::new("mock").expect("mock client is always valid");
+        instance.mocks = Some(mocks.clone());
+
+        MockDigitalOcean { instance, mocks }
+    }
+
+    /// Register a response for requests using `method` on `path`.
+    ///
+    /// `path` is relative to the API root, for example `/droplets/123`. Query
+    /// strings are ignored when matching.
+    pub fn respond<S: AsRef<str>>(
+        self,
+        method: Method,
+        path: S,
+        status: StatusCode,
+        body: Value,
+    ) -> Self {
+        let body = match body {
+            Value::Null => Vec::new(),
+            body => body.to_string().into_bytes(),
+        };
+        let canned = Canned {
+            status,
+            content_type: "application/json".to_string(),
+            body,
+            gzip: false,
+        };
+
+        self.mocks
+            .register(method, path.as_ref().to_string(), canned);
+        self
+    }
+
+    /// Register a gzip compressed JSON response for requests using `method`
+    /// on `path`. It is decoded like a real one unless compression has been
+    /// disabled with
+    /// [`with_compression()`](../struct.DigitalOcean.html#method.with_compression).
+    pub fn respond_gzip<S: AsRef<str>>(
+        self,
+        method: Method,
+        path: S,
+        status: StatusCode,
+        body: Value,
+    ) -> Self {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(body.to_string().as_bytes())
+            .expect("writing to a Vec never fails");
+        let canned = Canned {
+            status,
+            content_type: "application/json".to_string(),
+            body: encoder.finish().expect("writing to a Vec never fails"),
+            gzip: true,
+        };
+
+        self.mocks
+            .register(method, path.as_ref().to_string(), canned);
+        self
+    }
+
+    /// Register a response which is not JSON, such as the HTML page served
+    /// while the API is under maintenance, for requests using `method` on `path`.
+    pub fn respond_text<S: AsRef<str>, T: Into<String>>(
+        self,
+        method: Method,
+        path: S,
+        status: StatusCode,
+        content_type: &str,
+        body: T,
+    ) -> Self {
+        let canned = Canned {
+            status,
+            content_type: content_type.to_string(),
+            body: body.into().into_bytes(),
+            gzip: false,
+        };
+
+        self.mocks
+            .register(method, path.as_ref().to_string(), canned);
+        self
+    }
+
+    /// Apply client configuration, such as
+    /// [`with_user_agent()`](../struct.DigitalOcean.html#method.with_user_agent),
+    /// while keeping the registered responses.
+    pub fn configure<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(DigitalOcean) -> DigitalOcean,
+    {
+        self.instance = f(self.instance);
+        self
+    }
+
+    /// Every request answered so far, oldest first.
+    pub fn received(&self) -> Vec<ReceivedRequest> {
+        self.mocks
+            .received
+            .lock()
+            .expect("mock registry poisoned")
+            .clone()
+    }
+}
+
+impl Default for MockDigitalOcean {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for MockDigitalOcean {
+    type Target = DigitalOcean;
+
+    fn deref(&self) -> &DigitalOcean {
+        &self.instance
+    }
+}
@@ -0,0 +1,72 @@
+//! Polling for resources which change state asynchronously.
+
+use crate::error::Error;
+use log::info;
+use std::future::{poll_fn, Future};
+use std::pin::{pin, Pin};
+use std::task::Poll;
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+
+/// Call `check` every `interval` until it produces a value, giving up with
+/// `Error::Timeout` once `timeout` has passed. A `timeout` too large to be
+/// represented, such as `Duration::MAX`, waits forever.
+///
+/// The loop stops with `Error::Cancelled` as soon as `cancelled` completes,
+/// dropping any request which is still in flight. Pass
+/// `std::future::pending()` for a wait which cannot be cancelled.
+///
+/// `what` describes the awaited state in the timeout error.
+pub(crate) async fn poll<T, F, Fut, C>(
+    what: &str,
+    interval: Duration,
+    timeout: Duration,
+    cancelled: C,
+    mut check: F,
+) -> Result<T, Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Option<T>, Error>>,
+    C: Future<Output = ()>,
+{
+    let deadline = Instant::now().checked_add(timeout);
+    let mut cancelled = pin!(cancelled);
+
+    loop {
+        if let Some(value) = race(cancelled.as_mut(), check()).await? {
+            return Ok(value);
+        }
+
+        if let Some(deadline) = deadline
+            && Instant::now()
+                .checked_add(interval)
+                .is_none_or(|next| next > deadline)
+        {
+            return Err(Error::Timeout(what.to_string()));
+        }
+
+        info!("Waiting for {}...", what);
+        race(cancelled.as_mut(), async {
+            sleep(interval).await;
+            Ok(())
+        })
+        .await?;
+    }
+}
+
+/// Drive `work` to completion unless `cancelled` completes first.
+async fn race<T, C, W>(mut cancelled: Pin<&mut C>, work: W) -> Result<T, Error>
+where
+    C: Future<Output = ()>,
+    W: Future<Output = Result<T, Error>>,
+{
+    let mut work = pin!(work);
+
+    poll_fn(|cx| {
+        if cancelled.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(Err(Error::Cancelled));
+        }
+        work.as_mut().poll(cx)
+    })
+    .await
+}
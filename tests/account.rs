@@ -1,6 +1,7 @@
 extern crate digitalocean;
 #[macro_use]
 extern crate log;
+#[macro_use]
 extern crate serde_json;
 extern crate url;
 extern crate url_serde;
@@ -9,7 +10,7 @@ mod utils;
 
 use serde_json::Value;
 
-use digitalocean::api::Account;
+use digitalocean::api::{Account, HasResponse, HasValue};
 use digitalocean::method::Get;
 use digitalocean::request::Request;
 
@@ -27,3 +28,50 @@ fn get_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+fn account(team: Value) -> Value {
+    json!({
+        "account": {
+            "droplet_limit": 25,
+            "floating_ip_limit": 5,
+            "email": "sammy@digitalocean.com",
+            "uuid": "b6fr89dbf6d9156cace5f3c78dc9851d957381ef",
+            "email_verified": true,
+            "status": "active",
+            "status_message": "",
+            "team": team
+        }
+    })
+}
+
+#[test]
+fn team_context_deserializes() {
+    before();
+
+    let response: <Account as HasResponse>::Response = serde_json::from_value(account(json!({
+        "uuid": "5df3e3004a17e242b7c20ca6c9fc25b701a47ece",
+        "name": "My Team"
+    })))
+    .unwrap();
+
+    let account = response.value();
+    info!("{:#?}", account);
+
+    let team = account.team().as_ref().unwrap();
+    assert_eq!(team.uuid, "5df3e3004a17e242b7c20ca6c9fc25b701a47ece");
+    assert_eq!(team.name, "My Team");
+}
+
+#[test]
+fn personal_account_has_no_team() {
+    before();
+
+    let mut fixture = account(Value::Null);
+    fixture["account"].as_object_mut().unwrap().remove("team");
+    let response: <Account as HasResponse>::Response = serde_json::from_value(fixture).unwrap();
+
+    let account = response.value();
+    info!("{:#?}", account);
+
+    assert!(account.team().is_none());
+}
@@ -0,0 +1,193 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+extern crate serde_json;
+extern crate url;
+
+mod utils;
+
+use std::net::IpAddr;
+
+use serde_json::Value;
+
+use digitalocean::api::{Action, Droplet, FloatingIp, Image, Volume};
+use digitalocean::method::{Get, List};
+use digitalocean::request::Request;
+
+use crate::utils::before;
+
+#[test]
+fn list_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/actions";
+
+    let req: Request<List, Vec<Action>> = Action::list();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn get_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/actions/36804636";
+
+    let req: Request<Get, Action> = Action::get(36804636);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn history_get_produces_correct_request() {
+    before();
+
+    let cases: Vec<(Request<Get, Action>, &str)> = vec![
+        (
+            Action::list().get(36804636),
+            "https://api.digitalocean.com/v2/actions/36804636",
+        ),
+        (
+            Droplet::get(123).actions().get(456),
+            "https://api.digitalocean.com/v2/droplets/123/actions/456",
+        ),
+        (
+            FloatingIp::get("45.55.96.47".parse::<IpAddr>().unwrap())
+                .actions()
+                .get(456),
+            "https://api.digitalocean.com/v2/floating_ips/45.55.96.47/actions/456",
+        ),
+        (
+            Image::get(123).actions().get(456),
+            "https://api.digitalocean.com/v2/images/123/actions/456",
+        ),
+        (
+            Volume::get("123").actions().get(456),
+            "https://api.digitalocean.com/v2/volumes/123/actions/456",
+        ),
+    ];
+
+    for (req, correct_url) in cases {
+        info!("{:#?}", req);
+
+        assert_eq!(req.url().as_str(), correct_url);
+        assert_eq!(*req.body(), Value::Null);
+    }
+}
+
+#[cfg(feature = "testing")]
+mod mock {
+    use chrono::{TimeZone, Utc};
+    use reqwest::{Method, StatusCode};
+    use serde_json::json;
+
+    use digitalocean::api::Action;
+    use digitalocean::testing::MockDigitalOcean;
+
+    use crate::utils::{before, block_on};
+
+    fn action(id: usize, started_at: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "status": "completed",
+            "type": "create",
+            "started_at": started_at,
+            "completed_at": started_at,
+            "resource_id": 3164444,
+            "resource_type": "droplet",
+            "region_slug": "nyc3"
+        })
+    }
+
+    #[test]
+    fn since_stops_at_the_first_older_action() {
+        before();
+
+        let mock = MockDigitalOcean::new()
+            .respond(
+                Method::GET,
+                "/actions",
+                StatusCode::OK,
+                json!({
+                    "actions": [
+                        action(5, "2024-05-05T12:00:00Z"),
+                        action(4, "2024-05-04T12:00:00Z")
+                    ],
+                    "links": {
+                        "pages": {
+                            "next": "https://api.digitalocean.com/v2/actions?page=2&per_page=2"
+                        }
+                    },
+                    "meta": { "total": 6 }
+                }),
+            )
+            .respond(
+                Method::GET,
+                "/actions",
+                StatusCode::OK,
+                json!({
+                    "actions": [
+                        action(3, "2024-05-03T12:00:00Z"),
+                        action(2, "2024-05-02T12:00:00Z")
+                    ],
+                    "links": {
+                        "pages": {
+                            "next": "https://api.digitalocean.com/v2/actions?page=3&per_page=2"
+                        }
+                    },
+                    "meta": { "total": 6 }
+                }),
+            )
+            .respond(
+                Method::GET,
+                "/actions",
+                StatusCode::OK,
+                json!({
+                    "actions": [
+                        action(1, "2024-05-01T12:00:00Z"),
+                        action(0, "2024-04-30T12:00:00Z")
+                    ],
+                    "links": {},
+                    "meta": { "total": 6 }
+                }),
+            );
+
+        let after = Utc.with_ymd_and_hms(2024, 5, 2, 12, 0, 0).unwrap();
+        let actions = block_on(Action::list().since(&mock, after)).unwrap();
+        info!("{:#?}", actions);
+
+        let ids: Vec<usize> = actions.iter().map(|a| *a.id()).collect();
+        assert_eq!(ids, vec![5, 4, 3]);
+        assert_eq!(mock.received().len(), 2);
+    }
+
+    #[test]
+    fn since_fetches_every_page_when_all_are_newer() {
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::GET,
+            "/actions",
+            StatusCode::OK,
+            json!({
+                "actions": [
+                    action(2, "2024-05-02T12:00:00Z"),
+                    action(1, "2024-05-01T12:00:00Z")
+                ],
+                "links": {},
+                "meta": { "total": 2 }
+            }),
+        );
+
+        let after = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let actions = block_on(Action::list().since(&mock, after)).unwrap();
+        info!("{:#?}", actions);
+
+        assert_eq!(actions.len(), 2);
+        assert_eq!(mock.received().len(), 1);
+    }
+}
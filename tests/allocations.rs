@@ -0,0 +1,62 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+extern crate serde_json;
+
+mod utils;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use digitalocean::api::{Domain, DomainRecord};
+use digitalocean::method::Create;
+use digitalocean::request::Request;
+
+use crate::utils::before;
+
+/// Counts the bytes allocated by the current thread, so tests running in
+/// parallel do not disturb each other.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATED: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATED.try_with(|allocated| allocated.set(allocated.get() + layout.size()));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocated_by<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATED.with(Cell::get);
+    let value = f();
+    (value, ALLOCATED.with(Cell::get) - before)
+}
+
+#[test]
+fn transmute_does_not_copy_the_body() {
+    before();
+
+    const SIZE: usize = 1 << 20;
+    let data = "x".repeat(SIZE);
+
+    // The body is built from `data` once, then the request changes type.
+    let (req, allocated): (Request<Create, DomainRecord>, usize) = allocated_by(|| {
+        Domain::get("example.com")
+            .records()
+            .create("TXT", "@", data.as_str())
+    });
+    info!("{} bytes allocated", allocated);
+
+    assert_eq!(req.body()["data"].as_str().map(str::len), Some(SIZE));
+    assert!(allocated < 2 * SIZE);
+}
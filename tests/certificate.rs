@@ -91,3 +91,151 @@ fn delete_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+#[test]
+fn create_lets_encrypt_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/certificates";
+    let (name, dns_names) = ("le-cert-01", vec!["example.com", "www.example.com"]);
+
+    let req: Request<Create, Certificate> = Certificate::create_lets_encrypt(name, dns_names);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "name": name,
+            "type": "lets_encrypt",
+            "dns_names": ["example.com", "www.example.com"],
+        })
+    );
+}
+
+#[cfg(feature = "testing")]
+mod mock {
+    use chrono::{NaiveDateTime, TimeDelta, Timelike, Utc};
+    use reqwest::{Method, StatusCode};
+    use serde_json::json;
+
+    use std::time::Duration;
+
+    use digitalocean::api::{Certificate, CertificateType};
+    use digitalocean::error::Error;
+    use digitalocean::testing::MockDigitalOcean;
+
+    use crate::utils::{before, block_on};
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    fn certificate(name: &str, kind: &str, expires_in: TimeDelta) -> serde_json::Value {
+        json!({
+            "certificate": {
+                "id": "892071a0-bb95-49bc-8021-3afd67a210bf",
+                "name": name,
+                "not_after": (Utc::now() + expires_in).to_rfc3339(),
+                "sha1_fingerprint": "dfcc9f57d86bf58e321c2c6c31c7a971be244ac7",
+                "created_at": "2017-02-08T16:02:37Z",
+                "dns_names": ["example.com", "www.example.com"],
+                "state": "verified",
+                "type": kind
+            }
+        })
+    }
+
+    fn mock(kind: &str, expires_in: TimeDelta) -> MockDigitalOcean {
+        MockDigitalOcean::new()
+            .respond(
+                Method::GET,
+                "/certificates/892071a0-bb95-49bc-8021-3afd67a210bf",
+                StatusCode::OK,
+                certificate("web-cert-01", kind, expires_in),
+            )
+            .respond(
+                Method::POST,
+                "/certificates",
+                StatusCode::CREATED,
+                certificate("web-cert-01-renewed", kind, TimeDelta::days(90)),
+            )
+    }
+
+    fn renew(mock: &MockDigitalOcean) -> Result<Option<Certificate>, Error> {
+        block_on(Certificate::get("892071a0-bb95-49bc-8021-3afd67a210bf").renew(mock, 30 * DAY))
+    }
+
+    #[test]
+    fn renew_issues_new_certificate_when_expiring() {
+        before();
+
+        let mock = mock("lets_encrypt", TimeDelta::days(10));
+
+        let started = Utc::now().naive_utc().with_nanosecond(0).unwrap();
+        let renewed = renew(&mock).unwrap().expect("certificate was not renewed");
+        info!("{:#?}", renewed);
+
+        assert_eq!(renewed.name(), "web-cert-01-renewed");
+        assert_eq!(*renewed.kind(), Some(CertificateType::LetsEncrypt));
+
+        let received = mock.received();
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[1].method, Method::POST);
+
+        let mut body: serde_json::Value = serde_json::from_slice(&received[1].body).unwrap();
+        info!("{:#?}", body);
+
+        // The time of renewal keeps a second renewal on the same day unique.
+        let name = body["name"].take();
+        let renewed_at = name
+            .as_str()
+            .and_then(|name| name.strip_prefix("web-cert-01-"))
+            .and_then(|at| NaiveDateTime::parse_from_str(at, "%Y%m%d%H%M%S").ok())
+            .expect("renewed name has no timestamp");
+        assert!(started <= renewed_at && renewed_at <= Utc::now().naive_utc());
+
+        assert_eq!(
+            body,
+            json!({
+                "name": null,
+                "type": "lets_encrypt",
+                "dns_names": ["example.com", "www.example.com"],
+            })
+        );
+    }
+
+    #[test]
+    fn renew_within_longest_duration_does_not_overflow() {
+        before();
+
+        let mock = mock("lets_encrypt", TimeDelta::days(3650));
+
+        let renewed = block_on(
+            Certificate::get("892071a0-bb95-49bc-8021-3afd67a210bf").renew(&mock, Duration::MAX),
+        )
+        .unwrap();
+        assert!(renewed.is_some());
+    }
+
+    #[test]
+    fn renew_leaves_certificate_not_yet_due() {
+        before();
+
+        let mock = mock("lets_encrypt", TimeDelta::days(60));
+
+        assert!(renew(&mock).unwrap().is_none());
+        assert_eq!(mock.received().len(), 1);
+    }
+
+    #[test]
+    fn renew_refuses_custom_certificate() {
+        before();
+
+        let mock = mock("custom", TimeDelta::days(10));
+
+        match renew(&mock) {
+            Err(Error::InvalidRequest(_)) => {}
+            other => panic!("expected InvalidRequest, got {:?}", other),
+        }
+        assert_eq!(mock.received().len(), 1);
+    }
+}
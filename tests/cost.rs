@@ -0,0 +1,194 @@
+#![cfg(feature = "testing")]
+
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+extern crate reqwest;
+#[macro_use]
+extern crate serde_json;
+
+mod utils;
+
+use reqwest::{Method, StatusCode};
+
+use digitalocean::api::{LOAD_BALANCER_PRICE, VOLUME_PRICE_PER_GIB};
+use digitalocean::testing::MockDigitalOcean;
+
+use crate::utils::{before, block_on};
+
+fn region() -> serde_json::Value {
+    json!({
+        "name": "New York 3",
+        "slug": "nyc3",
+        "sizes": ["s-1vcpu-1gb", "s-2vcpu-2gb"],
+        "features": [],
+        "available": true
+    })
+}
+
+fn size(slug: &str, price_monthly: f64) -> serde_json::Value {
+    json!({
+        "slug": slug,
+        "memory": 1024,
+        "vcpus": 1,
+        "disk": 25,
+        "transfer": 1.0,
+        "price_monthly": price_monthly,
+        "price_hourly": price_monthly / 672.0,
+        "regions": ["nyc3"],
+        "available": true
+    })
+}
+
+fn droplet(id: usize, size_slug: &str, size: Option<serde_json::Value>) -> serde_json::Value {
+    json!({
+        "id": id,
+        "name": format!("web-{}", id),
+        "memory": 1024,
+        "vcpus": 1,
+        "disk": 25,
+        "locked": false,
+        "status": "active",
+        "kernel": null,
+        "created_at": "2020-07-21T18:37:44Z",
+        "features": [],
+        "backup_ids": [],
+        "next_backup_window": null,
+        "snapshot_ids": [],
+        "image": {
+            "id": 63663980,
+            "name": "20.04 (LTS) x64",
+            "type": "snapshot",
+            "distribution": "Ubuntu",
+            "slug": "ubuntu-20-04-x64",
+            "public": true,
+            "regions": ["nyc3"],
+            "min_disk_size": 20,
+            "size_gigabytes": 2.36,
+            "created_at": "2020-05-15T05:47:50Z"
+        },
+        "volume_ids": [],
+        "size": size,
+        "size_slug": size_slug,
+        "networks": { "v4": [], "v6": [] },
+        "region": region(),
+        "tags": []
+    })
+}
+
+fn fleet() -> MockDigitalOcean {
+    MockDigitalOcean::new()
+        .respond(
+            Method::GET,
+            "/droplets",
+            StatusCode::OK,
+            json!({
+                "droplets": [
+                    droplet(1, "s-1vcpu-1gb", Some(size("s-1vcpu-1gb", 6.0))),
+                    droplet(2, "s-2vcpu-2gb", None),
+                    droplet(3, "retired-size", None)
+                ],
+                "links": {},
+                "meta": { "total": 3 }
+            }),
+        )
+        .respond(
+            Method::GET,
+            "/volumes",
+            StatusCode::OK,
+            json!({
+                "volumes": [
+                    {
+                        "id": "6fc4c277-ea5c-448a-93cd-dd496cfef71f",
+                        "region": region(),
+                        "droplet_ids": [1],
+                        "name": "data",
+                        "description": "",
+                        "size_gigabytes": 100,
+                        "created_at": "2016-03-02T17:00:49Z"
+                    }
+                ],
+                "links": {},
+                "meta": { "total": 1 }
+            }),
+        )
+        .respond(
+            Method::GET,
+            "/load_balancers",
+            StatusCode::OK,
+            json!({
+                "load_balancers": [
+                    {
+                        "id": "4de7ac8b-495b-4884-9a69-1050c6793cd6",
+                        "name": "example-lb-01",
+                        "ip": "104.131.186.241",
+                        "algorithm": "round_robin",
+                        "status": "active",
+                        "created_at": "2017-02-01T22:22:58Z",
+                        "forwarding_rules": [],
+                        "health_check": {
+                            "protocol": "http",
+                            "port": 80,
+                            "path": "/",
+                            "check_interval_seconds": 10,
+                            "response_timeout_seconds": 5,
+                            "healthy_threshold": 5,
+                            "unhealthy_threshold": 3
+                        },
+                        "sticky_sessions": { "type": "none" },
+                        "region": region(),
+                        "tag": "",
+                        "droplet_ids": [1, 2],
+                        "redirect_http_to_https": false
+                    }
+                ],
+                "links": {},
+                "meta": { "total": 1 }
+            }),
+        )
+        .respond(
+            Method::GET,
+            "/sizes",
+            StatusCode::OK,
+            json!({
+                "sizes": [size("s-1vcpu-1gb", 5.0), size("s-2vcpu-2gb", 18.0)],
+                "links": {},
+                "meta": { "total": 2 }
+            }),
+        )
+}
+
+#[test]
+fn estimated_monthly_cost_sums_fleet() {
+    before();
+
+    let mock = fleet();
+
+    let cost = block_on(mock.estimated_monthly_cost()).unwrap();
+    info!("{}", cost);
+
+    // The nested size of a Droplet wins over the size list, Droplets of an
+    // unknown size count as free.
+    let expected = 6.0 + 18.0 + 100.0 * VOLUME_PRICE_PER_GIB + LOAD_BALANCER_PRICE;
+    assert!((cost - expected).abs() < 1e-9, "{} != {}", cost, expected);
+    assert_eq!(mock.received().len(), 4);
+}
+
+#[test]
+fn estimated_monthly_cost_of_empty_account_is_zero() {
+    before();
+
+    let empty = |key: &str| json!({ key: [], "links": {}, "meta": { "total": 0 } });
+    let mock = MockDigitalOcean::new()
+        .respond(Method::GET, "/droplets", StatusCode::OK, empty("droplets"))
+        .respond(Method::GET, "/volumes", StatusCode::OK, empty("volumes"))
+        .respond(
+            Method::GET,
+            "/load_balancers",
+            StatusCode::OK,
+            empty("load_balancers"),
+        )
+        .respond(Method::GET, "/sizes", StatusCode::OK, empty("sizes"));
+
+    assert_eq!(block_on(mock.estimated_monthly_cost()).unwrap(), 0.0);
+}
@@ -8,8 +8,8 @@ extern crate url_serde;
 
 mod utils;
 
-use digitalocean::api::CustomImage;
-use digitalocean::method::Create;
+use digitalocean::api::{CustomImage, ImageStatus, ImageType};
+use digitalocean::method::{Create, Get};
 use digitalocean::request::Request;
 
 use crate::utils::before;
@@ -43,3 +43,137 @@ fn create_produces_correct_request() {
         })
     );
 }
+
+#[test]
+fn get_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/images/38413969";
+
+    let req: Request<Get, CustomImage> = CustomImage::get(38413969);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+}
+
+fn custom_image_fixture() -> serde_json::Value {
+    json!({
+        "id": 38413969,
+        "name": "ubuntu-18.04-minimal",
+        "distribution": "Ubuntu",
+        "regions": [],
+        "created_at": "2018-09-20T19:28:00Z",
+        "type": "custom",
+        "tags": ["base-image", "prod"],
+        "description": "Cloud-optimized image w/ small footprint",
+        "status": "NEW"
+    })
+}
+
+#[test]
+fn custom_image_deserializes_type_and_status() {
+    before();
+
+    let image: CustomImage = serde_json::from_value(custom_image_fixture()).unwrap();
+    info!("{:#?}", image);
+
+    assert_eq!(*image.kind(), ImageType::Custom);
+    assert_eq!(*image.status(), ImageStatus::New);
+}
+
+#[cfg(feature = "testing")]
+mod mock {
+    use reqwest::{Method, StatusCode};
+    use serde_json::json;
+    use std::time::Duration;
+
+    use digitalocean::api::{CustomImage, ImageStatus};
+    use digitalocean::error::Error;
+    use digitalocean::method::Create;
+    use digitalocean::request::Request;
+    use digitalocean::testing::MockDigitalOcean;
+
+    use super::custom_image_fixture;
+    use crate::utils::{before, block_on};
+
+    fn image_with_status(status: &str) -> serde_json::Value {
+        let mut image = custom_image_fixture();
+        image["status"] = json!(status);
+        json!({ "image": image })
+    }
+
+    fn create_request() -> Request<Create, CustomImage> {
+        CustomImage::create(
+            "ubuntu-18.04-minimal",
+            "http://foo.bar/baz.raw",
+            "nyc3",
+            "Ubuntu",
+            "Cloud-optimized image w/ small footprint",
+            vec!["base-image", "prod"],
+        )
+    }
+
+    #[test]
+    fn create_and_wait_polls_until_available() {
+        before();
+
+        let mock = MockDigitalOcean::new()
+            .respond(
+                Method::POST,
+                "/images",
+                StatusCode::ACCEPTED,
+                image_with_status("NEW"),
+            )
+            .respond(
+                Method::GET,
+                "/images/38413969",
+                StatusCode::OK,
+                image_with_status("pending"),
+            )
+            .respond(
+                Method::GET,
+                "/images/38413969",
+                StatusCode::OK,
+                image_with_status("available"),
+            );
+
+        let image = block_on(create_request().create_and_wait(
+            &mock,
+            Duration::from_millis(1),
+            Duration::from_secs(5),
+        ))
+        .unwrap();
+        info!("{:#?}", image);
+
+        assert_eq!(*image.status(), ImageStatus::Available);
+        assert_eq!(mock.received().len(), 3);
+    }
+
+    #[test]
+    fn create_and_wait_fails_when_import_errors() {
+        before();
+
+        let mock = MockDigitalOcean::new()
+            .respond(
+                Method::POST,
+                "/images",
+                StatusCode::ACCEPTED,
+                image_with_status("NEW"),
+            )
+            .respond(
+                Method::GET,
+                "/images/38413969",
+                StatusCode::OK,
+                image_with_status("errored"),
+            );
+
+        match block_on(create_request().create_and_wait(
+            &mock,
+            Duration::from_millis(1),
+            Duration::from_secs(5),
+        )) {
+            Err(Error::Failed(_)) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+}
@@ -79,3 +79,62 @@ fn delete_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+#[cfg(feature = "testing")]
+mod mock {
+    use reqwest::{Method, StatusCode};
+
+    use digitalocean::api::Domain;
+    use digitalocean::error::Error;
+    use digitalocean::testing::MockDigitalOcean;
+
+    use crate::utils::{before, block_on};
+
+    const ZONE_FILE: &str = "$ORIGIN example.com.\n$TTL 1800\nexample.com. IN SOA ns1.digitalocean.com. hostmaster.example.com. 1415982609 10800 3600 604800 1800\nexample.com. 1800 IN NS ns1.digitalocean.com.\nexample.com. 1800 IN A 1.2.3.4\n";
+
+    #[test]
+    fn export_zone_returns_zone_file() {
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::GET,
+            "/domains/example.com",
+            StatusCode::OK,
+            json!({
+                "domain": {
+                    "name": "example.com",
+                    "ttl": 1800,
+                    "zone_file": ZONE_FILE
+                }
+            }),
+        );
+
+        let zone = block_on(Domain::get("example.com").export_zone(&mock)).unwrap();
+        info!("{}", zone);
+
+        assert_eq!(zone, ZONE_FILE);
+    }
+
+    #[test]
+    fn export_zone_errors_without_zone_file() {
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::GET,
+            "/domains/example.com",
+            StatusCode::OK,
+            json!({
+                "domain": {
+                    "name": "example.com",
+                    "ttl": 1800,
+                    "zone_file": null
+                }
+            }),
+        );
+
+        match block_on(Domain::get("example.com").export_zone(&mock)) {
+            Err(Error::EmptyZoneFile(name)) => assert_eq!(name, "example.com"),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+}
@@ -10,7 +10,7 @@ mod utils;
 
 use serde_json::Value;
 
-use digitalocean::api::{Domain, DomainRecord};
+use digitalocean::api::{diff_records, Domain, DomainRecord, NewDomainRecord};
 use digitalocean::method::{Create, Delete, Get, List, Update};
 use digitalocean::request::Request;
 
@@ -122,3 +122,179 @@ fn delete_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+#[test]
+fn create_caa_produces_correct_request() {
+    before();
+
+    let req: Request<Create, DomainRecord> = Domain::get("example.com")
+        .records()
+        .create("CAA", "@", "letsencrypt.org.")
+        .flags(Some(0))
+        .caa_tag("issue")
+        .ttl(1800);
+    info!("{:#?}", req);
+
+    assert_eq!(
+        *req.body(),
+        json!({
+            "type": "CAA",
+            "name": "@",
+            "data": "letsencrypt.org.",
+            "flags": 0,
+            "tag": "issue",
+            "ttl": 1800,
+        })
+    );
+}
+
+#[test]
+fn caa_record_deserializes() {
+    before();
+
+    let record: DomainRecord = serde_json::from_value(json!({
+        "id": 28448433,
+        "type": "CAA",
+        "name": "@",
+        "data": "letsencrypt.org.",
+        "priority": null,
+        "port": null,
+        "ttl": 1800,
+        "weight": null,
+        "flags": 0,
+        "tag": "issue"
+    }))
+    .unwrap();
+    info!("{:#?}", record);
+
+    assert_eq!(record.kind(), "CAA");
+    assert_eq!(*record.flags(), Some(0));
+    assert_eq!(record.tag().as_deref(), Some("issue"));
+}
+
+fn record(id: usize, kind: &str, name: &str, data: &str) -> DomainRecord {
+    serde_json::from_value(json!({
+        "id": id,
+        "type": kind,
+        "name": name,
+        "data": data,
+        "priority": null,
+        "port": null,
+        "ttl": 1800,
+        "weight": null,
+        "flags": null,
+        "tag": null
+    }))
+    .unwrap()
+}
+
+fn new_record(kind: &str, name: &str, data: &str) -> NewDomainRecord {
+    NewDomainRecord::from(&record(0, kind, name, data))
+}
+
+#[test]
+fn records_are_equal_regardless_of_id() {
+    before();
+
+    let a = record(1, "A", "www", "162.10.66.0");
+
+    assert_eq!(a, record(2, "A", "www", "162.10.66.0"));
+    assert_ne!(a, record(1, "A", "www", "162.10.66.1"));
+    assert_eq!(a, new_record("A", "www", "162.10.66.0"));
+}
+
+#[test]
+fn diff_of_identical_records_is_empty() {
+    before();
+
+    let diff = diff_records(
+        vec![new_record("A", "www", "162.10.66.0")],
+        vec![
+            record(28448429, "A", "www", "162.10.66.0"),
+            record(28448430, "SOA", "@", "1800"),
+        ],
+    );
+    info!("{:#?}", diff);
+
+    assert!(diff.is_empty());
+}
+
+#[test]
+fn diff_creates_and_deletes() {
+    before();
+
+    let diff = diff_records(
+        vec![
+            new_record("A", "www", "162.10.66.0"),
+            new_record("TXT", "@", "v=spf1 -all"),
+        ],
+        vec![
+            record(28448429, "A", "www", "162.10.66.0"),
+            record(28448431, "CNAME", "old", "www.example.com."),
+        ],
+    );
+    info!("{:#?}", diff);
+
+    assert_eq!(diff.to_create, vec![new_record("TXT", "@", "v=spf1 -all")]);
+    assert!(diff.to_update.is_empty());
+    assert_eq!(diff.to_delete.len(), 1);
+    assert_eq!(*diff.to_delete[0].id(), 28448431);
+}
+
+#[test]
+fn diff_updates_records_in_place() {
+    before();
+
+    let mut longer_ttl = new_record("A", "www", "162.10.66.0");
+    longer_ttl.ttl = 3600;
+
+    let diff = diff_records(
+        vec![
+            new_record("A", "@", "162.10.66.1"),
+            new_record("A", "api", "162.10.66.2"),
+            new_record("A", "api", "162.10.66.3"),
+            longer_ttl.clone(),
+        ],
+        vec![
+            record(1, "A", "@", "162.10.66.9"),
+            record(2, "A", "api", "162.10.66.2"),
+            record(3, "A", "www", "162.10.66.0"),
+        ],
+    );
+    info!("{:#?}", diff);
+
+    assert_eq!(
+        diff.to_update,
+        vec![(1, new_record("A", "@", "162.10.66.1")), (3, longer_ttl)]
+    );
+    // The second api record has nothing left to replace, so it is added.
+    assert_eq!(diff.to_create, vec![new_record("A", "api", "162.10.66.3")]);
+    assert!(diff.to_delete.is_empty());
+}
+
+#[test]
+fn diff_keeps_managed_nameservers() {
+    before();
+
+    let diff = diff_records(
+        vec![
+            new_record("A", "www", "162.10.66.0"),
+            new_record("NS", "@", "ns1.digitalocean.com"),
+        ],
+        vec![
+            record(1, "SOA", "@", "1800"),
+            record(2, "NS", "@", "ns1.digitalocean.com"),
+            record(3, "NS", "@", "ns2.digitalocean.com"),
+            record(4, "NS", "@", "ns3.digitalocean.com."),
+            record(5, "NS", "dev", "ns1.example.com."),
+            record(6, "A", "www", "162.10.66.0"),
+        ],
+    );
+    info!("{:#?}", diff);
+
+    assert!(diff.to_create.is_empty());
+    assert!(diff.to_update.is_empty());
+    // Only the delegation of a subdomain is left to delete.
+    assert_eq!(diff.to_delete.len(), 1);
+    assert_eq!(*diff.to_delete[0].id(), 5);
+}
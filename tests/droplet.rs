@@ -10,7 +10,14 @@ mod utils;
 
 use serde_json::Value;
 
-use digitalocean::api::{Droplet, Snapshot};
+use digitalocean::api::droplet_fields::{
+    AssociatedResourceIds, BackupPlan, BackupPolicy, BackupWeekday, DestroyAssociated, Kernel,
+};
+use digitalocean::api::{
+    Droplet, DropletFeature, DropletSummary, Firewall, HasResponse, HasUrn, ImageSource, Snapshot,
+    SshKeyRef,
+};
+use digitalocean::error::Error;
 use digitalocean::method::{Create, Delete, Get, List};
 use digitalocean::request::Request;
 
@@ -75,6 +82,298 @@ fn create_many_produces_correct_request() {
     );
 }
 
+#[test]
+fn create_with_mixed_ssh_keys_produces_correct_request() {
+    before();
+
+    let fingerprint = "3b:16:bf:e4:8b:00:8b:b8:59:8c:a9:d3:f0:19:45:fa";
+
+    let req: Request<Create, Droplet> = Droplet::create("bear", "tor1", "5gb", "ubuntu-14-04-x64")
+        .ssh_keys(vec![
+            SshKeyRef::Id(512189),
+            SshKeyRef::Fingerprint(fingerprint.to_string()),
+            SshKeyRef::from(512190),
+        ]);
+    info!("{:#?}", req);
+
+    assert_eq!(req.body()["ssh_keys"], json!([512189, fingerprint, 512190]));
+
+    let req: Request<Create, Vec<Droplet>> =
+        Droplet::create_multiple(vec!["bear", "bear2"], "tor1", "5gb", "ubuntu-14-04-x64")
+            .ssh_keys(vec![SshKeyRef::from(fingerprint), SshKeyRef::Id(512189)]);
+    info!("{:#?}", req);
+
+    assert_eq!(req.body()["ssh_keys"], json!([fingerprint, 512189]));
+}
+
+#[test]
+fn kernels_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/droplets/123/kernels";
+
+    let req: Request<List, Vec<Kernel>> = Droplet::get(123).kernels();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn create_with_networking_produces_correct_request() {
+    before();
+
+    let vpc = "760e09ef-dc84-11e8-981e-3cfdfeaae000";
+
+    let req: Request<Create, Droplet> = Droplet::create("bear", "tor1", "5gb", "ubuntu-14-04-x64")
+        .private_networking(true)
+        .vpc_uuid(vpc);
+    info!("{:#?}", req);
+
+    assert_eq!(req.body()["private_networking"], json!(true));
+    assert_eq!(req.body()["vpc_uuid"], json!(vpc));
+
+    let req: Request<Create, Vec<Droplet>> =
+        Droplet::create_multiple(vec!["bear", "bear2"], "tor1", "5gb", "ubuntu-14-04-x64")
+            .private_networking(false)
+            .vpc_uuid(vpc);
+    info!("{:#?}", req);
+
+    assert_eq!(req.body()["private_networking"], json!(false));
+    assert_eq!(req.body()["vpc_uuid"], json!(vpc));
+}
+
+#[test]
+fn create_with_backup_policy_produces_correct_request() {
+    before();
+
+    let policy = BackupPolicy {
+        plan: BackupPlan::Weekly,
+        weekday: Some(BackupWeekday::Sun),
+        hour: Some(8),
+    };
+
+    let req: Request<Create, Droplet> =
+        Droplet::create("bear", "tor1", "5gb", "ubuntu-14-04-x64").backup_policy(policy);
+    info!("{:#?}", req);
+
+    assert_eq!(req.body()["backups"], json!(true));
+    assert_eq!(
+        req.body()["backup_policy"],
+        json!({
+            "plan": "weekly",
+            "weekday": "SUN",
+            "hour": 8
+        })
+    );
+    assert!(req.validate().is_ok());
+
+    let req: Request<Create, Vec<Droplet>> =
+        Droplet::create_multiple(vec!["bear", "bear2"], "tor1", "5gb", "ubuntu-14-04-x64")
+            .backup_policy(BackupPolicy {
+                plan: BackupPlan::Daily,
+                weekday: None,
+                hour: None,
+            });
+
+    assert_eq!(req.body()["backup_policy"], json!({ "plan": "daily" }));
+    assert!(req.validate().is_ok());
+}
+
+#[test]
+fn create_with_backup_policy_and_no_backups_is_invalid() {
+    before();
+
+    let policy = BackupPolicy {
+        plan: BackupPlan::Daily,
+        weekday: None,
+        hour: Some(4),
+    };
+
+    let req: Request<Create, Droplet> = Droplet::create("bear", "tor1", "5gb", "ubuntu-14-04-x64")
+        .backup_policy(policy.clone())
+        .backups(false);
+
+    match req.validate() {
+        Err(Error::InvalidRequest(_)) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+
+    let req: Request<Create, Droplet> = Droplet::create("bear", "tor1", "5gb", "ubuntu-14-04-x64")
+        .backup_policy(BackupPolicy {
+            hour: Some(5),
+            ..policy
+        });
+
+    match req.validate() {
+        Err(Error::InvalidRequest(_)) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn create_from_image_slug_serializes_string() {
+    before();
+
+    let req: Request<Create, Droplet> = Droplet::create(
+        "bear",
+        "tor1",
+        "5gb",
+        ImageSource::Slug("ubuntu-14-04-x64".into()),
+    );
+    info!("{:#?}", req);
+
+    assert_eq!(req.body()["image"], json!("ubuntu-14-04-x64"));
+}
+
+#[test]
+fn create_from_image_id_serializes_number() {
+    before();
+
+    let snapshot_id = 67512819;
+
+    let req: Request<Create, Droplet> = Droplet::create("bear", "tor1", "5gb", snapshot_id);
+    info!("{:#?}", req);
+
+    assert_eq!(req.body()["image"], json!(snapshot_id));
+
+    let req: Request<Create, Vec<Droplet>> = Droplet::create_multiple(
+        vec!["bear", "badger"],
+        "tor1",
+        "5gb",
+        ImageSource::Id(snapshot_id),
+    );
+    info!("{:#?}", req);
+
+    assert_eq!(req.body()["image"], json!(snapshot_id));
+}
+
+#[test]
+fn create_with_droplet_agent_produces_correct_request() {
+    before();
+
+    let req: Request<Create, Droplet> = Droplet::create("bear", "tor1", "5gb", "ubuntu-14-04-x64")
+        .monitoring(false)
+        .with_droplet_agent(true);
+    info!("{:#?}", req);
+
+    assert_eq!(req.body()["monitoring"], json!(false));
+    assert_eq!(req.body()["with_droplet_agent"], json!(true));
+
+    let req: Request<Create, Vec<Droplet>> =
+        Droplet::create_multiple(vec!["bear", "badger"], "tor1", "5gb", "ubuntu-14-04-x64")
+            .with_droplet_agent(false);
+    info!("{:#?}", req);
+
+    assert_eq!(req.body()["with_droplet_agent"], json!(false));
+}
+
+#[test]
+fn create_with_volumes_produces_correct_request() {
+    before();
+
+    let volumes = vec![
+        String::from("506f78a4-e098-11e5-ad9f-000f53306ae1"),
+        String::from("7724db7c-e098-11e5-b522-000f53304e51"),
+    ];
+
+    let req: Request<Create, Droplet> =
+        Droplet::create("bear", "tor1", "5gb", "ubuntu-14-04-x64").volumes(volumes.clone());
+    info!("{:#?}", req);
+
+    assert_eq!(
+        *req.body(),
+        json!({
+            "name": "bear",
+            "region": "tor1",
+            "size": "5gb",
+            "image": "ubuntu-14-04-x64",
+            "volumes": volumes,
+        })
+    );
+
+    let req: Request<Create, Vec<Droplet>> =
+        Droplet::create_multiple(vec!["bear", "badger"], "tor1", "5gb", "ubuntu-14-04-x64")
+            .volumes(volumes.clone());
+    info!("{:#?}", req);
+
+    assert_eq!(req.body()["volumes"], json!(volumes));
+}
+
+#[test]
+fn create_validates_correct_request() {
+    before();
+
+    let req: Request<Create, Droplet> = Droplet::create("bear", "tor1", "5gb", "ubuntu-14-04-x64");
+    assert!(req.validate().is_ok());
+
+    let req: Request<Create, Vec<Droplet>> =
+        Droplet::create_multiple(vec!["bear", "badger"], "tor1", "5gb", "ubuntu-14-04-x64");
+    assert!(req.validate().is_ok());
+}
+
+#[test]
+fn create_without_region_is_invalid() {
+    before();
+
+    let req: Request<Create, Droplet> = Droplet::create("bear", "", "5gb", "ubuntu-14-04-x64");
+    info!("{:#?}", req);
+
+    match req.validate() {
+        Err(Error::InvalidRequest(_)) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+
+    let mut req: Request<Create, Vec<Droplet>> =
+        Droplet::create_multiple(vec!["bear", "badger"], "tor1", "5gb", "ubuntu-14-04-x64");
+    req.body_mut().as_object_mut().unwrap().remove("region");
+    info!("{:#?}", req);
+
+    match req.validate() {
+        Err(Error::InvalidRequest(_)) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn create_with_name_and_names_is_invalid() {
+    before();
+
+    let mut req: Request<Create, Vec<Droplet>> =
+        Droplet::create_multiple(vec!["bear", "badger"], "tor1", "5gb", "ubuntu-14-04-x64");
+    req.body_mut()["name"] = json!("bear");
+    info!("{:#?}", req);
+
+    match req.validate() {
+        Err(Error::InvalidRequest(_)) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+
+    let mut req: Request<Create, Droplet> =
+        Droplet::create("bear", "tor1", "5gb", "ubuntu-14-04-x64");
+    req.body_mut()["names"] = json!(["bear", "badger"]);
+    info!("{:#?}", req);
+
+    match req.validate() {
+        Err(Error::InvalidRequest(_)) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn create_multiple_without_names_is_invalid() {
+    before();
+
+    let req: Request<Create, Vec<Droplet>> =
+        Droplet::create_multiple(Vec::<&str>::new(), "tor1", "5gb", "ubuntu-14-04-x64");
+    info!("{:#?}", req);
+
+    match req.validate() {
+        Err(Error::InvalidRequest(_)) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
 #[test]
 fn get_produces_correct_request() {
     before();
@@ -119,6 +418,33 @@ fn list_by_tag_produces_correct_request() {
     assert_eq!(*req.body(), Value::Null);
 }
 
+#[test]
+fn list_gpus_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/droplets?type=gpus";
+
+    let req: Request<List, Vec<Droplet>> = Droplet::list().only_gpus();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn list_by_kind_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/droplets?type=droplets";
+
+    let req: Request<List, Vec<Droplet>> = Droplet::list().kind("droplets");
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+    assert_eq!(Droplet::list().only_droplets().url().as_str(), correct_url);
+}
+
 #[test]
 fn delete_produces_correct_request() {
     before();
@@ -133,6 +459,54 @@ fn delete_produces_correct_request() {
     assert_eq!(*req.body(), Value::Null);
 }
 
+#[test]
+fn destroy_with_selected_resources_produces_correct_request() {
+    before();
+
+    let correct_url =
+        "https://api.digitalocean.com/v2/droplets/123/destroy_with_associated_resources/selective";
+
+    let req: Request<Delete, ()> = Droplet::get(123).destroy_with_associated_resources(
+        DestroyAssociated::Selective(AssociatedResourceIds {
+            reserved_ips: vec!["45.55.96.47".to_string()],
+            volumes: vec!["ba49449a-7435-11ea-b89e-0a58ac14480f".to_string()],
+            ..Default::default()
+        }),
+    );
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "reserved_ips": ["45.55.96.47"],
+            "snapshots": [],
+            "volumes": ["ba49449a-7435-11ea-b89e-0a58ac14480f"],
+            "volume_snapshots": []
+        })
+    );
+    assert!(req.headers().is_empty());
+}
+
+#[test]
+fn destroy_with_all_resources_is_dangerous() {
+    before();
+
+    let correct_url =
+        "https://api.digitalocean.com/v2/droplets/123/destroy_with_associated_resources/dangerous";
+
+    let req: Request<Delete, ()> =
+        Droplet::get(123).destroy_with_associated_resources(DestroyAssociated::Dangerous);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+    assert_eq!(
+        *req.headers(),
+        vec![("X-Dangerous".to_string(), "true".to_string())]
+    );
+}
+
 #[test]
 fn delete_by_tag_produces_correct_request() {
     before();
@@ -213,3 +587,701 @@ fn get_neighbors_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+#[test]
+fn firewalls_produces_correct_request() {
+    before();
+
+    let droplet_id = 123;
+    let correct_url = format!(
+        "https://api.digitalocean.com/v2/droplets/{}/firewalls",
+        droplet_id
+    );
+
+    let req: Request<List, Vec<Firewall>> = Droplet::get(droplet_id).firewalls();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+fn droplet_fixture() -> Value {
+    json!({
+        "id": 3164444,
+        "name": "example.com",
+        "memory": 1024,
+        "vcpus": 1,
+        "disk": 25,
+        "locked": false,
+        "status": "active",
+        "kernel": null,
+        "created_at": "2020-07-21T18:37:44Z",
+        "features": ["backups", "ipv6", "private_networking"],
+        "backup_ids": [53893572],
+        "next_backup_window": null,
+        "snapshot_ids": [67512819],
+        "image": {
+            "id": 63663980,
+            "name": "20.04 (LTS) x64",
+            "type": "snapshot",
+            "distribution": "Ubuntu",
+            "slug": "ubuntu-20-04-x64",
+            "public": true,
+            "regions": ["nyc3"],
+            "min_disk_size": 20,
+            "size_gigabytes": 2.36,
+            "created_at": "2020-05-15T05:47:50Z"
+        },
+        "volume_ids": [],
+        "size": {
+            "slug": "s-1vcpu-1gb",
+            "memory": 1024,
+            "vcpus": 1,
+            "disk": 25,
+            "transfer": 1.0,
+            "price_monthly": 5.0,
+            "price_hourly": 0.00743999984115362,
+            "regions": ["nyc3"],
+            "available": true
+        },
+        "size_slug": "s-1vcpu-1gb",
+        "networks": {
+            "v4": [
+                {
+                    "ip_address": "10.128.192.124",
+                    "netmask": "255.255.0.0",
+                    "gateway": "10.128.0.1",
+                    "type": "private"
+                },
+                {
+                    "ip_address": "192.241.165.154",
+                    "netmask": "255.255.255.0",
+                    "gateway": "192.241.165.1",
+                    "type": "public"
+                }
+            ],
+            "v6": [
+                {
+                    "ip_address": "2604:a880:0:1010::18a:a001",
+                    "netmask": 64,
+                    "gateway": "2604:a880:0:1010::1",
+                    "type": "public"
+                }
+            ]
+        },
+        "region": {
+            "name": "New York 3",
+            "slug": "nyc3",
+            "features": ["private_networking", "backups", "ipv6"],
+            "available": true,
+            "sizes": ["s-1vcpu-1gb"]
+        },
+        "tags": ["web", "env:prod"]
+    })
+}
+
+#[test]
+fn droplet_urn() {
+    before();
+
+    let droplet: Droplet = serde_json::from_value(droplet_fixture()).unwrap();
+
+    assert_eq!(droplet.urn(), format!("do:droplet:{}", droplet.id()));
+}
+
+#[test]
+fn has_feature_reads_features() {
+    before();
+
+    let droplet: Droplet = serde_json::from_value(droplet_fixture()).unwrap();
+
+    assert!(droplet.has_feature(DropletFeature::Backups));
+    assert!(droplet.has_feature(DropletFeature::Ipv6));
+    assert!(droplet.has_feature(DropletFeature::PrivateNetworking));
+    assert!(!droplet.has_feature(DropletFeature::Monitoring));
+    assert!(!droplet.has_feature(DropletFeature::Unknown));
+
+    let mut fixture = droplet_fixture();
+    fixture["features"] = json!(["monitoring", "droplet_agent", "something_new"]);
+    let droplet: Droplet = serde_json::from_value(fixture).unwrap();
+
+    assert!(droplet.has_feature(DropletFeature::Monitoring));
+    assert!(droplet.has_feature(DropletFeature::DropletAgent));
+    assert!(!droplet.has_feature(DropletFeature::Backups));
+    assert!(!droplet.has_feature(DropletFeature::Unknown));
+    assert_eq!(droplet.features().len(), 3);
+}
+
+#[test]
+fn network_accessors_read_typed_networks() {
+    before();
+
+    let droplet: Droplet = serde_json::from_value(droplet_fixture()).unwrap();
+    info!("{:#?}", droplet);
+
+    assert_eq!(droplet.networks().v4.len(), 2);
+    assert_eq!(droplet.networks().v6.len(), 1);
+    assert_eq!(
+        droplet.public_ipv4(),
+        Some("192.241.165.154".parse().unwrap())
+    );
+    assert_eq!(
+        droplet.private_ipv4(),
+        Some("10.128.192.124".parse().unwrap())
+    );
+    assert_eq!(
+        droplet.public_ipv6(),
+        Some("2604:a880:0:1010::18a:a001".parse().unwrap())
+    );
+}
+
+#[test]
+fn network_accessors_return_none_without_networks() {
+    before();
+
+    let mut fixture = droplet_fixture();
+    fixture["networks"] = json!({ "v4": [], "v6": [] });
+
+    let droplet: Droplet = serde_json::from_value(fixture).unwrap();
+    info!("{:#?}", droplet);
+
+    assert_eq!(droplet.public_ipv4(), None);
+    assert_eq!(droplet.private_ipv4(), None);
+    assert_eq!(droplet.public_ipv6(), None);
+}
+
+#[test]
+fn droplet_carries_nested_size() {
+    before();
+
+    let droplet: Droplet = serde_json::from_value(droplet_fixture()).unwrap();
+    info!("{:#?}", droplet);
+
+    let size = droplet.size().as_ref().unwrap();
+    assert_eq!(*size.vcpus(), 1);
+    assert_eq!(*size.memory(), 1024);
+    assert_eq!(*size.price_monthly(), 5.0);
+    assert_eq!(droplet.size_slug(), Some("s-1vcpu-1gb"));
+}
+
+#[test]
+fn droplet_deserializes_without_size_or_size_slug() {
+    before();
+
+    let mut fixture = droplet_fixture();
+    fixture.as_object_mut().unwrap().remove("size_slug");
+
+    let droplet: Droplet = serde_json::from_value(fixture).unwrap();
+    info!("{:#?}", droplet);
+
+    assert_eq!(droplet.size_slug(), Some("s-1vcpu-1gb"));
+
+    let mut fixture = droplet_fixture();
+    fixture.as_object_mut().unwrap().remove("size");
+
+    let droplet: Droplet = serde_json::from_value(fixture).unwrap();
+    info!("{:#?}", droplet);
+
+    assert!(droplet.size().is_none());
+    assert_eq!(droplet.size_slug(), Some("s-1vcpu-1gb"));
+}
+
+#[test]
+fn list_response_iterates_droplets() {
+    before();
+
+    let mut second = droplet_fixture();
+    second["id"] = json!(3164445);
+    second["name"] = json!("example.org");
+
+    let response: <Vec<Droplet> as HasResponse>::Response = serde_json::from_value(json!({
+        "droplets": [droplet_fixture(), second],
+        "links": {},
+        "meta": { "total": 2 }
+    }))
+    .unwrap();
+    info!("{:#?}", response);
+
+    let names: Vec<String> = response
+        .into_iter()
+        .map(|droplet| droplet.name().clone())
+        .collect();
+
+    assert_eq!(names, vec!["example.com", "example.org"]);
+}
+
+#[test]
+fn display_shows_name_and_id() {
+    before();
+
+    let droplet: Droplet = serde_json::from_value(droplet_fixture()).unwrap();
+
+    assert_eq!(droplet.to_string(), r#"droplet "example.com" (3164444)"#);
+}
+
+#[test]
+fn list_summary_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/droplets";
+
+    let req: Request<List, Vec<DropletSummary>> = Droplet::list_summary();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn summary_deserializes_from_full_droplet() {
+    before();
+
+    let summary: DropletSummary = serde_json::from_value(droplet_fixture()).unwrap();
+    info!("{:#?}", summary);
+
+    assert_eq!(*summary.id(), 3164444);
+    assert_eq!(summary.name(), "example.com");
+    assert_eq!(summary.status(), "active");
+}
+
+#[cfg(feature = "testing")]
+mod mock {
+    use reqwest::{Method, StatusCode};
+    use serde_json::json;
+
+    use digitalocean::api::droplet_fields::{AssociatedResourceIds, DestroyAssociated};
+    use digitalocean::api::Droplet;
+    use digitalocean::error::Error;
+    use digitalocean::request::Executable;
+    use digitalocean::testing::MockDigitalOcean;
+
+    use super::droplet_fixture;
+    use crate::utils::{before, block_on};
+
+    #[test]
+    fn associated_resources_are_listed() {
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::GET,
+            "/droplets/123/destroy_with_associated_resources",
+            StatusCode::OK,
+            json!({
+                "reserved_ips": [
+                    { "id": "6186916", "name": "45.55.96.47", "cost": "4.00" }
+                ],
+                "floating_ips": [
+                    { "id": "6186916", "name": "45.55.96.47", "cost": "4.00" }
+                ],
+                "snapshots": [
+                    { "id": "61486916", "name": "ubuntu-s-1vcpu-1gb-nyc1-01-1585758823330", "cost": "0.05" }
+                ],
+                "volumes": [
+                    { "id": "ba49449a-7435-11ea-b89e-0a58ac14480f", "name": "volume-nyc1-01", "cost": "10.00" }
+                ],
+                "volume_snapshots": [
+                    { "id": "edb0478d-7436-11ea-86e6-0a58ac144b91", "name": "volume-nyc1-01-1585758983629", "cost": "0.04" }
+                ]
+            }),
+        );
+
+        let resources = block_on(Droplet::get(123).associated_resources(&mock)).unwrap();
+        info!("{:#?}", resources);
+
+        assert_eq!(resources.reserved_ips[0].name, "45.55.96.47");
+        assert_eq!(resources.floating_ips.len(), 1);
+        assert_eq!(resources.snapshots[0].id, "61486916");
+        assert_eq!(resources.volumes[0].cost, "10.00");
+        assert_eq!(resources.volume_snapshots[0].cost, "0.04");
+    }
+
+    #[test]
+    fn destroy_with_associated_resources_sends_confirmation() {
+        before();
+
+        let mock = MockDigitalOcean::new()
+            .respond(
+                Method::DELETE,
+                "/droplets/123/destroy_with_associated_resources/dangerous",
+                StatusCode::ACCEPTED,
+                serde_json::Value::Null,
+            )
+            .respond(
+                Method::DELETE,
+                "/droplets/123/destroy_with_associated_resources/selective",
+                StatusCode::ACCEPTED,
+                serde_json::Value::Null,
+            );
+
+        block_on(
+            Droplet::get(123)
+                .destroy_with_associated_resources(DestroyAssociated::Dangerous)
+                .execute(&mock),
+        )
+        .unwrap();
+
+        block_on(
+            Droplet::get(123)
+                .destroy_with_associated_resources(DestroyAssociated::Selective(
+                    AssociatedResourceIds {
+                        snapshots: vec!["61486916".to_string()],
+                        ..Default::default()
+                    },
+                ))
+                .execute(&mock),
+        )
+        .unwrap();
+
+        let received = mock.received();
+        assert_eq!(received[0].headers["x-dangerous"], "true");
+        assert!(received[1].headers.get("x-dangerous").is_none());
+
+        let body: serde_json::Value = serde_json::from_slice(&received[1].body).unwrap();
+        assert_eq!(body["snapshots"], json!(["61486916"]));
+    }
+
+    fn tagged(count: usize) -> MockDigitalOcean {
+        let droplets: Vec<_> = (0..count)
+            .map(|i| {
+                let mut droplet = droplet_fixture();
+                droplet["id"] = json!(3164444 + i);
+                droplet
+            })
+            .collect();
+
+        MockDigitalOcean::new().respond(
+            Method::GET,
+            "/droplets",
+            StatusCode::OK,
+            json!({ "droplets": droplets, "links": {}, "meta": { "total": count } }),
+        )
+    }
+
+    #[test]
+    fn get_by_tag_without_matches_is_none() {
+        before();
+
+        let mock = tagged(0);
+        let droplet = block_on(Droplet::get_by_tag(&mock, "web-1")).unwrap();
+
+        assert!(droplet.is_none());
+        assert_eq!(
+            mock.received()[0].url.query(),
+            Some("tag_name=web-1&per_page=2")
+        );
+    }
+
+    #[test]
+    fn get_by_tag_returns_only_match() {
+        before();
+
+        let mock = tagged(1);
+        let droplet = block_on(Droplet::get_by_tag(&mock, "web-1"))
+            .unwrap()
+            .unwrap();
+        info!("{:#?}", droplet);
+
+        assert_eq!(*droplet.id(), 3164444);
+    }
+
+    #[test]
+    fn get_by_tag_with_many_matches_returns_first() {
+        before();
+
+        let mock = tagged(2);
+        let droplet = block_on(Droplet::get_by_tag(&mock, "web-1"))
+            .unwrap()
+            .unwrap();
+        info!("{:#?}", droplet);
+
+        assert_eq!(*droplet.id(), 3164444);
+    }
+
+    #[test]
+    fn execute_with_action_fetches_create_action() {
+        before();
+
+        let mock = MockDigitalOcean::new()
+            .respond(
+                Method::POST,
+                "/droplets",
+                StatusCode::ACCEPTED,
+                json!({
+                    "droplet": droplet_fixture(),
+                    "links": {
+                        "actions": [{
+                            "id": 36805096,
+                            "rel": "create",
+                            "href": "https://api.digitalocean.com/v2/actions/36805096"
+                        }]
+                    }
+                }),
+            )
+            .respond(
+                Method::GET,
+                "/actions/36805096",
+                StatusCode::OK,
+                json!({
+                    "action": {
+                        "id": 36805096,
+                        "status": "in-progress",
+                        "type": "create",
+                        "started_at": "2014-11-14T16:29:21Z",
+                        "completed_at": null,
+                        "resource_id": 3164444,
+                        "resource_type": "droplet",
+                        "region_slug": "nyc3"
+                    }
+                }),
+            );
+
+        let (droplet, action) = block_on(
+            Droplet::create("example.com", "nyc3", "s-1vcpu-1gb", "ubuntu-20-04-x64")
+                .execute_with_action(&mock),
+        )
+        .unwrap();
+        info!("{:#?} {:#?}", droplet, action);
+
+        let action = action.unwrap();
+        assert_eq!(*droplet.id(), 3164444);
+        assert_eq!(*action.id(), 36805096);
+        assert_eq!(action.kind(), "create");
+        assert_eq!(mock.received().len(), 2);
+    }
+
+    #[test]
+    fn execute_with_action_without_link_keeps_droplet() {
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::POST,
+            "/droplets",
+            StatusCode::ACCEPTED,
+            json!({ "droplet": droplet_fixture() }),
+        );
+
+        let (droplet, action) = block_on(
+            Droplet::create("example.com", "nyc3", "s-1vcpu-1gb", "ubuntu-20-04-x64")
+                .execute_with_action(&mock),
+        )
+        .unwrap();
+
+        assert_eq!(*droplet.id(), 3164444);
+        assert!(action.is_none());
+        assert_eq!(mock.received().len(), 1);
+    }
+
+    fn named(count: usize) -> MockDigitalOcean {
+        let droplets: Vec<_> = (0..count)
+            .map(|_| json!({ "id": 3164444, "name": "example.com", "status": "active" }))
+            .collect();
+
+        MockDigitalOcean::new()
+            .respond(
+                Method::GET,
+                "/droplets",
+                StatusCode::OK,
+                json!({ "droplets": droplets, "links": {}, "meta": { "total": count } }),
+            )
+            .respond(
+                Method::POST,
+                "/droplets",
+                StatusCode::ACCEPTED,
+                json!({ "droplet": droplet_fixture() }),
+            )
+    }
+
+    #[test]
+    fn fail_if_exists_creates_without_collision() {
+        before();
+
+        let mock = named(0);
+        let droplet = block_on(
+            Droplet::create("example.com", "nyc3", "s-1vcpu-1gb", "ubuntu-20-04-x64")
+                .fail_if_exists(&mock),
+        )
+        .unwrap();
+        info!("{:#?}", droplet);
+
+        let received = mock.received();
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0].url.query(), Some("name=example.com&per_page=1"));
+        assert_eq!(received[1].method, Method::POST);
+    }
+
+    #[test]
+    fn fail_if_exists_refuses_collision() {
+        before();
+
+        let mock = named(1);
+        match block_on(
+            Droplet::create("example.com", "nyc3", "s-1vcpu-1gb", "ubuntu-20-04-x64")
+                .fail_if_exists(&mock),
+        ) {
+            Err(error @ Error::AlreadyExists(_)) => {
+                assert_eq!(error.to_string(), "Droplet example.com already exists");
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        assert!(mock.received().iter().all(|r| r.method == Method::GET));
+    }
+
+    fn lookup_mock(matches: usize) -> MockDigitalOcean {
+        let droplets: Vec<_> = (0..matches)
+            .map(|i| {
+                let mut droplet = droplet_fixture();
+                droplet["id"] = json!(3164444 + i);
+                droplet
+            })
+            .collect();
+
+        MockDigitalOcean::new()
+            .respond(
+                Method::GET,
+                "/droplets/3164444",
+                StatusCode::OK,
+                json!({ "droplet": droplet_fixture() }),
+            )
+            .respond(
+                Method::GET,
+                "/droplets",
+                StatusCode::OK,
+                json!({ "droplets": droplets, "links": {}, "meta": { "total": matches } }),
+            )
+    }
+
+    #[test]
+    fn lookup_by_id() {
+        before();
+
+        let mock = lookup_mock(0);
+        let droplet = block_on(Droplet::lookup(&mock, "3164444")).unwrap();
+
+        assert_eq!(*droplet.id(), 3164444);
+        assert_eq!(mock.received()[0].url.path(), "/v2/droplets/3164444");
+    }
+
+    #[test]
+    fn lookup_by_urn() {
+        before();
+
+        let mock = lookup_mock(0);
+        let droplet = block_on(Droplet::lookup(&mock, "do:droplet:3164444")).unwrap();
+
+        assert_eq!(*droplet.id(), 3164444);
+        assert_eq!(mock.received()[0].url.path(), "/v2/droplets/3164444");
+    }
+
+    #[test]
+    fn lookup_by_urn_of_other_resource_is_invalid() {
+        before();
+
+        let mock = lookup_mock(0);
+        match block_on(Droplet::lookup(&mock, "do:volume:506f78a4")) {
+            Err(Error::InvalidRequest(_)) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+        assert!(mock.received().is_empty());
+    }
+
+    #[test]
+    fn lookup_by_name() {
+        before();
+
+        let mock = lookup_mock(1);
+        let droplet = block_on(Droplet::lookup(&mock, "example.com")).unwrap();
+
+        assert_eq!(*droplet.id(), 3164444);
+        let received = mock.received();
+        assert_eq!(received[0].url.path(), "/v2/droplets");
+        assert_eq!(
+            received[0].url.query(),
+            Some("name=example.com&per_page=200")
+        );
+    }
+
+    #[test]
+    fn lookup_by_unknown_name_is_not_found() {
+        before();
+
+        let mock = lookup_mock(0);
+        match block_on(Droplet::lookup(&mock, "example.com")) {
+            Err(Error::NotFound { request_id: None }) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn lookup_by_ambiguous_name_is_invalid() {
+        before();
+
+        let mock = lookup_mock(2);
+        match block_on(Droplet::lookup(&mock, "example.com")) {
+            Err(Error::InvalidRequest(_)) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn create_spread_names_and_distributes_droplets() {
+        before();
+
+        let created = || json!({ "droplet": droplet_fixture() });
+        let mock = MockDigitalOcean::new()
+            .respond(Method::POST, "/droplets", StatusCode::ACCEPTED, created())
+            .respond(Method::POST, "/droplets", StatusCode::ACCEPTED, created())
+            .respond(
+                Method::POST,
+                "/droplets",
+                StatusCode::UNPROCESSABLE_ENTITY,
+                json!({ "id": "unprocessable_entity", "message": "Region is not available." }),
+            )
+            .respond(Method::POST, "/droplets", StatusCode::ACCEPTED, created());
+
+        let batch = block_on(Droplet::create_spread(
+            &mock,
+            "web",
+            vec!["nyc3".to_string(), "ams3".to_string()],
+            "s-1vcpu-1gb",
+            "ubuntu-20-04-x64",
+            2,
+            3,
+        ));
+        info!("{:#?}", batch);
+
+        let bodies: Vec<serde_json::Value> = mock
+            .received()
+            .iter()
+            .map(|request| serde_json::from_slice(&request.body).unwrap())
+            .collect();
+
+        let mut placed: Vec<(&str, &str)> = bodies
+            .iter()
+            .map(|body| {
+                (
+                    body["name"].as_str().unwrap(),
+                    body["region"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        placed.sort();
+        assert_eq!(
+            placed,
+            vec![
+                ("web-ams3-1", "ams3"),
+                ("web-ams3-2", "ams3"),
+                ("web-nyc3-1", "nyc3"),
+                ("web-nyc3-2", "nyc3"),
+            ]
+        );
+
+        assert_eq!(batch.succeeded.len(), 3);
+        assert_eq!(batch.failed.len(), 1);
+        assert_eq!(batch.failed[0].0, bodies[2]["name"].as_str().unwrap());
+        assert!(matches!(
+            batch.failed[0].1,
+            Error::UnprocessableEntity { .. }
+        ));
+    }
+}
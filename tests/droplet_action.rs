@@ -10,6 +10,7 @@ mod utils;
 
 use serde_json::Value;
 
+use digitalocean::api::droplet_fields::{BackupPlan, BackupPolicy, BackupWeekday};
 use digitalocean::api::{Action, Droplet};
 use digitalocean::method::{Create, Get, List};
 use digitalocean::request::Request;
@@ -77,6 +78,39 @@ fn disable_backups_produces_correct_request() {
     );
 }
 
+#[test]
+fn change_backup_policy_produces_correct_request() {
+    before();
+
+    let droplet_id = 123;
+    let correct_url = format!(
+        "https://api.digitalocean.com/v2/droplets/{}/actions",
+        droplet_id
+    );
+
+    let policy = BackupPolicy {
+        plan: BackupPlan::Weekly,
+        weekday: Some(BackupWeekday::Tue),
+        hour: Some(20),
+    };
+
+    let req: Request<Create, Action> = Droplet::get(droplet_id).change_backup_policy(policy);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "type": "change_backup_policy",
+            "backup_policy": {
+                "plan": "weekly",
+                "weekday": "TUE",
+                "hour": 20
+            }
+        })
+    );
+}
+
 #[test]
 fn reboot_produces_correct_request() {
     before();
@@ -313,6 +347,7 @@ fn rename_produces_correct_request() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn kernel_produces_correct_request() {
     before();
 
@@ -336,6 +371,25 @@ fn kernel_produces_correct_request() {
     );
 }
 
+#[test]
+fn change_kernel_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/droplets/123/actions";
+
+    let req: Request<Create, Action> = Droplet::get(123).change_kernel(7515);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "type": "change_kernel",
+            "kernel": 7515,
+        })
+    );
+}
+
 #[test]
 fn enable_ipv6_kernel_produces_correct_request() {
     before();
@@ -421,3 +475,71 @@ fn get_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+#[cfg(feature = "testing")]
+mod mock {
+    use reqwest::{Method, StatusCode};
+    use serde_json::{json, Value};
+
+    use digitalocean::api::Droplet;
+    use digitalocean::request::Executable;
+    use digitalocean::testing::MockDigitalOcean;
+
+    use crate::utils::{before, block_on};
+
+    fn accepting(kind: &str) -> MockDigitalOcean {
+        MockDigitalOcean::new().respond(
+            Method::POST,
+            "/droplets/3164450/actions",
+            StatusCode::CREATED,
+            json!({
+                "action": {
+                    "id": 36804758,
+                    "status": "in-progress",
+                    "type": kind,
+                    "started_at": "2014-11-14T16:31:00Z",
+                    "completed_at": null,
+                    "resource_id": 3164450,
+                    "resource_type": "droplet",
+                    "region_slug": "nyc3"
+                }
+            }),
+        )
+    }
+
+    fn sent_body(mock: &MockDigitalOcean) -> Value {
+        serde_json::from_slice(&mock.received()[0].body).unwrap()
+    }
+
+    #[test]
+    fn password_reset_is_executed() {
+        before();
+
+        let mock = accepting("password_reset");
+        let action = block_on(Droplet::get(3164450).password_reset().execute(&mock)).unwrap();
+        info!("{:#?}", action);
+
+        assert_eq!(action.kind(), "password_reset");
+        assert_eq!(sent_body(&mock), json!({ "type": "password_reset" }));
+    }
+
+    #[test]
+    fn rename_is_executed() {
+        before();
+
+        let mock = accepting("rename");
+        let action = block_on(
+            Droplet::get(3164450)
+                .rename("nifty-new-name")
+                .execute(&mock),
+        )
+        .unwrap();
+        info!("{:#?}", action);
+
+        assert_eq!(action.kind(), "rename");
+        assert_eq!(
+            sent_body(&mock),
+            json!({ "type": "rename", "name": "nifty-new-name" })
+        );
+    }
+}
@@ -0,0 +1,352 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+extern crate url;
+
+mod utils;
+
+use serde_json::Value;
+
+use digitalocean::api::Firewall;
+use digitalocean::method::{Get, List};
+use digitalocean::request::Request;
+
+use crate::utils::before;
+
+#[test]
+fn list_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/firewalls";
+
+    let req: Request<List, Vec<Firewall>> = Firewall::list();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn get_produces_correct_request() {
+    before();
+
+    let firewall_id = "bb4b2611-3d72-467b-8602-280330ecd65c";
+    let correct_url = format!("https://api.digitalocean.com/v2/firewalls/{}", firewall_id);
+
+    let req: Request<Get, Firewall> = Firewall::get(firewall_id);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+fn firewall_fixture() -> Value {
+    json!({
+        "id": "bb4b2611-3d72-467b-8602-280330ecd65c",
+        "status": "succeeded",
+        "created_at": "2017-05-23T21:24:00Z",
+        "pending_changes": [
+            { "droplet_id": 8043964, "removing": false, "status": "waiting" }
+        ],
+        "name": "firewall",
+        "inbound_rules": [
+            {
+                "protocol": "tcp",
+                "ports": "80",
+                "sources": { "load_balancer_uids": ["4de7ac8b-495b-4884-9a69-1050c6793cd6"] }
+            },
+            {
+                "protocol": "tcp",
+                "ports": "22",
+                "sources": { "tags": ["gateway"], "addresses": ["18.0.0.0/8"] }
+            }
+        ],
+        "outbound_rules": [
+            {
+                "protocol": "icmp",
+                "destinations": { "addresses": ["0.0.0.0/0", "::/0"] }
+            }
+        ],
+        "droplet_ids": [8043964],
+        "tags": []
+    })
+}
+
+#[test]
+fn firewall_deserializes() {
+    before();
+
+    let firewall: Firewall = serde_json::from_value(firewall_fixture()).unwrap();
+    info!("{:#?}", firewall);
+
+    assert_eq!(firewall.status(), "succeeded");
+    assert_eq!(firewall.pending_changes()[0].droplet_id, 8043964);
+    assert_eq!(firewall.inbound_rules().len(), 2);
+    assert_eq!(firewall.inbound_rules()[1].sources.tags, vec!["gateway"]);
+    assert!(firewall.inbound_rules()[1].sources.droplet_ids.is_empty());
+    assert_eq!(firewall.outbound_rules()[0].ports, "");
+    assert_eq!(firewall.outbound_rules()[0].destinations.addresses.len(), 2);
+    assert_eq!(*firewall.droplet_ids(), vec![8043964]);
+}
+
+#[cfg(feature = "testing")]
+mod mock {
+    use reqwest::{Method, StatusCode};
+    use serde_json::json;
+
+    use std::time::Duration;
+
+    use digitalocean::api::{Droplet, Firewall};
+    use digitalocean::error::Error;
+    use digitalocean::request::Executable;
+    use digitalocean::testing::MockDigitalOcean;
+
+    use super::firewall_fixture;
+    use crate::utils::{before, block_on};
+
+    #[test]
+    fn droplet_firewalls_are_listed() {
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::GET,
+            "/droplets/8043964/firewalls",
+            StatusCode::OK,
+            json!({
+                "firewalls": [firewall_fixture()],
+                "links": {},
+                "meta": { "total": 1 }
+            }),
+        );
+
+        let firewalls = block_on(Droplet::get(8043964).firewalls().execute(&mock)).unwrap();
+        info!("{:#?}", firewalls);
+
+        assert_eq!(firewalls.len(), 1);
+        assert_eq!(firewalls[0].name(), "firewall");
+        assert!(firewalls[0].droplet_ids().contains(&8043964));
+    }
+
+    #[test]
+    fn for_droplet_keeps_assigned_firewalls() {
+        before();
+
+        let mut other = firewall_fixture();
+        other["id"] = json!("fb6045f1-cf1d-4ca3-bfac-18832663025b");
+        other["name"] = json!("other");
+        other["droplet_ids"] = json!([8043965]);
+
+        let mut shared = firewall_fixture();
+        shared["id"] = json!("84d7d6e3-b3a6-4a6b-9ec1-d8fa8ad1e2c6");
+        shared["name"] = json!("shared");
+        shared["droplet_ids"] = json!([8043965, 8043964]);
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::GET,
+            "/firewalls",
+            StatusCode::OK,
+            json!({
+                "firewalls": [firewall_fixture(), other, shared],
+                "links": {},
+                "meta": { "total": 3 }
+            }),
+        );
+
+        let firewalls = block_on(Firewall::for_droplet(&mock, 8043964)).unwrap();
+        info!("{:#?}", firewalls);
+
+        let names: Vec<&str> = firewalls.iter().map(|f| f.name().as_str()).collect();
+        assert_eq!(names, vec!["firewall", "shared"]);
+        assert_eq!(mock.received()[0].url.path(), "/v2/firewalls");
+    }
+
+    fn firewall_with_status(status: &str) -> serde_json::Value {
+        let mut firewall = firewall_fixture();
+        firewall["status"] = json!(status);
+        json!({ "firewall": firewall })
+    }
+
+    #[test]
+    fn wait_until_ready_polls_until_succeeded() {
+        before();
+
+        let path = "/firewalls/bb4b2611-3d72-467b-8602-280330ecd65c";
+        let mock = MockDigitalOcean::new()
+            .respond(
+                Method::GET,
+                path,
+                StatusCode::OK,
+                firewall_with_status("waiting"),
+            )
+            .respond(
+                Method::GET,
+                path,
+                StatusCode::OK,
+                firewall_with_status("waiting"),
+            )
+            .respond(
+                Method::GET,
+                path,
+                StatusCode::OK,
+                firewall_with_status("succeeded"),
+            );
+
+        let firewall = block_on(
+            Firewall::get("bb4b2611-3d72-467b-8602-280330ecd65c").wait_until_ready(
+                &mock,
+                Duration::from_millis(1),
+                Duration::from_secs(5),
+            ),
+        )
+        .unwrap();
+        info!("{:#?}", firewall);
+
+        assert_eq!(firewall.status(), "succeeded");
+        assert_eq!(mock.received().len(), 3);
+    }
+
+    #[test]
+    fn wait_until_ready_fails_on_failed_status() {
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::GET,
+            "/firewalls/bb4b2611-3d72-467b-8602-280330ecd65c",
+            StatusCode::OK,
+            firewall_with_status("failed"),
+        );
+
+        match block_on(
+            Firewall::get("bb4b2611-3d72-467b-8602-280330ecd65c").wait_until_ready(
+                &mock,
+                Duration::from_millis(1),
+                Duration::from_secs(5),
+            ),
+        ) {
+            Err(Error::Failed(_)) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn wait_until_ready_times_out() {
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::GET,
+            "/firewalls/bb4b2611-3d72-467b-8602-280330ecd65c",
+            StatusCode::OK,
+            firewall_with_status("waiting"),
+        );
+
+        match block_on(
+            Firewall::get("bb4b2611-3d72-467b-8602-280330ecd65c").wait_until_ready(
+                &mock,
+                Duration::from_millis(5),
+                Duration::from_millis(20),
+            ),
+        ) {
+            Err(Error::Timeout(what)) => {
+                assert_eq!(what, "firewall bb4b2611-3d72-467b-8602-280330ecd65c")
+            }
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn wait_until_ready_without_deadline() {
+        before();
+
+        let path = "/firewalls/bb4b2611-3d72-467b-8602-280330ecd65c";
+        let mock = MockDigitalOcean::new()
+            .respond(
+                Method::GET,
+                path,
+                StatusCode::OK,
+                firewall_with_status("waiting"),
+            )
+            .respond(
+                Method::GET,
+                path,
+                StatusCode::OK,
+                firewall_with_status("succeeded"),
+            );
+
+        let firewall = block_on(
+            Firewall::get("bb4b2611-3d72-467b-8602-280330ecd65c").wait_until_ready(
+                &mock,
+                Duration::from_millis(1),
+                Duration::MAX,
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(firewall.status(), "succeeded");
+        assert_eq!(mock.received().len(), 2);
+    }
+
+    #[test]
+    fn wait_until_ready_with_longest_interval_times_out() {
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::GET,
+            "/firewalls/bb4b2611-3d72-467b-8602-280330ecd65c",
+            StatusCode::OK,
+            firewall_with_status("waiting"),
+        );
+
+        match block_on(
+            Firewall::get("bb4b2611-3d72-467b-8602-280330ecd65c").wait_until_ready(
+                &mock,
+                Duration::MAX,
+                Duration::from_secs(5),
+            ),
+        ) {
+            Err(Error::Timeout(_)) => assert_eq!(mock.received().len(), 1),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "cancellation")]
+    #[test]
+    fn wait_until_ready_stops_when_cancelled() {
+        use tokio_util::sync::CancellationToken;
+
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::GET,
+            "/firewalls/bb4b2611-3d72-467b-8602-280330ecd65c",
+            StatusCode::OK,
+            firewall_with_status("waiting"),
+        );
+
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+
+        let result = block_on(async {
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                canceller.cancel();
+            });
+
+            Firewall::get("bb4b2611-3d72-467b-8602-280330ecd65c")
+                .wait_until_ready_cancellable(
+                    &mock,
+                    Duration::from_millis(5),
+                    Duration::from_secs(5),
+                    &token,
+                )
+                .await
+        });
+
+        match result {
+            Err(Error::Cancelled) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+        assert!(!mock.received().is_empty());
+    }
+}
@@ -12,7 +12,8 @@ use serde_json::Value;
 use std::net::IpAddr;
 use std::str::FromStr;
 
-use digitalocean::api::FloatingIp;
+use digitalocean::api::{FloatingIp, Urn};
+use digitalocean::error::Error;
 use digitalocean::method::{Create, Delete, Get, List};
 use digitalocean::request::Request;
 
@@ -50,6 +51,25 @@ fn for_droplet_produces_correct_request() {
     );
 }
 
+#[test]
+fn for_droplet_with_zero_id_is_invalid() {
+    before();
+
+    let req: Request<Create, FloatingIp> = FloatingIp::for_droplet(123);
+    assert!(req.validate().is_ok());
+
+    let req: Request<Create, FloatingIp> = FloatingIp::for_droplet(0);
+    assert_eq!(*req.body(), json!({ "droplet_id": 0 }));
+
+    match req.validate() {
+        Err(Error::InvalidRequest(_)) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+
+    let req: Request<Create, FloatingIp> = FloatingIp::for_region("tor1");
+    assert!(req.validate().is_ok());
+}
+
 #[test]
 fn for_region_produces_correct_request() {
     before();
@@ -102,3 +122,159 @@ fn delete_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+#[test]
+fn urn_has_correct_format() {
+    before();
+
+    let ip = IpAddr::from_str("45.55.96.47").unwrap();
+
+    let req: Request<Get, FloatingIp> = FloatingIp::get(ip);
+    info!("{:#?}", req);
+
+    assert_eq!(req.urn(), "do:floatingip:45.55.96.47");
+    assert_eq!(req.urn(), Urn::FloatingIp(ip).to_string());
+}
+
+#[test]
+fn project_id_deserializes() {
+    before();
+
+    let fixture = json!({
+        "ip": "45.55.96.47",
+        "droplet": null,
+        "region": {
+            "name": "New York 3",
+            "slug": "nyc3",
+            "sizes": ["s-1vcpu-1gb"],
+            "features": ["backups", "ipv6"],
+            "available": true
+        },
+        "locked": false,
+        "project_id": "746c6152-2fa2-11ed-92d3-27aaa54e4988"
+    });
+
+    let floating_ip: FloatingIp = serde_json::from_value(fixture).unwrap();
+    info!("{:#?}", floating_ip);
+
+    assert_eq!(
+        floating_ip.project_id().as_deref(),
+        Some("746c6152-2fa2-11ed-92d3-27aaa54e4988")
+    );
+}
+
+#[test]
+fn display_shows_ip() {
+    before();
+
+    let floating_ip: FloatingIp = serde_json::from_value(json!({
+        "ip": "45.55.96.47",
+        "droplet": null,
+        "region": {
+            "name": "New York 3",
+            "slug": "nyc3",
+            "sizes": ["s-1vcpu-1gb"],
+            "features": ["backups", "ipv6"],
+            "available": true
+        },
+        "locked": false
+    }))
+    .unwrap();
+
+    assert_eq!(floating_ip.to_string(), "floating ip 45.55.96.47");
+}
+
+#[cfg(feature = "testing")]
+mod mock {
+    use reqwest::{Method, StatusCode};
+    use serde_json::json;
+
+    use digitalocean::api::FloatingIp;
+    use digitalocean::error::Error;
+    use digitalocean::request::Executable;
+    use digitalocean::testing::MockDigitalOcean;
+
+    use crate::utils::{before, block_on};
+
+    #[test]
+    fn delete_sends_delete_to_the_address() {
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::DELETE,
+            "/floating_ips/45.55.96.47",
+            StatusCode::NO_CONTENT,
+            json!(null),
+        );
+
+        let ip: std::net::IpAddr = "45.55.96.47".parse().unwrap();
+        block_on(FloatingIp::delete(ip).execute(&mock)).unwrap();
+
+        let received = mock.received();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].method, Method::DELETE);
+        assert_eq!(received[0].url.path(), "/v2/floating_ips/45.55.96.47");
+    }
+
+    #[test]
+    fn for_droplet_with_zero_id_is_not_sent() {
+        before();
+
+        let mock = MockDigitalOcean::new();
+
+        match block_on(FloatingIp::for_droplet(0).execute(&mock)) {
+            Err(Error::InvalidRequest(_)) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+        assert!(mock.received().is_empty());
+    }
+
+    fn floating_ip(ip: &str, project_id: Option<&str>) -> serde_json::Value {
+        json!({
+            "ip": ip,
+            "droplet": null,
+            "region": {
+                "name": "New York 3",
+                "slug": "nyc3",
+                "sizes": ["s-1vcpu-1gb"],
+                "features": ["backups", "ipv6"],
+                "available": true
+            },
+            "locked": false,
+            "project_id": project_id
+        })
+    }
+
+    #[test]
+    fn project_id_filters_floating_ips() {
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::GET,
+            "/floating_ips",
+            StatusCode::OK,
+            json!({
+                "floating_ips": [
+                    floating_ip("45.55.96.47", Some("746c6152-2fa2-11ed-92d3-27aaa54e4988")),
+                    floating_ip("45.55.96.48", Some("8e3c4f1a-2fa2-11ed-92d3-27aaa54e4988")),
+                    floating_ip("45.55.96.49", None),
+                    floating_ip("45.55.96.50", Some("746c6152-2fa2-11ed-92d3-27aaa54e4988"))
+                ],
+                "links": {},
+                "meta": { "total": 4 }
+            }),
+        );
+
+        let floating_ips = block_on(
+            FloatingIp::list()
+                .project_id("746c6152-2fa2-11ed-92d3-27aaa54e4988")
+                .execute(&mock),
+        )
+        .unwrap();
+        info!("{:#?}", floating_ips);
+
+        let ips: Vec<String> = floating_ips.iter().map(|f| f.ip().to_string()).collect();
+        assert_eq!(ips, vec!["45.55.96.47", "45.55.96.50"]);
+        assert_eq!(mock.received()[0].url.query(), Some("per_page=200"));
+    }
+}
@@ -98,3 +98,194 @@ fn unassign_produces_correct_request() {
         })
     );
 }
+
+#[cfg(feature = "testing")]
+mod mock {
+    use reqwest::{Method, StatusCode};
+    use serde_json::json;
+
+    use std::net::IpAddr;
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use digitalocean::api::FloatingIp;
+    use digitalocean::error::Error;
+    use digitalocean::testing::MockDigitalOcean;
+
+    use crate::utils::{before, block_on};
+
+    fn assign_action(status: &str) -> serde_json::Value {
+        json!({
+            "action": {
+                "id": 68212728,
+                "status": status,
+                "type": "assign_ip",
+                "started_at": "2015-10-15T17:45:44Z",
+                "completed_at": null,
+                "resource_id": 758603823,
+                "resource_type": "floating_ip",
+                "region_slug": "nyc3"
+            }
+        })
+    }
+
+    fn floating_ip(droplet_id: Option<usize>) -> serde_json::Value {
+        let droplet = droplet_id.map(|id| {
+            json!({
+                "id": id,
+                "name": "example.com",
+                "memory": 1024,
+                "vcpus": 1,
+                "disk": 25,
+                "locked": false,
+                "status": "active",
+                "kernel": null,
+                "created_at": "2020-07-21T18:37:44Z",
+                "features": [],
+                "backup_ids": [],
+                "next_backup_window": null,
+                "snapshot_ids": [],
+                "image": {
+                    "id": 63663980,
+                    "name": "20.04 (LTS) x64",
+                    "type": "snapshot",
+                    "distribution": "Ubuntu",
+                    "slug": "ubuntu-20-04-x64",
+                    "public": true,
+                    "regions": ["nyc3"],
+                    "min_disk_size": 20,
+                    "size_gigabytes": 2.36,
+                    "created_at": "2020-05-15T05:47:50Z"
+                },
+                "volume_ids": [],
+                "size_slug": "s-1vcpu-1gb",
+                "networks": { "v4": [], "v6": [] },
+                "region": {
+                    "name": "New York 3",
+                    "slug": "nyc3",
+                    "features": [],
+                    "available": true,
+                    "sizes": ["s-1vcpu-1gb"]
+                },
+                "tags": []
+            })
+        });
+
+        json!({
+            "floating_ip": {
+                "ip": "45.55.96.47",
+                "droplet": droplet,
+                "region": {
+                    "name": "New York 3",
+                    "slug": "nyc3",
+                    "sizes": ["s-1vcpu-1gb"],
+                    "features": ["backups", "ipv6"],
+                    "available": true
+                },
+                "locked": false
+            }
+        })
+    }
+
+    fn mock(assigned_to: Option<usize>, final_status: &str) -> MockDigitalOcean {
+        MockDigitalOcean::new()
+            .respond(
+                Method::POST,
+                "/floating_ips/45.55.96.47/actions",
+                StatusCode::CREATED,
+                assign_action("in-progress"),
+            )
+            .respond(
+                Method::GET,
+                "/floating_ips/45.55.96.47/actions/68212728",
+                StatusCode::OK,
+                assign_action("in-progress"),
+            )
+            .respond(
+                Method::GET,
+                "/floating_ips/45.55.96.47/actions/68212728",
+                StatusCode::OK,
+                assign_action(final_status),
+            )
+            .respond(
+                Method::GET,
+                "/floating_ips/45.55.96.47",
+                StatusCode::OK,
+                floating_ip(assigned_to),
+            )
+    }
+
+    fn assign_and_wait(mock: &MockDigitalOcean) -> Result<FloatingIp, Error> {
+        let ip = IpAddr::from_str("45.55.96.47").unwrap();
+        block_on(FloatingIp::get(ip).assign_and_wait(
+            mock,
+            123456,
+            Duration::from_millis(1),
+            Duration::from_secs(5),
+        ))
+    }
+
+    #[test]
+    fn assign_and_wait_polls_action_then_refetches() {
+        before();
+
+        let mock = mock(Some(123456), "completed");
+
+        let floating_ip = assign_and_wait(&mock).unwrap();
+        info!("{:#?}", floating_ip);
+
+        assert_eq!(*floating_ip.droplet().as_ref().unwrap().id(), 123456);
+
+        let received = mock.received();
+        let paths: Vec<_> = received
+            .iter()
+            .map(|request| (request.method.clone(), request.url.path().to_string()))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                (
+                    Method::POST,
+                    "/v2/floating_ips/45.55.96.47/actions".to_string()
+                ),
+                (
+                    Method::GET,
+                    "/v2/floating_ips/45.55.96.47/actions/68212728".to_string()
+                ),
+                (
+                    Method::GET,
+                    "/v2/floating_ips/45.55.96.47/actions/68212728".to_string()
+                ),
+                (Method::GET, "/v2/floating_ips/45.55.96.47".to_string()),
+            ]
+        );
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&received[0].body).unwrap(),
+            json!({ "type": "assign", "droplet_id": 123456 })
+        );
+    }
+
+    #[test]
+    fn assign_and_wait_fails_on_errored_action() {
+        before();
+
+        let mock = mock(None, "errored");
+
+        match assign_and_wait(&mock) {
+            Err(Error::Failed(_)) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn assign_and_wait_fails_when_not_assigned() {
+        before();
+
+        let mock = mock(None, "completed");
+
+        match assign_and_wait(&mock) {
+            Err(Error::Failed(_)) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+}
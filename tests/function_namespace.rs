@@ -0,0 +1,208 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+extern crate url;
+
+mod utils;
+
+use serde_json::Value;
+
+use digitalocean::api::{FunctionNamespace, FunctionTrigger, HasResponse, HasValue};
+use digitalocean::method::{Create, Delete, Get, List};
+use digitalocean::request::Request;
+
+use crate::utils::before;
+
+const NAMESPACE: &str = "fn-xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx";
+
+#[test]
+fn create_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/functions/namespaces";
+
+    let req: Request<Create, FunctionNamespace> = FunctionNamespace::create("nyc1", "my namespace");
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "region": "nyc1",
+            "label": "my namespace",
+        })
+    );
+}
+
+#[test]
+fn list_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/functions/namespaces";
+
+    let req: Request<List, Vec<FunctionNamespace>> = FunctionNamespace::list();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn get_produces_correct_request() {
+    before();
+
+    let correct_url = format!("https://api.digitalocean.com/v2/functions/namespaces/{NAMESPACE}");
+
+    let req: Request<Get, FunctionNamespace> = FunctionNamespace::get(NAMESPACE);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn delete_produces_correct_request() {
+    before();
+
+    let correct_url = format!("https://api.digitalocean.com/v2/functions/namespaces/{NAMESPACE}");
+
+    let req: Request<Delete, ()> = FunctionNamespace::delete(NAMESPACE);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn triggers_produces_correct_request() {
+    before();
+
+    let correct_url =
+        format!("https://api.digitalocean.com/v2/functions/namespaces/{NAMESPACE}/triggers");
+
+    let req: Request<List, Vec<FunctionTrigger>> = FunctionNamespace::get(NAMESPACE).triggers();
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn trigger_produces_correct_request() {
+    before();
+
+    let correct_url = format!(
+        "https://api.digitalocean.com/v2/functions/namespaces/{NAMESPACE}/triggers/my-trigger"
+    );
+
+    let req: Request<Get, FunctionTrigger> =
+        FunctionNamespace::get(NAMESPACE).trigger("my-trigger");
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+
+    let req: Request<Delete, ()> = FunctionNamespace::get(NAMESPACE).delete_trigger("my-trigger");
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+}
+
+#[test]
+fn create_trigger_produces_correct_request() {
+    before();
+
+    let correct_url =
+        format!("https://api.digitalocean.com/v2/functions/namespaces/{NAMESPACE}/triggers");
+
+    let req: Request<Create, FunctionTrigger> = FunctionNamespace::get(NAMESPACE)
+        .create_trigger("my-trigger", "hello/world", "*/5 * * * *")
+        .is_enabled(false)
+        .invoke_with(json!({ "name": "sammy" }));
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "name": "my-trigger",
+            "function": "hello/world",
+            "type": "SCHEDULED",
+            "is_enabled": false,
+            "scheduled_details": {
+                "cron": "*/5 * * * *",
+                "body": { "name": "sammy" },
+            },
+        })
+    );
+}
+
+#[test]
+fn namespace_list_deserializes_without_pagination() {
+    before();
+
+    let response: <Vec<FunctionNamespace> as HasResponse>::Response =
+        serde_json::from_value(json!({
+            "namespaces": [{
+                "created_at": "2022-09-14T04:16:45Z",
+                "updated_at": "2022-09-14T04:16:45Z",
+                "label": "my namespace",
+                "region": "nyc1",
+                "api_host": "https://faas-nyc1-2ef2e6cc.doserverless.co",
+                "namespace": NAMESPACE,
+                "uuid": "xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx",
+                "key": "d1zcd455h01mqjfs4s2eaewyejehi5f2uj4etqq3h7cera8iwkub6xg5of1wdde2"
+            }]
+        }))
+        .unwrap();
+    info!("{:#?}", response);
+
+    assert!(response.links().pages().is_none());
+
+    let namespaces: Vec<FunctionNamespace> = response.into_iter().collect();
+    assert_eq!(namespaces[0].namespace(), NAMESPACE);
+    assert_eq!(
+        namespaces[0].api_host(),
+        "https://faas-nyc1-2ef2e6cc.doserverless.co"
+    );
+    assert!(namespaces[0].key().is_some());
+}
+
+#[test]
+fn trigger_deserializes() {
+    before();
+
+    let response: <FunctionTrigger as HasResponse>::Response = serde_json::from_value(json!({
+        "trigger": {
+            "namespace": NAMESPACE,
+            "name": "my-trigger",
+            "function": "hello/world",
+            "type": "SCHEDULED",
+            "is_enabled": true,
+            "created_at": "2022-11-11T04:16:45Z",
+            "updated_at": "2022-11-11T04:16:45Z",
+            "scheduled_details": {
+                "cron": "* * * * *",
+                "body": { "name": "Welcome to DO!" }
+            },
+            "scheduled_runs": {
+                "last_run_at": "2022-11-11T04:16:45Z",
+                "next_run_at": "2022-11-11T04:17:45Z"
+            }
+        }
+    }))
+    .unwrap();
+
+    let trigger = response.value();
+    info!("{:#?}", trigger);
+
+    assert_eq!(trigger.function(), "hello/world");
+    assert_eq!(trigger.scheduled_details().cron, "* * * * *");
+    assert!(trigger
+        .scheduled_runs()
+        .as_ref()
+        .unwrap()
+        .next_run_at
+        .is_some());
+}
@@ -10,7 +10,7 @@ mod utils;
 
 use serde_json::Value;
 
-use digitalocean::api::Image;
+use digitalocean::api::{Image, ImageListType, ImageStatus, ImageType};
 use digitalocean::method::{Delete, Get, List, Update};
 use digitalocean::request::Request;
 
@@ -29,6 +29,51 @@ fn list_produces_correct_request() {
     assert_eq!(*req.body(), Value::Null);
 }
 
+#[test]
+fn list_by_tag_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/images?tag_name=base-image";
+
+    let req: Request<List, Vec<Image>> = Image::list().tag("base-image");
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+
+    let correct_url = "https://api.digitalocean.com/v2/images?private=true&tag_name=base-image";
+
+    let req: Request<List, Vec<Image>> = Image::user().tag("base-image");
+    assert_eq!(req.url().as_str(), correct_url);
+}
+
+#[test]
+fn list_by_image_type_produces_correct_request() {
+    before();
+
+    let cases = vec![
+        (ImageListType::Distribution, "type=distribution"),
+        (ImageListType::Application, "type=application"),
+    ];
+
+    for (kind, query) in cases {
+        let req: Request<List, Vec<Image>> = Image::list().image_type(kind);
+        info!("{:#?}", req);
+
+        assert_eq!(req.url().query(), Some(query));
+        assert_eq!(*req.body(), Value::Null);
+    }
+
+    assert_eq!(
+        Image::distributions().url(),
+        Image::list().image_type(ImageListType::Distribution).url()
+    );
+    assert_eq!(
+        Image::applications().url(),
+        Image::list().image_type(ImageListType::Application).url()
+    );
+}
+
 #[test]
 fn get_produces_correct_request() {
     before();
@@ -76,3 +121,154 @@ fn delete_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+fn image_fixture() -> Value {
+    json!({
+        "id": 7555620,
+        "name": "Nifty New Snapshot",
+        "distribution": "Ubuntu",
+        "slug": null,
+        "public": false,
+        "regions": ["nyc2", "nyc3"],
+        "created_at": "2014-11-04T22:23:02Z",
+        "type": "snapshot",
+        "min_disk_size": 20,
+        "size_gigabytes": 2.34,
+        "status": "available"
+    })
+}
+
+#[test]
+fn image_deserializes_type_and_status() {
+    before();
+
+    let image: Image = serde_json::from_value(image_fixture()).unwrap();
+    info!("{:#?}", image);
+
+    assert_eq!(*image.kind(), ImageType::Snapshot);
+    assert_eq!(*image.status(), Some(ImageStatus::Available));
+}
+
+#[test]
+fn image_type_deserializes_every_variant() {
+    before();
+
+    let cases = vec![
+        ("snapshot", ImageType::Snapshot),
+        ("backup", ImageType::Backup),
+        ("custom", ImageType::Custom),
+        ("distribution", ImageType::Distribution),
+        ("application", ImageType::Application),
+        ("base", ImageType::Unknown),
+    ];
+
+    for (kind, expected) in cases {
+        let mut fixture = image_fixture();
+        fixture["type"] = json!(kind);
+
+        let image: Image = serde_json::from_value(fixture).unwrap();
+        assert_eq!(*image.kind(), expected);
+    }
+}
+
+#[test]
+fn image_status_deserializes_every_variant() {
+    before();
+
+    let cases = vec![
+        ("NEW", ImageStatus::New),
+        ("available", ImageStatus::Available),
+        ("pending", ImageStatus::Pending),
+        ("deleted", ImageStatus::Deleted),
+        ("retired", ImageStatus::Retired),
+        ("errored", ImageStatus::Unknown),
+    ];
+
+    for (status, expected) in cases {
+        let mut fixture = image_fixture();
+        fixture["status"] = json!(status);
+
+        let image: Image = serde_json::from_value(fixture).unwrap();
+        assert_eq!(*image.status(), Some(expected));
+    }
+
+    let mut fixture = image_fixture();
+    fixture.as_object_mut().unwrap().remove("status");
+
+    let image: Image = serde_json::from_value(fixture).unwrap();
+    assert_eq!(*image.status(), None);
+}
+
+#[cfg(feature = "testing")]
+mod mock {
+    use reqwest::{Method, StatusCode};
+    use serde_json::json;
+
+    use digitalocean::api::Image;
+    use digitalocean::testing::MockDigitalOcean;
+
+    use super::image_fixture;
+    use crate::utils::{before, block_on};
+
+    fn region(slug: &str, available: bool, features: Vec<&str>) -> serde_json::Value {
+        json!({
+            "name": slug.to_uppercase(),
+            "slug": slug,
+            "sizes": ["s-1vcpu-1gb"],
+            "available": available,
+            "features": features
+        })
+    }
+
+    fn mock(source_features: Vec<&str>) -> MockDigitalOcean {
+        MockDigitalOcean::new()
+            .respond(
+                Method::GET,
+                "/images/7555620",
+                StatusCode::OK,
+                json!({ "image": image_fixture() }),
+            )
+            .respond(
+                Method::GET,
+                "/regions",
+                StatusCode::OK,
+                json!({
+                    "regions": [
+                        region("nyc3", true, source_features),
+                        region("ams3", true, vec!["backups", "image_transfer"]),
+                        region("sfo1", false, vec!["backups", "image_transfer"])
+                    ],
+                    "links": {},
+                    "meta": { "total": 3 }
+                }),
+            )
+    }
+
+    #[test]
+    fn can_transfer_to_available_region() {
+        before();
+
+        let mock = mock(vec!["backups", "image_transfer"]);
+
+        assert!(block_on(Image::can_transfer_to(&mock, 7555620, "ams3")).unwrap());
+    }
+
+    #[test]
+    fn cannot_transfer_to_unavailable_or_unknown_region() {
+        before();
+
+        let mock = mock(vec!["backups", "image_transfer"]);
+
+        assert!(!block_on(Image::can_transfer_to(&mock, 7555620, "sfo1")).unwrap());
+        assert!(!block_on(Image::can_transfer_to(&mock, 7555620, "xyz9")).unwrap());
+    }
+
+    #[test]
+    fn cannot_transfer_without_source_support() {
+        before();
+
+        let mock = mock(vec!["backups"]);
+
+        assert!(!block_on(Image::can_transfer_to(&mock, 7555620, "ams3")).unwrap());
+    }
+}
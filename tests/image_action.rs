@@ -96,3 +96,74 @@ fn convert_produces_correct_request() {
         })
     );
 }
+
+#[test]
+fn action_history_get_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/images/123/actions/456";
+
+    let req: Request<Get, Action> = Image::get(123).actions().get(456);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[cfg(feature = "testing")]
+mod mock {
+    use reqwest::{Method, StatusCode};
+    use serde_json::json;
+
+    use digitalocean::api::Image;
+    use digitalocean::request::Executable;
+    use digitalocean::testing::MockDigitalOcean;
+
+    use crate::utils::{before, block_on};
+
+    fn action(id: usize, kind: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "status": "completed",
+            "type": kind,
+            "started_at": "2014-11-14T16:42:45Z",
+            "completed_at": "2014-11-14T16:56:37Z",
+            "resource_id": 7938269,
+            "resource_type": "image",
+            "region_slug": "nyc2"
+        })
+    }
+
+    #[test]
+    fn action_history_is_listed() {
+        before();
+
+        let mock = MockDigitalOcean::new()
+            .respond(
+                Method::GET,
+                "/images/7938269/actions",
+                StatusCode::OK,
+                json!({
+                    "actions": [action(36805527, "transfer"), action(36805528, "convert")],
+                    "links": {},
+                    "meta": { "total": 2 }
+                }),
+            )
+            .respond(
+                Method::GET,
+                "/images/7938269/actions/36805528",
+                StatusCode::OK,
+                json!({ "action": action(36805528, "convert") }),
+            );
+
+        let actions = block_on(Image::get(7938269).actions().execute(&mock)).unwrap();
+        info!("{:#?}", actions);
+
+        let kinds: Vec<&str> = actions.iter().map(|a| a.kind().as_str()).collect();
+        assert_eq!(kinds, vec!["transfer", "convert"]);
+
+        let action = block_on(Image::get(7938269).actions().get(36805528).execute(&mock)).unwrap();
+        assert_eq!(*action.id(), 36805528);
+        assert_eq!(action.kind(), "convert");
+    }
+}
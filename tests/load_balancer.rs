@@ -10,6 +10,7 @@ mod utils;
 
 use serde_json::Value;
 
+use digitalocean::api::load_balancer_fields::{LbAlgorithm, LbSize};
 use digitalocean::api::LoadBalancer;
 use digitalocean::method::{Create, Delete, Get, List, Update};
 use digitalocean::request::Request;
@@ -51,7 +52,7 @@ fn create_produces_correct_request() {
     before();
 
     let correct_url = "https://api.digitalocean.com/v2/load_balancers";
-    let (name, region, algo) = ("test", "tor1", "least_connections");
+    let (name, region, algo) = ("test", "tor1", LbAlgorithm::LeastConnections);
     let rule_1 = ("tcp", 22, "tcp", 22);
     let rule_2 = ("http", 443, "http", 443, None, false);
 
@@ -67,7 +68,7 @@ fn create_produces_correct_request() {
         json!({
             "name": name,
             "region": region,
-            "algorithm": algo,
+            "algorithm": "least_connections",
             "forwarding_rules": [
                 {
                     "entry_protocol": rule_1.0,
@@ -90,6 +91,69 @@ fn create_produces_correct_request() {
     );
 }
 
+#[test]
+fn create_serializes_algorithm() {
+    before();
+
+    let req: Request<Create, LoadBalancer> =
+        LoadBalancer::create("test", "tor1").algorithm(LbAlgorithm::RoundRobin);
+
+    assert_eq!(req.body()["algorithm"], json!("round_robin"));
+}
+
+#[test]
+fn create_serializes_size() {
+    before();
+
+    let req: Request<Create, LoadBalancer> =
+        LoadBalancer::create("test", "tor1").size(LbSize::Medium);
+
+    assert_eq!(req.body()["size"], json!("lb-medium"));
+}
+
+#[test]
+fn create_serializes_size_unit() {
+    before();
+
+    let req: Request<Create, LoadBalancer> = LoadBalancer::create("test", "tor1").size_unit(3);
+
+    assert_eq!(req.body()["size_unit"], json!(3));
+}
+
+#[test]
+fn create_serializes_redirect_http_to_https() {
+    before();
+
+    let req: Request<Create, LoadBalancer> =
+        LoadBalancer::create("test", "tor1").redirect_http_to_https(true);
+
+    assert_eq!(req.body()["redirect_http_to_https"], json!(true));
+}
+
+#[test]
+fn create_serializes_enable_proxy_protocol() {
+    before();
+
+    let req: Request<Create, LoadBalancer> =
+        LoadBalancer::create("test", "tor1").enable_proxy_protocol(true);
+
+    assert_eq!(req.body()["enable_proxy_protocol"], json!(true));
+}
+
+#[test]
+fn create_serializes_vpc_uuid() {
+    before();
+
+    let req: Request<Create, LoadBalancer> =
+        LoadBalancer::create("test", "tor1").vpc_uuid("c33931f2-a26a-4e61-b85c-4e95a2ec431b");
+    info!("{:#?}", req);
+
+    assert_eq!(
+        req.body()["vpc_uuid"],
+        json!("c33931f2-a26a-4e61-b85c-4e95a2ec431b")
+    );
+}
+
 #[test]
 fn update_produces_correct_request() {
     before();
@@ -261,3 +325,256 @@ fn remove_forwarding_rule_produces_correct_request() {
         })
     );
 }
+
+#[cfg(feature = "testing")]
+mod mock {
+    use chrono::{TimeDelta, Utc};
+    use reqwest::{Method, StatusCode};
+    use serde_json::json;
+    use std::time::Duration;
+
+    use digitalocean::api::LoadBalancer;
+    use digitalocean::testing::MockDigitalOcean;
+
+    use crate::utils::{before, block_on};
+
+    fn load_balancer(id: &str, certificate_ids: &[&str]) -> serde_json::Value {
+        let forwarding_rules: Vec<_> = certificate_ids
+            .iter()
+            .map(|certificate_id| {
+                json!({
+                    "entry_protocol": "https",
+                    "entry_port": 443,
+                    "target_protocol": "http",
+                    "target_port": 80,
+                    "certificate_id": certificate_id,
+                    "tls_passthrough": false
+                })
+            })
+            .collect();
+
+        json!({
+            "id": id,
+            "name": format!("lb-{}", id),
+            "ip": "104.131.186.241",
+            "algorithm": "round_robin",
+            "status": "active",
+            "created_at": "2017-02-01T22:22:58Z",
+            "forwarding_rules": forwarding_rules,
+            "health_check": {
+                "protocol": "http",
+                "port": 80,
+                "path": "/",
+                "check_interval_seconds": 10,
+                "response_timeout_seconds": 5,
+                "healthy_threshold": 5,
+                "unhealthy_threshold": 3
+            },
+            "sticky_sessions": { "type": "none" },
+            "region": {
+                "name": "New York 3",
+                "slug": "nyc3",
+                "sizes": ["s-1vcpu-1gb"],
+                "features": ["backups", "ipv6"],
+                "available": true
+            },
+            "tag": "",
+            "droplet_ids": [3164444, 3164445],
+            "redirect_http_to_https": false
+        })
+    }
+
+    fn certificate(id: &str, expires_in_days: i64) -> serde_json::Value {
+        json!({
+            "certificate": {
+                "id": id,
+                "name": format!("cert-{}", id),
+                "not_after": Utc::now() + TimeDelta::days(expires_in_days),
+                "sha1_fingerprint": "dfcc9f57d86bf58e321c2c6c31c7a971be244ac7",
+                "created_at": "2017-02-08T16:02:37Z"
+            }
+        })
+    }
+
+    #[test]
+    fn audit_certificates_finds_expiring_certificates() {
+        before();
+
+        let mock = MockDigitalOcean::new()
+            .respond(
+                Method::GET,
+                "/load_balancers",
+                StatusCode::OK,
+                json!({
+                    "load_balancers": [
+                        load_balancer("lb1", &["soon", "later"]),
+                        load_balancer("lb2", &["later"]),
+                        load_balancer("lb3", &["soon", ""])
+                    ],
+                    "links": {},
+                    "meta": { "total": 3 }
+                }),
+            )
+            .respond(
+                Method::GET,
+                "/certificates/soon",
+                StatusCode::OK,
+                certificate("soon", 5),
+            )
+            .respond(
+                Method::GET,
+                "/certificates/later",
+                StatusCode::OK,
+                certificate("later", 90),
+            );
+
+        let expiring = block_on(LoadBalancer::audit_certificates(
+            &mock,
+            Duration::from_secs(30 * 24 * 60 * 60),
+        ))
+        .unwrap();
+        info!("{:#?}", expiring);
+
+        assert!(expiring.is_complete());
+        let pairs: Vec<(&str, &str)> = expiring
+            .succeeded
+            .iter()
+            .map(|(lb, cert)| (lb.id().as_str(), cert.id().as_str()))
+            .collect();
+        assert_eq!(pairs, vec![("lb1", "soon"), ("lb3", "soon")]);
+
+        // One list and one fetch per distinct certificate.
+        assert_eq!(mock.received().len(), 3);
+    }
+
+    #[test]
+    fn audit_certificates_reports_missing_certificates() {
+        before();
+
+        let mock = MockDigitalOcean::new()
+            .respond(
+                Method::GET,
+                "/load_balancers",
+                StatusCode::OK,
+                json!({
+                    "load_balancers": [
+                        load_balancer("lb1", &["deleted"]),
+                        load_balancer("lb2", &["soon"])
+                    ],
+                    "links": {},
+                    "meta": { "total": 2 }
+                }),
+            )
+            .respond(
+                Method::GET,
+                "/certificates/deleted",
+                StatusCode::NOT_FOUND,
+                json!({
+                    "id": "not_found",
+                    "message": "The resource you were accessing could not be found."
+                }),
+            )
+            .respond(
+                Method::GET,
+                "/certificates/soon",
+                StatusCode::OK,
+                certificate("soon", 5),
+            );
+
+        // The longest window does not overflow, every certificate expires
+        // within it.
+        let audit = block_on(LoadBalancer::audit_certificates(&mock, Duration::MAX)).unwrap();
+        info!("{:#?}", audit);
+
+        assert_eq!(audit.succeeded.len(), 1);
+        assert_eq!(audit.succeeded[0].1.id(), "soon");
+        assert_eq!(audit.failed.len(), 1);
+        assert_eq!(audit.failed[0].0, "deleted");
+    }
+
+    #[test]
+    fn patch_keeps_unchanged_attributes() {
+        before();
+
+        let mut current = load_balancer("lb1", &["cert"]);
+        current["enable_proxy_protocol"] = json!(true);
+        current["size_unit"] = json!(3);
+        current["size"] = json!("lb-small");
+        current["vpc_uuid"] = json!("c33931f2-a26a-4e61-b85c-4e95a2ec431b");
+
+        let mut updated = current.clone();
+        updated["name"] = json!("renamed");
+
+        let mock = MockDigitalOcean::new()
+            .respond(
+                Method::GET,
+                "/load_balancers/lb1",
+                StatusCode::OK,
+                json!({ "load_balancer": current }),
+            )
+            .respond(
+                Method::PUT,
+                "/load_balancers/lb1",
+                StatusCode::OK,
+                json!({ "load_balancer": updated }),
+            );
+
+        let load_balancer =
+            block_on(LoadBalancer::get("lb1").patch(&mock, |update| update.name("renamed")))
+                .unwrap();
+        assert_eq!(load_balancer.name(), "renamed");
+
+        let received = mock.received();
+        assert_eq!(received[1].method, Method::PUT);
+
+        let body: serde_json::Value = serde_json::from_slice(&received[1].body).unwrap();
+        info!("{:#?}", body);
+
+        assert_eq!(body["name"], json!("renamed"));
+        assert_eq!(body["region"], json!("nyc3"));
+        assert_eq!(body["algorithm"], json!("round_robin"));
+        assert_eq!(body["forwarding_rules"][0]["certificate_id"], json!("cert"));
+        assert_eq!(body["health_check"]["path"], json!("/"));
+        assert_eq!(body["sticky_sessions"]["type"], json!("none"));
+        assert_eq!(body["redirect_http_to_https"], json!(false));
+        assert_eq!(body["droplet_ids"], json!([3164444, 3164445]));
+        assert!(body.get("tag").is_none());
+
+        // Attributes not modelled on `LoadBalancer` are not reset either.
+        assert_eq!(body["enable_proxy_protocol"], json!(true));
+        assert_eq!(body["size_unit"], json!(3));
+        assert_eq!(
+            body["vpc_uuid"],
+            json!("c33931f2-a26a-4e61-b85c-4e95a2ec431b")
+        );
+        assert!(body.get("size").is_none());
+        assert!(body.get("id").is_none());
+        assert!(body.get("status").is_none());
+    }
+
+    #[test]
+    fn patch_switching_to_tag_drops_droplet_ids() {
+        before();
+
+        let mock = MockDigitalOcean::new()
+            .respond(
+                Method::GET,
+                "/load_balancers/lb1",
+                StatusCode::OK,
+                json!({ "load_balancer": load_balancer("lb1", &[]) }),
+            )
+            .respond(
+                Method::PUT,
+                "/load_balancers/lb1",
+                StatusCode::OK,
+                json!({ "load_balancer": load_balancer("lb1", &[]) }),
+            );
+
+        block_on(LoadBalancer::get("lb1").patch(&mock, |update| update.tag("web"))).unwrap();
+
+        let body: serde_json::Value = serde_json::from_slice(&mock.received()[1].body).unwrap();
+
+        assert_eq!(body["tag"], json!("web"));
+        assert!(body.get("droplet_ids").is_none());
+    }
+}
@@ -0,0 +1,102 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+extern crate url;
+
+mod utils;
+
+use std::time::Duration;
+
+use chrono::{TimeZone, Utc};
+use serde_json::Value;
+
+use digitalocean::api::Metrics;
+use digitalocean::method::Get;
+use digitalocean::request::Request;
+
+use crate::utils::before;
+
+fn query(req: &Request<Get, Metrics>, key: &str) -> i64 {
+    req.url()
+        .query_pairs()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.parse().unwrap())
+        .unwrap()
+}
+
+#[test]
+fn droplet_cpu_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/monitoring/metrics/droplet/cpu?host_id=17209102&start=1620683817&end=1620705417";
+    let start = Utc.timestamp_opt(1620683817, 0).unwrap();
+    let end = Utc.timestamp_opt(1620705417, 0).unwrap();
+
+    let req: Request<Get, Metrics> = Metrics::droplet_cpu(17209102, start, end);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[test]
+fn droplet_cpu_last_ends_now() {
+    before();
+
+    let before_call = Utc::now().timestamp();
+    let req: Request<Get, Metrics> =
+        Metrics::droplet_cpu_last(17209102, Duration::from_secs(60 * 60));
+    let after_call = Utc::now().timestamp();
+    info!("{:#?}", req);
+
+    let (start, end) = (query(&req, "start"), query(&req, "end"));
+
+    assert!(before_call <= end && end <= after_call);
+    assert_eq!(end - start, 60 * 60);
+}
+
+#[cfg(feature = "testing")]
+mod mock {
+    use reqwest::{Method, StatusCode};
+    use serde_json::json;
+    use std::time::Duration;
+
+    use digitalocean::api::Metrics;
+    use digitalocean::request::Executable;
+    use digitalocean::testing::MockDigitalOcean;
+
+    use crate::utils::{before, block_on};
+
+    #[test]
+    fn droplet_cpu_deserializes() {
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::GET,
+            "/monitoring/metrics/droplet/cpu",
+            StatusCode::OK,
+            json!({
+                "status": "success",
+                "data": {
+                    "resultType": "matrix",
+                    "result": [{
+                        "metric": { "host_id": "17209102", "mode": "idle" },
+                        "values": [[1620683817, "1.8"], [1620684117, "2.1"]]
+                    }]
+                }
+            }),
+        );
+
+        let metrics =
+            block_on(Metrics::droplet_cpu_last(17209102, Duration::from_secs(600)).execute(&mock))
+                .unwrap();
+        info!("{:#?}", metrics);
+
+        assert_eq!(metrics.result_type(), "matrix");
+        assert_eq!(metrics.result()[0].metric["mode"], "idle");
+        assert_eq!(
+            metrics.result()[0].values[1],
+            (1620684117.0, "2.1".to_string())
+        );
+    }
+}
@@ -0,0 +1,801 @@
+#![cfg(feature = "testing")]
+
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+extern crate reqwest;
+#[macro_use]
+extern crate serde_json;
+extern crate url;
+
+mod utils;
+
+use reqwest::{Method, StatusCode};
+
+use digitalocean::api::{
+    concat, merge_pages, Account, Action, Droplet, HasResponse, HasValue, SshKey, Tag,
+};
+use digitalocean::error::Error;
+use digitalocean::method::Create;
+use digitalocean::request::{Executable, Request};
+use digitalocean::testing::MockDigitalOcean;
+use digitalocean::{DigitalOcean, DEFAULT_USER_AGENT};
+
+use crate::utils::{before, block_on};
+
+fn account_fixture() -> serde_json::Value {
+    json!({
+        "account": {
+            "droplet_limit": 25,
+            "floating_ip_limit": 5,
+            "email": "sammy@digitalocean.com",
+            "uuid": "b6fr89dbf6d9156cace5f3c78dc9851d957381ef",
+            "email_verified": true,
+            "status": "active",
+            "status_message": ""
+        }
+    })
+}
+
+#[test]
+fn mock_answers_registered_request() {
+    before();
+
+    let mock =
+        MockDigitalOcean::new().respond(Method::GET, "/account", StatusCode::OK, account_fixture());
+
+    let account = block_on(Account::get().execute(&mock)).unwrap();
+    info!("{:#?}", account);
+
+    assert_eq!(account.email(), "sammy@digitalocean.com");
+    assert_eq!(*account.droplet_limit(), 25);
+}
+
+#[test]
+fn mock_answers_through_client_execute() {
+    before();
+
+    let mock = MockDigitalOcean::new().respond(
+        Method::DELETE,
+        "/droplets/123",
+        StatusCode::NO_CONTENT,
+        serde_json::Value::Null,
+    );
+
+    block_on(mock.execute(Droplet::delete(123))).unwrap();
+}
+
+#[test]
+fn mock_serves_responses_in_order() {
+    before();
+
+    let mock = MockDigitalOcean::new()
+        .respond(
+            Method::GET,
+            "/account",
+            StatusCode::UNAUTHORIZED,
+            serde_json::Value::Null,
+        )
+        .respond(Method::GET, "/account", StatusCode::OK, account_fixture());
+
+    match block_on(Account::get().execute(&mock)) {
+        Err(Error::UnexpectedStatus { status, .. }) => {
+            assert_eq!(status, StatusCode::UNAUTHORIZED)
+        }
+        other => panic!("Unexpected result: {:?}", other),
+    }
+
+    // The last response is repeated once the queue is drained.
+    assert!(block_on(Account::get().execute(&mock)).is_ok());
+    assert!(block_on(Account::get().execute(&mock)).is_ok());
+}
+
+#[test]
+fn mock_answers_unknown_requests_with_not_found() {
+    before();
+
+    let mock = MockDigitalOcean::new();
+
+    match block_on(Droplet::get(123).execute(&mock)) {
+        Err(Error::NotFound { .. }) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn gzip_responses_are_decoded() {
+    before();
+
+    let mock = MockDigitalOcean::new().respond_gzip(
+        Method::GET,
+        "/account",
+        StatusCode::OK,
+        account_fixture(),
+    );
+
+    let account = block_on(Account::get().execute(&mock)).unwrap();
+    info!("{:#?}", account);
+
+    assert_eq!(account.email(), "sammy@digitalocean.com");
+}
+
+#[test]
+fn gzip_responses_are_not_decoded_without_compression() {
+    before();
+
+    let mock = MockDigitalOcean::new()
+        .respond_gzip(Method::GET, "/account", StatusCode::OK, account_fixture())
+        .configure(|client| client.with_compression(false).unwrap());
+
+    match block_on(Account::get().execute(&mock)) {
+        Err(Error::ReqwestError(error)) => assert!(error.is_decode()),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn requests_carry_default_user_agent() {
+    before();
+
+    let mock =
+        MockDigitalOcean::new().respond(Method::GET, "/account", StatusCode::OK, account_fixture());
+
+    block_on(Account::get().execute(&mock)).unwrap();
+
+    let received = mock.received();
+    info!("{:#?}", received);
+
+    assert_eq!(received.len(), 1);
+    assert_eq!(
+        received[0].headers[reqwest::header::USER_AGENT],
+        DEFAULT_USER_AGENT
+    );
+    assert!(DEFAULT_USER_AGENT.starts_with("digitalocean-rs/"));
+}
+
+#[test]
+fn user_agent_can_be_overridden() {
+    before();
+
+    let mock = MockDigitalOcean::new()
+        .respond(Method::GET, "/account", StatusCode::OK, account_fixture())
+        .configure(|client| client.with_user_agent("my-tool/1.0"));
+
+    block_on(Account::get().execute(&mock)).unwrap();
+
+    let received = mock.received();
+    info!("{:#?}", received);
+
+    assert_eq!(
+        received[0].headers[reqwest::header::USER_AGENT],
+        "my-tool/1.0"
+    );
+}
+
+fn ssh_keys_page(ids: std::ops::Range<usize>, next: Option<&str>) -> serde_json::Value {
+    let ssh_keys: Vec<_> = ids
+        .map(|id| {
+            json!({
+                "id": id,
+                "fingerprint": format!("fingerprint-{}", id),
+                "public_key": "ssh-rsa AAAA",
+                "name": format!("key-{}", id)
+            })
+        })
+        .collect();
+
+    json!({
+        "ssh_keys": ssh_keys,
+        "links": { "pages": { "next": next } },
+        "meta": { "total": 1000 }
+    })
+}
+
+fn ssh_keys(page: serde_json::Value) -> Vec<SshKey> {
+    serde_json::from_value::<<Vec<SshKey> as HasResponse>::Response>(page)
+        .unwrap()
+        .value()
+}
+
+#[test]
+fn merge_pages_joins_three_pages() {
+    before();
+
+    let pages = vec![
+        ssh_keys(ssh_keys_page(0..2, None)),
+        ssh_keys(ssh_keys_page(2..4, None)),
+        ssh_keys(ssh_keys_page(4..6, None)),
+    ];
+    let keys = merge_pages(pages);
+    info!("{:#?}", keys);
+
+    let ids: Vec<usize> = keys.iter().map(|key| *key.id()).collect();
+    assert_eq!(ids, vec![0, 1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn concat_sums_totals() {
+    before();
+
+    let lists = (0..3)
+        .map(|list| {
+            let page = ssh_keys_page(list * 2..list * 2 + 2, None);
+            let meta = serde_json::from_value(page["meta"].clone()).unwrap();
+            (ssh_keys(page), meta)
+        })
+        .collect();
+    let (keys, meta) = concat(lists);
+    info!("{:#?}", keys);
+
+    assert_eq!(keys.len(), 6);
+    assert_eq!(*meta.total(), 3000);
+}
+
+#[test]
+fn limit_below_page_size_fetches_one_page() {
+    before();
+
+    let mock = MockDigitalOcean::new().respond(
+        Method::GET,
+        "/account/keys",
+        StatusCode::OK,
+        ssh_keys_page(
+            0..50,
+            Some("https://api.digitalocean.com/v2/account/keys?page=2&per_page=50"),
+        ),
+    );
+
+    let keys = block_on(SshKey::list().limit(Some(50)).execute(&mock)).unwrap();
+
+    let received = mock.received();
+    info!("{:#?}", received);
+
+    assert_eq!(keys.len(), 50);
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].url.query(), Some("per_page=50"));
+}
+
+#[test]
+fn limit_above_page_size_fetches_two_pages() {
+    before();
+
+    let mock = MockDigitalOcean::new()
+        .respond(
+            Method::GET,
+            "/account/keys",
+            StatusCode::OK,
+            ssh_keys_page(
+                0..200,
+                Some("https://api.digitalocean.com/v2/account/keys?page=2&per_page=200"),
+            ),
+        )
+        .respond(
+            Method::GET,
+            "/account/keys",
+            StatusCode::OK,
+            ssh_keys_page(
+                200..400,
+                Some("https://api.digitalocean.com/v2/account/keys?page=3&per_page=200"),
+            ),
+        );
+
+    let keys = block_on(SshKey::list().limit(Some(250)).execute(&mock)).unwrap();
+
+    let received = mock.received();
+    info!("{:#?}", received);
+
+    assert_eq!(keys.len(), 250);
+    assert_eq!(*keys[249].id(), 249);
+    assert_eq!(received.len(), 2);
+    assert_eq!(received[1].url.query(), Some("page=2&per_page=200"));
+}
+
+#[test]
+fn no_limit_fetches_every_page() {
+    before();
+
+    let mock = MockDigitalOcean::new()
+        .respond(
+            Method::GET,
+            "/account/keys",
+            StatusCode::OK,
+            ssh_keys_page(
+                0..200,
+                Some("https://api.digitalocean.com/v2/account/keys?page=2&per_page=200"),
+            ),
+        )
+        .respond(
+            Method::GET,
+            "/account/keys",
+            StatusCode::OK,
+            ssh_keys_page(200..300, None),
+        );
+
+    let keys = block_on(SshKey::list().execute(&mock)).unwrap();
+
+    assert_eq!(keys.len(), 300);
+    assert_eq!(mock.received().len(), 2);
+}
+
+#[test]
+fn get_page_resumes_from_captured_next_url() {
+    before();
+
+    let mock = MockDigitalOcean::new()
+        .respond(
+            Method::GET,
+            "/account/keys",
+            StatusCode::OK,
+            ssh_keys_page(
+                0..50,
+                Some("https://api.digitalocean.com/v2/account/keys?page=2&per_page=50"),
+            ),
+        )
+        .respond(
+            Method::GET,
+            "/account/keys",
+            StatusCode::OK,
+            ssh_keys_page(50..80, None),
+        );
+
+    let first =
+        url::Url::parse("https://api.digitalocean.com/v2/account/keys?per_page=50").unwrap();
+    let (keys, next) = block_on(mock.get_page::<SshKey>(first)).unwrap();
+    assert_eq!(keys.len(), 50);
+
+    // As if persisted and parsed again after a restart.
+    let next = url::Url::parse(next.unwrap().as_str()).unwrap();
+    let (keys, next) = block_on(mock.get_page::<SshKey>(next)).unwrap();
+
+    assert_eq!(keys.len(), 30);
+    assert_eq!(*keys[0].id(), 50);
+    assert!(next.is_none());
+    assert_eq!(mock.received()[1].url.query(), Some("page=2&per_page=50"));
+}
+
+#[test]
+fn get_page_rejects_urls_outside_the_api() {
+    before();
+
+    let mock = MockDigitalOcean::new();
+
+    for url in [
+        "https://example.com/v2/account/keys?page=2",
+        "http://api.digitalocean.com/v2/account/keys?page=2",
+        "https://api.digitalocean.com:8443/v2/account/keys?page=2",
+        "https://user@api.digitalocean.com/v2/account/keys?page=2",
+        "https://api.digitalocean.com.example.com/v2/account/keys",
+        "https://api.digitalocean.com/v2",
+    ] {
+        let url = url::Url::parse(url).unwrap();
+        match block_on(mock.get_page::<SshKey>(url)) {
+            Err(Error::InvalidRequest(_)) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    assert!(mock.received().is_empty());
+}
+
+#[test]
+fn execute_raw_matches_typed_execute() {
+    before();
+
+    let mock =
+        MockDigitalOcean::new().respond(Method::GET, "/account", StatusCode::OK, account_fixture());
+
+    let raw = block_on(Account::get().execute_raw(&mock)).unwrap();
+    let account = block_on(Account::get().execute(&mock)).unwrap();
+    info!("{:#?}", raw);
+
+    assert_eq!(raw, account_fixture());
+    assert_eq!(raw["account"], serde_json::to_value(&account).unwrap());
+}
+
+#[test]
+fn execute_raw_without_content_is_null() {
+    before();
+
+    let mock = MockDigitalOcean::new().respond(
+        Method::DELETE,
+        "/droplets/123",
+        StatusCode::NO_CONTENT,
+        serde_json::Value::Null,
+    );
+
+    let raw = block_on(Droplet::delete(123).execute_raw(&mock)).unwrap();
+
+    assert_eq!(raw, serde_json::Value::Null);
+    assert_eq!(mock.received()[0].method, Method::DELETE);
+}
+
+#[test]
+fn errors_carry_request_id() {
+    before();
+
+    let mock = MockDigitalOcean::new().respond(
+        Method::GET,
+        "/account",
+        StatusCode::INTERNAL_SERVER_ERROR,
+        serde_json::Value::Null,
+    );
+
+    assert!(block_on(Account::get().execute(&mock)).is_err());
+
+    match block_on(Account::get().execute(&mock)) {
+        Err(error @ Error::UnexpectedStatus { .. }) => {
+            assert_eq!(
+                error.to_string(),
+                "Unexpected status code: 500 Internal Server Error (request id: mock-2)"
+            );
+        }
+        other => panic!("Unexpected result: {:?}", other),
+    }
+
+    match block_on(Droplet::get(123).execute(&mock)) {
+        Err(error @ Error::NotFound { .. }) => {
+            assert_eq!(error.to_string(), "Not Found (request id: mock-3)");
+        }
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn html_maintenance_page_is_service_unavailable() {
+    before();
+
+    let page = "<html><body><h1>Down for maintenance</h1></body></html>";
+    let mock = MockDigitalOcean::new()
+        .respond_text(
+            Method::GET,
+            "/account",
+            StatusCode::SERVICE_UNAVAILABLE,
+            "text/html; charset=utf-8",
+            page,
+        )
+        .respond_text(
+            Method::GET,
+            "/account/keys",
+            StatusCode::SERVICE_UNAVAILABLE,
+            "text/html",
+            page,
+        );
+
+    match block_on(Account::get().execute(&mock)) {
+        Err(error @ Error::ServiceUnavailable { .. }) => {
+            assert_eq!(
+                error.to_string(),
+                "Service unavailable: 503 Service Unavailable (request id: mock-1)"
+            );
+            if let Error::ServiceUnavailable { body, .. } = error {
+                assert_eq!(body, page);
+            }
+        }
+        other => panic!("Unexpected result: {:?}", other),
+    }
+
+    assert!(matches!(
+        block_on(SshKey::list().execute(&mock)),
+        Err(Error::ServiceUnavailable { .. })
+    ));
+}
+
+fn action_fixture() -> serde_json::Value {
+    json!({
+        "action": {
+            "id": 36804745,
+            "status": "in-progress",
+            "type": "enable_backups",
+            "started_at": "2014-11-14T16:30:56Z",
+            "completed_at": null,
+            "resource_id": 3164450,
+            "resource_type": "droplet",
+            "region_slug": "nyc3"
+        }
+    })
+}
+
+#[test]
+fn bodyless_post_sends_empty_body() {
+    before();
+
+    let mock = MockDigitalOcean::new().respond(
+        Method::POST,
+        "/droplets/3164450/actions",
+        StatusCode::CREATED,
+        action_fixture(),
+    );
+
+    let url = "https://api.digitalocean.com/v2/droplets/3164450/actions"
+        .parse()
+        .unwrap();
+    let req: Request<Create, Action> = Request::new(url);
+    block_on(req.clone().execute(&mock)).unwrap();
+    block_on(req.execute_raw(&mock)).unwrap();
+
+    let received = mock.received();
+    info!("{:#?}", received);
+
+    assert_eq!(received.len(), 2);
+    for request in received {
+        assert!(request.body.is_empty());
+        assert_eq!(request.headers[reqwest::header::CONTENT_LENGTH], "0");
+    }
+}
+
+#[test]
+fn post_with_body_sends_json() {
+    before();
+
+    let mock = MockDigitalOcean::new().respond(
+        Method::POST,
+        "/droplets/3164450/actions",
+        StatusCode::CREATED,
+        action_fixture(),
+    );
+
+    block_on(Droplet::get(3164450).enable_backups().execute(&mock)).unwrap();
+
+    let received = mock.received();
+    info!("{:#?}", received);
+
+    let body: serde_json::Value = serde_json::from_slice(&received[0].body).unwrap();
+    assert_eq!(body, json!({ "type": "enable_backups" }));
+}
+
+#[test]
+fn pool_config_keeps_client_configuration() {
+    before();
+
+    let mock = MockDigitalOcean::new()
+        .respond(Method::GET, "/account", StatusCode::OK, account_fixture())
+        .configure(|client| {
+            client
+                .with_user_agent("provisioner/1.0")
+                .with_pool_config(8, std::time::Duration::from_secs(30))
+                .unwrap()
+        });
+
+    block_on(Account::get().execute(&mock)).unwrap();
+
+    let received = mock.received();
+    info!("{:#?}", received);
+
+    assert_eq!(received.len(), 1);
+    assert_eq!(
+        received[0].headers[reqwest::header::USER_AGENT],
+        "provisioner/1.0"
+    );
+}
+
+#[test]
+fn api_version_is_sent_in_accept_header() {
+    before();
+
+    let mock = MockDigitalOcean::new()
+        .respond(Method::GET, "/account", StatusCode::OK, account_fixture())
+        .configure(|client| client.with_api_version("v2.1"));
+
+    block_on(Account::get().execute(&mock)).unwrap();
+    // Unanswered, but sent all the same.
+    block_on(Droplet::delete(123).execute(&mock)).ok();
+
+    let received = mock.received();
+    assert_eq!(received.len(), 2);
+    for request in received {
+        assert_eq!(
+            request.headers[reqwest::header::ACCEPT],
+            "application/vnd.digitalocean.v2.1+json"
+        );
+    }
+}
+
+#[test]
+fn no_api_version_is_asked_for_by_default() {
+    before();
+
+    let mock =
+        MockDigitalOcean::new().respond(Method::GET, "/account", StatusCode::OK, account_fixture());
+
+    block_on(Account::get().execute(&mock)).unwrap();
+
+    assert!(mock.received()[0]
+        .headers
+        .get(reqwest::header::ACCEPT)
+        .is_none());
+}
+
+#[test]
+fn accepting_invalid_certs_keeps_client_configuration() {
+    before();
+
+    DigitalOcean::new("token")
+        .unwrap()
+        .danger_accept_invalid_certs(false)
+        .unwrap();
+
+    let mock = MockDigitalOcean::new()
+        .respond(Method::GET, "/account", StatusCode::OK, account_fixture())
+        .configure(|client| {
+            client
+                .with_user_agent("mitmproxy-test/1.0")
+                .with_pool_config(8, std::time::Duration::from_secs(30))
+                .unwrap()
+                .danger_accept_invalid_certs(true)
+                .unwrap()
+        });
+
+    block_on(Account::get().execute(&mock)).unwrap();
+
+    let received = mock.received();
+    assert_eq!(received.len(), 1);
+    assert_eq!(
+        received[0].headers[reqwest::header::USER_AGENT],
+        "mitmproxy-test/1.0"
+    );
+}
+
+#[test]
+fn retry_budget_retries_rate_limited_requests() {
+    before();
+
+    let mock = MockDigitalOcean::new()
+        .respond(
+            Method::GET,
+            "/account",
+            StatusCode::TOO_MANY_REQUESTS,
+            json!({ "id": "too_many_requests", "message": "API Rate limit exceeded." }),
+        )
+        .respond(Method::GET, "/account", StatusCode::OK, account_fixture())
+        .configure(|client| client.with_retry_budget(5, std::time::Duration::from_secs(60)));
+
+    block_on(Account::get().execute(&mock)).unwrap();
+
+    assert_eq!(mock.received().len(), 2);
+}
+
+#[test]
+fn saturated_retry_budget_fails_fast() {
+    before();
+
+    let mock = MockDigitalOcean::new()
+        .respond(
+            Method::GET,
+            "/account",
+            StatusCode::SERVICE_UNAVAILABLE,
+            json!({ "id": "service_unavailable", "message": "Try again later." }),
+        )
+        .configure(|client| client.with_retry_budget(2, std::time::Duration::from_secs(60)));
+
+    // The first request spends the whole budget...
+    match block_on(Account::get().execute(&mock)) {
+        Err(Error::UnexpectedStatus { status, .. }) => {
+            assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE)
+        }
+        other => panic!("Unexpected result: {:?}", other),
+    }
+    assert_eq!(mock.received().len(), 3);
+
+    // ...so a clone sharing it is not retried at all.
+    let clone = mock.clone();
+    assert!(block_on(Account::get().execute(&clone)).is_err());
+    assert_eq!(mock.received().len(), 4);
+}
+
+#[test]
+fn creates_are_only_retried_when_rate_limited() {
+    before();
+
+    let unavailable = json!({ "id": "service_unavailable", "message": "Try again later." });
+    let mock = MockDigitalOcean::new()
+        .respond(
+            Method::POST,
+            "/tags",
+            StatusCode::SERVICE_UNAVAILABLE,
+            unavailable,
+        )
+        .configure(|client| client.with_retry_budget(10, std::time::Duration::from_secs(60)));
+
+    // The API may have created the tag before failing, it is not sent again.
+    assert!(block_on(Tag::create("web").execute(&mock)).is_err());
+    assert_eq!(mock.received().len(), 1);
+
+    let mock = MockDigitalOcean::new()
+        .respond(
+            Method::POST,
+            "/tags",
+            StatusCode::TOO_MANY_REQUESTS,
+            json!({ "id": "too_many_requests", "message": "API Rate limit exceeded." }),
+        )
+        .respond(
+            Method::POST,
+            "/tags",
+            StatusCode::CREATED,
+            json!({ "tag": { "name": "web", "resources": { "count": 0 } } }),
+        )
+        .configure(|client| client.with_retry_budget(10, std::time::Duration::from_secs(60)));
+
+    block_on(Tag::create("web").execute(&mock)).unwrap();
+    assert_eq!(mock.received().len(), 2);
+}
+
+#[test]
+fn requests_are_not_retried_without_budget() {
+    before();
+
+    let mock = MockDigitalOcean::new().respond(
+        Method::GET,
+        "/account",
+        StatusCode::TOO_MANY_REQUESTS,
+        json!({ "id": "too_many_requests", "message": "API Rate limit exceeded." }),
+    );
+
+    assert!(block_on(Account::get().execute(&mock)).is_err());
+    assert_eq!(mock.received().len(), 1);
+}
+
+#[test]
+fn request_max_retries_overrides_client() {
+    before();
+
+    let unavailable = json!({ "id": "service_unavailable", "message": "Try again later." });
+    let mock = MockDigitalOcean::new()
+        .respond(
+            Method::GET,
+            "/account",
+            StatusCode::SERVICE_UNAVAILABLE,
+            unavailable.clone(),
+        )
+        .configure(|client| client.with_retry_budget(10, std::time::Duration::from_secs(60)));
+
+    // The client retries 3 times, this request only once...
+    assert!(block_on(Account::get().max_retries(1).execute(&mock)).is_err());
+    assert_eq!(mock.received().len(), 2);
+
+    // ...and this one not at all.
+    assert!(block_on(Account::get().max_retries(0).execute(&mock)).is_err());
+    assert_eq!(mock.received().len(), 3);
+
+    // Without a budget the client does not retry, but the request may.
+    let mock = MockDigitalOcean::new().respond(
+        Method::GET,
+        "/account",
+        StatusCode::SERVICE_UNAVAILABLE,
+        unavailable,
+    );
+    assert!(block_on(Account::get().max_retries(1).execute(&mock)).is_err());
+    assert_eq!(mock.received().len(), 2);
+}
+
+#[test]
+fn request_timeout_overrides_client() {
+    before();
+
+    let mock = MockDigitalOcean::new()
+        .respond(Method::GET, "/account", StatusCode::OK, account_fixture())
+        .respond(
+            Method::GET,
+            "/droplets",
+            StatusCode::OK,
+            json!({ "droplets": [], "links": {}, "meta": { "total": 0 } }),
+        )
+        .configure(|client| {
+            client
+                .with_timeout(std::time::Duration::from_secs(30))
+                .unwrap()
+        });
+
+    let long = std::time::Duration::from_secs(600);
+    block_on(Account::get().execute(&mock)).unwrap();
+    block_on(Account::get().timeout(long).execute(&mock)).unwrap();
+    block_on(Droplet::list().timeout(long).execute(&mock)).unwrap();
+
+    // The timeout of the client is applied by the client itself, requests
+    // only carry theirs when they override it.
+    let timeouts: Vec<_> = mock
+        .received()
+        .iter()
+        .map(|request| request.timeout)
+        .collect();
+    assert_eq!(timeouts, vec![None, Some(long), Some(long)]);
+}
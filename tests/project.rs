@@ -0,0 +1,81 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+extern crate url;
+
+mod utils;
+
+use serde_json::Value;
+
+use digitalocean::api::{HasResponse, HasValue, Project};
+
+use crate::utils::before;
+
+fn default_project_fixture() -> Value {
+    json!({
+        "project": {
+            "id": "4e1bfbc3-dc3e-41f2-a18f-1b4d7ba71679",
+            "owner_uuid": "99525febec065ca37b2ffe4f852fd2b2581895e7",
+            "owner_id": 258992,
+            "name": "my-web-api",
+            "description": "My website API",
+            "purpose": "Service or API",
+            "environment": "Production",
+            "is_default": true,
+            "created_at": "2018-09-27T20:10:35Z",
+            "updated_at": "2018-09-27T20:10:35Z"
+        }
+    })
+}
+
+#[test]
+fn default_project_deserializes() {
+    before();
+
+    let response: <Project as HasResponse>::Response =
+        serde_json::from_value(default_project_fixture()).unwrap();
+    let project = response.value();
+    info!("{:#?}", project);
+
+    assert_eq!(project.id(), "4e1bfbc3-dc3e-41f2-a18f-1b4d7ba71679");
+    assert_eq!(project.name(), "my-web-api");
+    assert_eq!(project.environment().as_deref(), Some("Production"));
+    assert!(*project.is_default());
+}
+
+#[cfg(feature = "testing")]
+mod mock {
+    use reqwest::{Method, StatusCode};
+
+    use digitalocean::api::Project;
+    use digitalocean::testing::MockDigitalOcean;
+
+    use crate::utils::{before, block_on};
+
+    #[test]
+    fn default_fetches_the_default_project() {
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::GET,
+            "/projects/default",
+            StatusCode::OK,
+            super::default_project_fixture(),
+        );
+
+        let project = block_on(Project::default(&mock)).unwrap();
+        info!("{:#?}", project);
+
+        assert!(*project.is_default());
+
+        let received = mock.received();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].method, Method::GET);
+        assert_eq!(
+            received[0].url.as_str(),
+            "https://api.digitalocean.com/v2/projects/default"
+        );
+    }
+}
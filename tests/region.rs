@@ -27,3 +27,217 @@ fn list_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+#[cfg(feature = "testing")]
+mod mock {
+    use reqwest::{Method, StatusCode};
+    use serde_json::json;
+
+    use digitalocean::api::{Region, RegionFeature};
+    use digitalocean::request::Executable;
+    use digitalocean::testing::MockDigitalOcean;
+
+    use crate::utils::{before, block_on};
+
+    fn regions_fixture() -> serde_json::Value {
+        json!({
+            "regions": [
+                {
+                    "name": "New York 1",
+                    "slug": "nyc1",
+                    "sizes": ["s-1vcpu-1gb"],
+                    "features": ["backups", "ipv6"],
+                    "available": true
+                },
+                {
+                    "name": "San Francisco 1",
+                    "slug": "sfo1",
+                    "sizes": [],
+                    "features": ["backups"],
+                    "available": false
+                },
+                {
+                    "name": "Amsterdam 3",
+                    "slug": "ams3",
+                    "sizes": ["s-1vcpu-1gb"],
+                    "features": ["backups", "ipv6"],
+                    "available": true
+                }
+            ],
+            "links": {},
+            "meta": { "total": 3 }
+        })
+    }
+
+    #[test]
+    fn only_available_filters_unavailable_regions() {
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::GET,
+            "/regions",
+            StatusCode::OK,
+            regions_fixture(),
+        );
+
+        let regions = block_on(Region::list().only_available().execute(&mock)).unwrap();
+        info!("{:#?}", regions);
+
+        let slugs: Vec<&str> = regions.iter().map(|r| r.slug().as_str()).collect();
+        assert_eq!(slugs, vec!["nyc1", "ams3"]);
+    }
+
+    #[test]
+    fn get_resolves_slug() {
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::GET,
+            "/regions",
+            StatusCode::OK,
+            regions_fixture(),
+        );
+
+        let region = block_on(Region::get(&mock, "ams3")).unwrap().unwrap();
+        info!("{:#?}", region);
+
+        assert_eq!(region.name(), "Amsterdam 3");
+        assert_eq!(*region.features(), vec!["backups", "ipv6"]);
+        assert!(region.has_feature(RegionFeature::Ipv6));
+        assert!(!region.has_feature(RegionFeature::ImageTransfer));
+        assert!(!region.has_feature(RegionFeature::Unknown));
+
+        assert!(block_on(Region::get(&mock, "xyz9")).unwrap().is_none());
+    }
+
+    #[test]
+    fn cursor_walks_pages() {
+        before();
+
+        let mut first = regions_fixture();
+        first["regions"] = json!([regions_fixture()["regions"][0]]);
+        first["links"] = json!({
+            "pages": {
+                "next": "https://api.digitalocean.com/v2/regions?page=2&per_page=1",
+                "last": "https://api.digitalocean.com/v2/regions?page=2&per_page=1"
+            }
+        });
+
+        let mut second = regions_fixture();
+        second["regions"] = json!([regions_fixture()["regions"][2]]);
+        second["links"] = json!({
+            "pages": {
+                "first": "https://api.digitalocean.com/v2/regions?page=1&per_page=1",
+                "prev": "https://api.digitalocean.com/v2/regions?page=1&per_page=1"
+            }
+        });
+
+        let mock = MockDigitalOcean::new()
+            .respond(Method::GET, "/regions", StatusCode::OK, first)
+            .respond(Method::GET, "/regions", StatusCode::OK, second);
+
+        let mut cursor = Region::list().limit(Some(1)).cursor(&mock);
+
+        let page = block_on(cursor.next_page()).unwrap().unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].slug(), "nyc1");
+
+        let page = block_on(cursor.next_page()).unwrap().unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].slug(), "ams3");
+
+        assert!(block_on(cursor.next_page()).unwrap().is_none());
+    }
+
+    fn region(slug: &str) -> serde_json::Value {
+        json!({
+            "name": slug.to_uppercase(),
+            "slug": slug,
+            "sizes": ["s-1vcpu-1gb"],
+            "features": ["backups"],
+            "available": true
+        })
+    }
+
+    fn floating_ip(ip: &str, slug: &str) -> serde_json::Value {
+        json!({ "ip": ip, "region": region(slug), "droplet": null })
+    }
+
+    #[test]
+    fn inventory_lists_every_resource_type() {
+        before();
+
+        let mock = MockDigitalOcean::new()
+            .respond(
+                Method::GET,
+                "/droplets",
+                StatusCode::OK,
+                json!({ "droplets": [], "links": {}, "meta": { "total": 0 } }),
+            )
+            .respond(
+                Method::GET,
+                "/volumes",
+                StatusCode::OK,
+                json!({
+                    "volumes": [{
+                        "id": "7724db7c-e098-11e5-b522-000f53304e51",
+                        "region": region("nyc1"),
+                        "droplet_ids": [],
+                        "name": "example",
+                        "description": "",
+                        "size_gigabytes": 10,
+                        "created_at": "2016-03-02T17:00:49Z"
+                    }],
+                    "links": {},
+                    "meta": { "total": 1 }
+                }),
+            )
+            .respond(
+                Method::GET,
+                "/load_balancers",
+                StatusCode::OK,
+                json!({ "load_balancers": [], "links": {}, "meta": { "total": 0 } }),
+            )
+            .respond(
+                Method::GET,
+                "/floating_ips",
+                StatusCode::OK,
+                json!({
+                    "floating_ips": [
+                        floating_ip("45.55.96.47", "nyc1"),
+                        floating_ip("45.55.96.48", "ams3")
+                    ],
+                    "links": {},
+                    "meta": { "total": 2 }
+                }),
+            );
+
+        let inventory = block_on(Region::inventory(&mock, "nyc1")).unwrap();
+        info!("{:#?}", inventory);
+
+        assert!(inventory.droplets().is_empty());
+        assert_eq!(inventory.volumes().len(), 1);
+        assert!(inventory.load_balancers().is_empty());
+        assert_eq!(inventory.floating_ips().len(), 1);
+        assert_eq!(inventory.floating_ips()[0].ip().to_string(), "45.55.96.47");
+
+        let received = mock.received();
+        let mut paths: Vec<&str> = received.iter().map(|r| r.url.path()).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                "/v2/droplets",
+                "/v2/floating_ips",
+                "/v2/load_balancers",
+                "/v2/volumes"
+            ]
+        );
+
+        let volumes = received
+            .iter()
+            .find(|r| r.url.path() == "/v2/volumes")
+            .unwrap();
+        assert_eq!(volumes.url.query(), Some("region=nyc1&per_page=200"));
+    }
+}
@@ -1,6 +1,7 @@
 extern crate digitalocean;
 #[macro_use]
 extern crate log;
+#[macro_use]
 extern crate serde_json;
 extern crate url;
 extern crate url_serde;
@@ -27,3 +28,128 @@ fn list_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+fn size_fixture() -> Value {
+    json!({
+        "slug": "s-1vcpu-1gb",
+        "memory": 1024,
+        "vcpus": 1,
+        "disk": 25,
+        "transfer": 1.0,
+        "price_monthly": 5.0,
+        "price_hourly": 0.00743999984115362,
+        "regions": ["ams2", "ams3", "nyc3"],
+        "available": true
+    })
+}
+
+#[test]
+fn gpu_size_deserializes_gpu_and_disk_info() {
+    before();
+
+    let mut fixture = size_fixture();
+    fixture["slug"] = json!("gpu-h100x1-80gb");
+    fixture["gpu_info"] = json!({
+        "count": 1,
+        "vram": { "amount": 80, "unit": "gib" },
+        "model": "nvidia_h100"
+    });
+    fixture["disk_info"] = json!([
+        { "type": "local", "size": { "amount": 720, "unit": "gib" } },
+        { "type": "scratch", "size": { "amount": 5120, "unit": "gib" } }
+    ]);
+
+    let size: Size = serde_json::from_value(fixture).unwrap();
+    info!("{:#?}", size);
+
+    let gpu_info = size.gpu_info().as_ref().unwrap();
+    assert_eq!(gpu_info.count, 1);
+    assert_eq!(gpu_info.model, "nvidia_h100");
+    assert_eq!(gpu_info.vram.amount, 80);
+    assert_eq!(gpu_info.vram.unit, "gib");
+
+    assert_eq!(size.disk_info().len(), 2);
+    assert_eq!(size.disk_info()[1].kind, "scratch");
+    assert_eq!(size.disk_info()[1].size.amount, 5120);
+}
+
+#[test]
+fn size_without_gpu_info_deserializes() {
+    before();
+
+    let size: Size = serde_json::from_value(size_fixture()).unwrap();
+    info!("{:#?}", size);
+
+    assert!(size.gpu_info().is_none());
+    assert!(size.disk_info().is_empty());
+}
+
+#[cfg(feature = "testing")]
+mod mock {
+    use reqwest::{Method, StatusCode};
+    use serde_json::json;
+
+    use digitalocean::api::Size;
+    use digitalocean::request::Executable;
+    use digitalocean::testing::MockDigitalOcean;
+
+    use super::size_fixture;
+    use crate::utils::{before, block_on};
+
+    fn sizes() -> MockDigitalOcean {
+        let size = |slug: &str, memory: usize, price: f64| {
+            let mut size = size_fixture();
+            size["slug"] = json!(slug);
+            size["memory"] = json!(memory);
+            size["price_monthly"] = json!(price);
+            size
+        };
+
+        MockDigitalOcean::new().respond(
+            Method::GET,
+            "/sizes",
+            StatusCode::OK,
+            json!({
+                "sizes": [
+                    size("m-2vcpu-16gb", 16384, 84.0),
+                    size("s-1vcpu-1gb", 1024, 6.0),
+                    size("c-2", 4096, 42.0),
+                    size("s-2vcpu-4gb", 4096, 24.0)
+                ],
+                "links": {},
+                "meta": { "total": 4 }
+            }),
+        )
+    }
+
+    fn slugs(sizes: &[Size]) -> Vec<&str> {
+        sizes.iter().map(|size| size.slug().as_str()).collect()
+    }
+
+    #[test]
+    fn sorted_by_price_orders_cheapest_first() {
+        before();
+
+        let sizes = block_on(Size::list().sorted_by_price().execute(&sizes())).unwrap();
+        info!("{:#?}", sizes);
+
+        assert_eq!(
+            slugs(&sizes),
+            vec!["s-1vcpu-1gb", "s-2vcpu-4gb", "c-2", "m-2vcpu-16gb"]
+        );
+    }
+
+    #[test]
+    fn sorted_by_memory_orders_smallest_first() {
+        before();
+
+        let sizes = block_on(Size::list().sorted_by_memory().execute(&sizes())).unwrap();
+        info!("{:#?}", sizes);
+
+        // Sizes with the same memory keep the order of the API.
+        assert_eq!(
+            slugs(&sizes),
+            vec!["s-1vcpu-1gb", "c-2", "s-2vcpu-4gb", "m-2vcpu-16gb"]
+        );
+    }
+}
@@ -0,0 +1,160 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+extern crate url;
+
+mod utils;
+
+use digitalocean::api::{Droplet, RegionSlug, SizeSlug, SlugCatalog};
+use digitalocean::error::Error;
+use digitalocean::method::Create;
+use digitalocean::request::Request;
+
+use crate::utils::before;
+
+#[test]
+fn well_formed_slugs_are_valid() {
+    before();
+
+    let region = RegionSlug::try_from("nyc1").unwrap();
+    let size = SizeSlug::try_from("s-1vcpu-1gb").unwrap();
+    info!("{:?} {:?}", region, size);
+
+    assert_eq!(region.as_ref(), "nyc1");
+    assert_eq!(size.to_string(), "s-1vcpu-1gb");
+
+    // Sizes are not checked against a list, new ones are accepted.
+    assert!(SizeSlug::try_from("gpu-h100x1-80gb").is_ok());
+}
+
+#[test]
+fn malformed_slugs_are_invalid() {
+    before();
+
+    for slug in ["nyc", "1", "NYC1", "nyc-1", ""] {
+        match RegionSlug::try_from(slug) {
+            Err(Error::InvalidRequest(_)) => {}
+            other => panic!("Unexpected result for {:?}: {:?}", slug, other),
+        }
+    }
+
+    for slug in ["s-1vcpu-1GB", "s--1gb", "-s-1gb", "s 1gb", ""] {
+        match SizeSlug::try_from(slug) {
+            Err(Error::InvalidRequest(_)) => {}
+            other => panic!("Unexpected result for {:?}: {:?}", slug, other),
+        }
+    }
+}
+
+#[test]
+fn catalog_rejects_unlisted_slugs() {
+    before();
+
+    let catalog = SlugCatalog::new(["nyc1", "tor1"], ["s-1vcpu-1gb"]);
+
+    assert_eq!(catalog.region("tor1").unwrap().as_ref(), "tor1");
+    assert_eq!(catalog.size("s-1vcpu-1gb").unwrap().as_ref(), "s-1vcpu-1gb");
+
+    match catalog.region("sfo3") {
+        Err(Error::InvalidRequest(_)) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+    match catalog.size("s-1vcpu-1g") {
+        Err(Error::InvalidRequest(_)) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn slugs_are_usable_in_droplet_create() {
+    before();
+
+    let region = RegionSlug::try_from("tor1").unwrap();
+    let size = SizeSlug::try_from("s-1vcpu-1gb").unwrap();
+
+    let req: Request<Create, Droplet> = Droplet::create("bear", region, size, "ubuntu-24-04-x64");
+    info!("{:#?}", req);
+
+    assert_eq!(
+        *req.body(),
+        json!({
+            "name": "bear",
+            "region": "tor1",
+            "size": "s-1vcpu-1gb",
+            "image": "ubuntu-24-04-x64",
+        })
+    );
+}
+
+#[cfg(feature = "testing")]
+mod mock {
+    use reqwest::{Method, StatusCode};
+    use serde_json::json;
+
+    use digitalocean::api::SlugCatalog;
+    use digitalocean::testing::MockDigitalOcean;
+
+    use crate::utils::{before, block_on};
+
+    fn region(slug: &str) -> serde_json::Value {
+        json!({
+            "name": slug.to_uppercase(),
+            "slug": slug,
+            "sizes": ["s-1vcpu-1gb"],
+            "features": ["backups"],
+            "available": true
+        })
+    }
+
+    fn size(slug: &str) -> serde_json::Value {
+        json!({
+            "slug": slug,
+            "memory": 1024,
+            "vcpus": 1,
+            "disk": 25,
+            "transfer": 1.0,
+            "price_monthly": 5.0,
+            "price_hourly": 0.00743999984115362,
+            "regions": ["nyc1", "tor1"],
+            "available": true
+        })
+    }
+
+    #[test]
+    fn fetched_catalog_lists_account_slugs() {
+        before();
+
+        let mock = MockDigitalOcean::new()
+            .respond(
+                Method::GET,
+                "/regions",
+                StatusCode::OK,
+                json!({
+                    "regions": [region("nyc1"), region("atl2")],
+                    "links": {},
+                    "meta": { "total": 2 }
+                }),
+            )
+            .respond(
+                Method::GET,
+                "/sizes",
+                StatusCode::OK,
+                json!({
+                    "sizes": [size("s-1vcpu-1gb"), size("gpu-h100x1-80gb")],
+                    "links": {},
+                    "meta": { "total": 2 }
+                }),
+            );
+
+        let catalog = block_on(SlugCatalog::fetch(&mock)).unwrap();
+        info!("{:#?}", catalog);
+
+        assert!(catalog.region("atl2").is_ok());
+        assert!(catalog.region("sfo3").is_err());
+        assert!(catalog.size("gpu-h100x1-80gb").is_ok());
+        assert!(catalog.size("c-2").is_err());
+        assert_eq!(mock.received().len(), 2);
+    }
+}
@@ -1,6 +1,7 @@
 extern crate digitalocean;
 #[macro_use]
 extern crate log;
+#[macro_use]
 extern crate serde_json;
 extern crate url;
 extern crate url_serde;
@@ -9,7 +10,7 @@ mod utils;
 
 use serde_json::Value;
 
-use digitalocean::api::Snapshot;
+use digitalocean::api::{HasResponse, HasValue, Snapshot};
 use digitalocean::method::{Delete, Get, List};
 use digitalocean::request::Request;
 
@@ -81,3 +82,193 @@ fn delete_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+#[test]
+fn list_by_tag_leaves_query_untouched() {
+    before();
+
+    // Snapshots cannot be filtered by tag server side.
+    let correct_url = "https://api.digitalocean.com/v2/snapshots?resource_type=droplet";
+
+    let req: Request<List, Vec<Snapshot>> = Snapshot::droplets().tag("nightly");
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+}
+
+#[test]
+fn volume_snapshot_deserializes() {
+    before();
+
+    let response: <Snapshot as HasResponse>::Response = serde_json::from_value(json!({
+        "snapshot": {
+            "id": "8fa70202-873f-11e6-8b68-000f533176b1",
+            "name": "big-data-snapshot1475261774",
+            "regions": ["nyc1"],
+            "created_at": "2016-09-30T18:56:14Z",
+            "resource_id": "82a48a18-873f-11e6-96bf-000f53315a41",
+            "resource_type": "volume",
+            "min_disk_size": 10,
+            "size_gigabytes": 0.04,
+            "tags": ["aninterestingtag"]
+        }
+    }))
+    .unwrap();
+
+    let snapshot = response.value();
+    info!("{:#?}", snapshot);
+
+    assert_eq!(snapshot.resource_type(), "volume");
+    assert_eq!(*snapshot.min_disk_size(), 10);
+    assert_eq!(*snapshot.size_gigabytes(), 0.04);
+    assert_eq!(*snapshot.tags(), vec!["aninterestingtag"]);
+}
+
+#[cfg(feature = "testing")]
+mod mock {
+    use reqwest::{Method, StatusCode};
+    use serde_json::json;
+
+    use digitalocean::api::Snapshot;
+    use digitalocean::error::Error;
+    use digitalocean::request::Executable;
+    use digitalocean::testing::MockDigitalOcean;
+
+    use crate::utils::{before, block_on};
+
+    fn snapshot(id: &str, tags: Vec<&str>) -> serde_json::Value {
+        json!({
+            "id": id,
+            "name": format!("web-01-{id}"),
+            "regions": ["nyc3"],
+            "created_at": "2017-09-28T21:24:57Z",
+            "resource_id": "3164444",
+            "resource_type": "droplet",
+            "min_disk_size": 20,
+            "size_gigabytes": 0.47,
+            "tags": tags
+        })
+    }
+
+    #[test]
+    fn list_by_tag_keeps_tagged_snapshots() {
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::GET,
+            "/snapshots",
+            StatusCode::OK,
+            json!({
+                "snapshots": [
+                    snapshot("6372321", vec!["nightly"]),
+                    snapshot("6372322", vec![]),
+                    snapshot("6372323", vec!["web", "nightly"])
+                ],
+                "links": {},
+                "meta": { "total": 3 }
+            }),
+        );
+
+        let snapshots = block_on(Snapshot::list().tag("nightly").execute(&mock)).unwrap();
+        info!("{:#?}", snapshots);
+
+        let ids: Vec<&str> = snapshots.iter().map(|s| s.id().as_str()).collect();
+        assert_eq!(ids, vec!["6372321", "6372323"]);
+    }
+
+    #[test]
+    fn droplet_snapshot_is_fetched_as_image() {
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::GET,
+            "/images/6372321",
+            StatusCode::OK,
+            json!({
+                "image": {
+                    "id": 6372321,
+                    "name": "web-01-6372321",
+                    "type": "snapshot",
+                    "distribution": "Ubuntu",
+                    "slug": null,
+                    "public": false,
+                    "regions": ["nyc3"],
+                    "min_disk_size": 20,
+                    "size_gigabytes": 0.47,
+                    "created_at": "2017-09-28T21:24:57Z"
+                }
+            }),
+        );
+
+        let snapshot: Snapshot = serde_json::from_value(snapshot("6372321", vec![])).unwrap();
+        let image = block_on(snapshot.as_image(&mock)).unwrap();
+        info!("{:#?}", image);
+
+        assert_eq!(*image.id(), 6372321);
+        assert_eq!(mock.received()[0].url.path(), "/v2/images/6372321");
+    }
+
+    #[test]
+    fn volume_snapshot_is_not_an_image() {
+        before();
+
+        let mock = MockDigitalOcean::new();
+
+        let mut fixture = snapshot("fbe805e8-866b-11e6-96bf-000f53315a41", vec![]);
+        fixture["resource_type"] = json!("volume");
+        let snapshot: Snapshot = serde_json::from_value(fixture).unwrap();
+
+        match block_on(snapshot.as_image(&mock)) {
+            Err(Error::InvalidRequest(_)) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+        assert!(mock.received().is_empty());
+    }
+
+    #[test]
+    fn delete_many_reports_each_id() {
+        before();
+
+        let mock = MockDigitalOcean::new()
+            .respond(
+                Method::DELETE,
+                "/snapshots/6372321",
+                StatusCode::NO_CONTENT,
+                json!(null),
+            )
+            .respond(
+                Method::DELETE,
+                "/snapshots/6372322",
+                StatusCode::NOT_FOUND,
+                json!({
+                    "id": "not_found",
+                    "message": "The resource you were accessing could not be found."
+                }),
+            )
+            .respond(
+                Method::DELETE,
+                "/snapshots/fbe805e8-866b-11e6-96bf-000f53315a41",
+                StatusCode::NO_CONTENT,
+                json!(null),
+            );
+
+        let ids = vec![
+            String::from("6372321"),
+            String::from("6372322"),
+            String::from("fbe805e8-866b-11e6-96bf-000f53315a41"),
+        ];
+        let batch = block_on(Snapshot::delete_many(&mock, ids, 2));
+        info!("{:#?}", batch);
+
+        assert_eq!(
+            batch.succeeded,
+            vec!["6372321", "fbe805e8-866b-11e6-96bf-000f53315a41"]
+        );
+        assert_eq!(batch.failed.len(), 1);
+        assert_eq!(batch.failed[0].0, "6372322");
+
+        let received = mock.received();
+        assert_eq!(received.len(), 3);
+        assert!(received.iter().all(|r| r.method == Method::DELETE));
+    }
+}
@@ -0,0 +1,85 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+extern crate url;
+
+mod utils;
+
+use digitalocean::api::spaces_key_fields::{Grant, SpacesPermission};
+use digitalocean::api::SpacesKey;
+use digitalocean::method::Create;
+use digitalocean::request::Request;
+
+use crate::utils::before;
+
+#[test]
+fn create_with_grants_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/spaces/keys";
+    let grants = vec![
+        Grant {
+            bucket: "assets".to_string(),
+            permission: SpacesPermission::Read,
+        },
+        Grant {
+            bucket: "uploads".to_string(),
+            permission: SpacesPermission::ReadWrite,
+        },
+    ];
+
+    let req: Request<Create, SpacesKey> = SpacesKey::create("deploy", grants);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(
+        *req.body(),
+        json!({
+            "name": "deploy",
+            "grants": [
+                { "bucket": "assets", "permission": "read" },
+                { "bucket": "uploads", "permission": "readwrite" },
+            ],
+        })
+    );
+}
+
+#[test]
+fn create_with_full_access_produces_correct_request() {
+    before();
+
+    let req: Request<Create, SpacesKey> = SpacesKey::create(
+        "admin",
+        vec![Grant {
+            bucket: String::new(),
+            permission: SpacesPermission::FullAccess,
+        }],
+    );
+    info!("{:#?}", req);
+
+    assert_eq!(
+        req.body()["grants"],
+        json!([{ "bucket": "", "permission": "fullaccess" }])
+    );
+}
+
+#[test]
+fn key_deserializes() {
+    before();
+
+    let response = json!({
+        "name": "deploy",
+        "access_key": "DOACCESSKEYEXAMPLE",
+        "secret_key": "secretkeyexample",
+        "grants": [{ "bucket": "assets", "permission": "read" }],
+        "created_at": "2024-06-20T18:53:12Z"
+    });
+
+    let key: SpacesKey = serde_json::from_value(response).unwrap();
+    info!("{:#?}", key);
+
+    assert_eq!(key.secret_key().as_deref(), Some("secretkeyexample"));
+    assert_eq!(key.grants()[0].permission, SpacesPermission::Read);
+}
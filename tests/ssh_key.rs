@@ -10,7 +10,7 @@ mod utils;
 
 use serde_json::Value;
 
-use digitalocean::api::SshKey;
+use digitalocean::api::{HasResponse, SshKey};
 use digitalocean::method::{Create, Delete, Get, List, Update};
 use digitalocean::request::Request;
 
@@ -96,3 +96,141 @@ fn delete_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+#[test]
+fn list_response_round_trips() {
+    before();
+
+    let wire = json!({
+        "ssh_keys": [
+            {
+                "id": 512189,
+                "fingerprint": "3b:16:bf:e4:8b:00:8b:b8:59:8c:a9:d3:f0:19:45:fa",
+                "public_key": "ssh-rsa AAAA example",
+                "name": "My SSH Public Key"
+            }
+        ],
+        "links": {
+            "pages": {
+                "first": "https://api.digitalocean.com/v2/account/keys?page=1&per_page=1",
+                "prev": "https://api.digitalocean.com/v2/account/keys?page=1&per_page=1",
+                "next": "https://api.digitalocean.com/v2/account/keys?page=3&per_page=1",
+                "last": "https://api.digitalocean.com/v2/account/keys?page=3&per_page=1"
+            }
+        },
+        "meta": { "total": 3 }
+    });
+
+    let response: <Vec<SshKey> as HasResponse>::Response =
+        serde_json::from_value(wire.clone()).unwrap();
+    info!("{:#?}", response);
+
+    assert_eq!(*response.meta().total(), 3);
+    let pages = response.links().pages().as_ref().unwrap();
+    assert_eq!(
+        pages.next().as_ref().map(|url| url.as_str()),
+        Some("https://api.digitalocean.com/v2/account/keys?page=3&per_page=1")
+    );
+
+    assert_eq!(serde_json::to_value(&response).unwrap(), wire);
+
+    // The last page has no `next` and single page lists no `pages` at all,
+    // neither gains a `null` when serialized again.
+    let mut wire = wire;
+    wire["links"]["pages"]
+        .as_object_mut()
+        .unwrap()
+        .remove("next");
+    let response: <Vec<SshKey> as HasResponse>::Response =
+        serde_json::from_value(wire.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&response).unwrap(), wire);
+
+    wire["links"] = json!({});
+    let response: <Vec<SshKey> as HasResponse>::Response =
+        serde_json::from_value(wire.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&response).unwrap(), wire);
+}
+
+#[cfg(feature = "testing")]
+mod mock {
+    use reqwest::{Method, StatusCode};
+    use serde_json::json;
+
+    use digitalocean::api::SshKey;
+    use digitalocean::testing::MockDigitalOcean;
+
+    use crate::utils::{before, block_on};
+
+    fn keys_fixture() -> MockDigitalOcean {
+        let key = |id: usize, name: &str| {
+            json!({
+                "id": id,
+                "fingerprint": format!("3b:16:bf:e4:8b:00:8b:b8:59:8c:a9:d3:f0:19:45:{:02x}", id % 256),
+                "public_key": format!("ssh-rsa AAAAB3NzaC1yc2E {name}"),
+                "name": name
+            })
+        };
+
+        MockDigitalOcean::new()
+            .respond(
+                Method::GET,
+                "/account/keys",
+                StatusCode::OK,
+                json!({
+                    "ssh_keys": [key(512189, "laptop")],
+                    "links": {
+                        "pages": {
+                            "next": "https://api.digitalocean.com/v2/account/keys?page=2&per_page=1"
+                        }
+                    },
+                    "meta": { "total": 2 }
+                }),
+            )
+            .respond(
+                Method::GET,
+                "/account/keys",
+                StatusCode::OK,
+                json!({
+                    "ssh_keys": [key(512190, "deploy")],
+                    "links": {},
+                    "meta": { "total": 2 }
+                }),
+            )
+    }
+
+    #[test]
+    fn list_all_follows_every_page() {
+        before();
+
+        let mock = keys_fixture();
+        let keys = block_on(SshKey::list_all(&mock)).unwrap();
+        info!("{:#?}", keys);
+
+        let ids: Vec<usize> = keys.iter().map(|key| *key.id()).collect();
+        assert_eq!(ids, vec![512189, 512190]);
+        assert_eq!(mock.received().len(), 2);
+    }
+
+    #[test]
+    fn find_by_name_returns_match() {
+        before();
+
+        let mock = keys_fixture();
+        let key = block_on(SshKey::find_by_name(&mock, "deploy"))
+            .unwrap()
+            .unwrap();
+        info!("{:#?}", key);
+
+        assert_eq!(*key.id(), 512190);
+    }
+
+    #[test]
+    fn find_by_name_without_match_is_none() {
+        before();
+
+        let mock = keys_fixture();
+        let key = block_on(SshKey::find_by_name(&mock, "missing")).unwrap();
+
+        assert!(key.is_none());
+    }
+}
@@ -76,60 +76,163 @@ fn delete_produces_correct_request() {
     assert_eq!(*req.body(), Value::Null);
 }
 
-#[test]
-fn add_resources_produces_correct_request() {
-    before();
-
-    let tag = "test";
-    let correct_url = format!("https://api.digitalocean.com/v2/tags/{}/resources", tag);
-    let resources = vec![("123", "droplet"), ("456", "droplet")];
-
-    let req: Request<Create, ()> = Tag::get(tag).add_resources(resources.clone());
-    info!("{:#?}", req);
-
-    assert_eq!(req.url().as_str(), correct_url);
-    assert_eq!(
-        *req.body(),
-        json!({
-            "resources": [
-                {
-                    "resource_id": resources[0].0,
-                    "resource_type": resources[0].1,
-                },
-                {
-                    "resource_id": resources[1].0,
-                    "resource_type": resources[1].1,
-                }
-            ],
-        })
-    );
-}
-
-#[test]
-fn remove_resources_produces_correct_request() {
-    before();
-
-    let tag = "test";
-    let correct_url = format!("https://api.digitalocean.com/v2/tags/{}/resources", tag);
-    let resources = vec![("123", "droplet"), ("456", "droplet")];
-
-    let req: Request<Delete, ()> = Tag::get(tag).remove_resources(resources.clone());
-    info!("{:#?}", req);
-
-    assert_eq!(req.url().as_str(), correct_url);
-    assert_eq!(
-        *req.body(),
-        json!({
-            "resources": [
-                {
-                    "resource_id": resources[0].0,
-                    "resource_type": resources[0].1,
-                },
-                {
-                    "resource_id": resources[1].0,
-                    "resource_type": resources[1].1,
-                }
-            ],
-        })
-    );
+#[cfg(feature = "testing")]
+mod mock {
+    use reqwest::{Method, StatusCode};
+    use serde_json::{json, Value};
+
+    use digitalocean::api::{Tag, MAX_TAG_RESOURCES};
+    use digitalocean::error::Error;
+    use digitalocean::testing::MockDigitalOcean;
+
+    use crate::utils::{before, block_on};
+
+    fn droplets(count: usize) -> Vec<(String, String)> {
+        (0..count)
+            .map(|i| ((3164444 + i).to_string(), "droplet".to_string()))
+            .collect()
+    }
+
+    fn sent_resources(body: &[u8]) -> usize {
+        let body: Value = serde_json::from_slice(body).unwrap();
+        body["resources"].as_array().unwrap().len()
+    }
+
+    #[test]
+    fn add_resources_produces_correct_request() {
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::POST,
+            "/tags/test/resources",
+            StatusCode::NO_CONTENT,
+            Value::Null,
+        );
+
+        let resources = vec![("123", "droplet"), ("456", "droplet")];
+        assert!(block_on(Tag::get("test").add_resources(&mock, resources)).is_complete());
+
+        let received = mock.received();
+        info!("{:#?}", received);
+
+        assert_eq!(received.len(), 1);
+        assert_eq!(
+            received[0].url.as_str(),
+            "https://api.digitalocean.com/v2/tags/test/resources"
+        );
+        assert_eq!(
+            serde_json::from_slice::<Value>(&received[0].body).unwrap(),
+            json!({
+                "resources": [
+                    { "resource_id": "123", "resource_type": "droplet" },
+                    { "resource_id": "456", "resource_type": "droplet" }
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn add_resources_splits_requests() {
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::POST,
+            "/tags/web/resources",
+            StatusCode::NO_CONTENT,
+            Value::Null,
+        );
+
+        let resources = droplets(2 * MAX_TAG_RESOURCES + 1);
+        assert!(block_on(Tag::get("web").add_resources(&mock, resources)).is_complete());
+
+        let received = mock.received();
+        info!("{:#?}", received.len());
+
+        let sizes: Vec<usize> = received.iter().map(|r| sent_resources(&r.body)).collect();
+        assert_eq!(sizes, vec![MAX_TAG_RESOURCES, MAX_TAG_RESOURCES, 1]);
+    }
+
+    #[test]
+    fn remove_resources_splits_requests() {
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::DELETE,
+            "/tags/web/resources",
+            StatusCode::NO_CONTENT,
+            Value::Null,
+        );
+
+        let resources = droplets(MAX_TAG_RESOURCES + 1);
+        assert!(block_on(Tag::get("web").remove_resources(&mock, resources)).is_complete());
+
+        let received = mock.received();
+        let sizes: Vec<usize> = received.iter().map(|r| sent_resources(&r.body)).collect();
+        assert_eq!(sizes, vec![MAX_TAG_RESOURCES, 1]);
+        assert!(received.iter().all(|r| r.method == Method::DELETE));
+    }
+
+    #[test]
+    fn failed_chunks_are_reported_and_the_rest_sent() {
+        before();
+
+        let mock = MockDigitalOcean::new()
+            .respond(
+                Method::POST,
+                "/tags/web/resources",
+                StatusCode::NO_CONTENT,
+                Value::Null,
+            )
+            .respond(
+                Method::POST,
+                "/tags/web/resources",
+                StatusCode::UNPROCESSABLE_ENTITY,
+                json!({ "id": "unprocessable_entity", "message": "too many resources" }),
+            )
+            .respond(
+                Method::POST,
+                "/tags/web/resources",
+                StatusCode::NO_CONTENT,
+                Value::Null,
+            );
+
+        let resources = droplets(2 * MAX_TAG_RESOURCES + 1);
+        let result = block_on(Tag::get("web").add_resources(&mock, resources));
+        info!("{:#?}", result);
+
+        assert!(!result.is_complete());
+        assert_eq!(mock.received().len(), 3);
+        assert_eq!(result.succeeded.len(), MAX_TAG_RESOURCES + 1);
+
+        assert_eq!(result.failed.len(), 1);
+        let (failed, error) = &result.failed[0];
+        assert_eq!(failed.len(), MAX_TAG_RESOURCES);
+        assert_eq!(failed[0].0, (3164444 + MAX_TAG_RESOURCES).to_string());
+        assert!(matches!(error, Error::UnprocessableEntity { .. }));
+
+        // Only the failures are retried.
+        let mock = MockDigitalOcean::new().respond(
+            Method::POST,
+            "/tags/web/resources",
+            StatusCode::NO_CONTENT,
+            Value::Null,
+        );
+        let (failed, _) = result.failed.into_iter().next().unwrap();
+        let retried = block_on(Tag::get("web").add_resources(&mock, failed));
+
+        assert_eq!(retried.into_result().unwrap().len(), MAX_TAG_RESOURCES);
+        assert_eq!(mock.received().len(), 1);
+    }
+
+    #[test]
+    fn empty_resources_send_nothing() {
+        before();
+
+        let mock = MockDigitalOcean::new();
+        let result = block_on(Tag::get("web").add_resources(&mock, droplets(0)));
+        assert!(result.is_complete());
+        assert!(result.succeeded.is_empty());
+
+        assert!(mock.received().is_empty());
+    }
 }
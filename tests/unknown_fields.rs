@@ -0,0 +1,356 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+extern crate url;
+
+mod utils;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use digitalocean::api::{
+    Account, Action, Certificate, CustomImage, Domain, DomainRecord, Droplet, Firewall, FloatingIp,
+    HasResponse, Image, LoadBalancer, Region, Size, Snapshot, SshKey, Tag, Volume,
+};
+
+use crate::utils::before;
+
+/// Add a field the crate does not know about to every object in `value`,
+/// nested ones included, as DigitalOcean does when extending its responses.
+fn with_unknown_fields(value: Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut object: serde_json::Map<String, Value> = object
+                .into_iter()
+                .map(|(key, value)| (key, with_unknown_fields(value)))
+                .collect();
+            object.insert(
+                "field_added_later".to_string(),
+                json!({ "nested": [1, "two", null] }),
+            );
+            Value::Object(object)
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(with_unknown_fields).collect()),
+        value => value,
+    }
+}
+
+/// Parse `fixture` as `T`, as sent and with unknown fields added everywhere.
+fn assert_tolerates_unknown_fields<T: DeserializeOwned>(name: &str, fixture: Value) {
+    if let Err(e) = serde_json::from_value::<T>(fixture.clone()) {
+        panic!("{} fixture does not parse: {}", name, e);
+    }
+
+    let extended = with_unknown_fields(fixture);
+    info!("{:#?}", extended);
+
+    if let Err(e) = serde_json::from_value::<T>(extended) {
+        panic!("{} does not tolerate unknown fields: {}", name, e);
+    }
+}
+
+fn region() -> Value {
+    json!({
+        "name": "New York 3",
+        "slug": "nyc3",
+        "sizes": ["s-1vcpu-1gb"],
+        "features": ["backups", "ipv6"],
+        "available": true
+    })
+}
+
+fn image() -> Value {
+    json!({
+        "id": 63663980,
+        "name": "20.04 (LTS) x64",
+        "type": "snapshot",
+        "distribution": "Ubuntu",
+        "slug": "ubuntu-20-04-x64",
+        "public": true,
+        "regions": ["nyc3"],
+        "min_disk_size": 20,
+        "size_gigabytes": 2.36,
+        "created_at": "2020-05-15T05:47:50Z",
+        "status": "available"
+    })
+}
+
+fn size() -> Value {
+    json!({
+        "slug": "s-1vcpu-1gb",
+        "memory": 1024,
+        "vcpus": 1,
+        "disk": 25,
+        "transfer": 1.0,
+        "price_monthly": 5.0,
+        "price_hourly": 0.00743999984115362,
+        "regions": ["nyc3"],
+        "available": true
+    })
+}
+
+fn droplet() -> Value {
+    json!({
+        "id": 3164444,
+        "name": "example.com",
+        "memory": 1024,
+        "vcpus": 1,
+        "disk": 25,
+        "locked": false,
+        "status": "active",
+        "kernel": { "id": 7515, "name": "DigitalOcean GrubLoader v0.2", "version": "2016.03" },
+        "created_at": "2020-07-21T18:37:44Z",
+        "features": ["backups", "ipv6"],
+        "backup_ids": [53893572],
+        "next_backup_window": {
+            "start": "2019-12-04T00:00:00Z",
+            "end": "2019-12-04T23:00:00Z"
+        },
+        "snapshot_ids": [67512819],
+        "image": image(),
+        "volume_ids": [],
+        "size": size(),
+        "size_slug": "s-1vcpu-1gb",
+        "networks": {
+            "v4": [
+                {
+                    "ip_address": "192.241.165.154",
+                    "netmask": "255.255.255.0",
+                    "gateway": "192.241.165.1",
+                    "type": "public"
+                }
+            ],
+            "v6": [
+                {
+                    "ip_address": "2604:a880:0:1010::18a:a001",
+                    "netmask": 64,
+                    "gateway": "2604:a880:0:1010::1",
+                    "type": "public"
+                }
+            ]
+        },
+        "region": region(),
+        "tags": ["web"]
+    })
+}
+
+#[test]
+fn resources_tolerate_unknown_fields() {
+    before();
+
+    assert_tolerates_unknown_fields::<Account>(
+        "Account",
+        json!({
+            "droplet_limit": 25,
+            "floating_ip_limit": 5,
+            "email": "sammy@digitalocean.com",
+            "uuid": "b6fr89dbf6d9156cace5f3c78dc9851d957381ef",
+            "email_verified": true,
+            "status": "active",
+            "status_message": ""
+        }),
+    );
+
+    assert_tolerates_unknown_fields::<Action>(
+        "Action",
+        json!({
+            "id": 36804745,
+            "status": "completed",
+            "type": "enable_backups",
+            "started_at": "2014-11-14T16:30:56Z",
+            "completed_at": "2014-11-14T16:31:56Z",
+            "resource_id": 3164450,
+            "resource_type": "droplet",
+            "region_slug": "nyc3"
+        }),
+    );
+
+    assert_tolerates_unknown_fields::<Certificate>(
+        "Certificate",
+        json!({
+            "id": "892071a0-bb95-49bc-8021-3afd67a210bf",
+            "name": "web-cert-01",
+            "not_after": "2017-02-22T00:23:00Z",
+            "sha1_fingerprint": "dfcc9f57d86bf58e321c2c6c31c7a971be244ac7",
+            "created_at": "2017-02-08T16:02:37Z"
+        }),
+    );
+
+    assert_tolerates_unknown_fields::<CustomImage>(
+        "CustomImage",
+        json!({
+            "id": 38413969,
+            "name": "ubuntu-18.04-minimal",
+            "distribution": "Ubuntu",
+            "regions": [],
+            "created_at": "2018-09-20T19:28:00Z",
+            "type": "custom",
+            "tags": ["base-image"],
+            "description": "Cloud-optimized image w/ small footprint",
+            "status": "NEW"
+        }),
+    );
+
+    assert_tolerates_unknown_fields::<Domain>(
+        "Domain",
+        json!({ "name": "example.com", "ttl": 1800, "zone_file": null }),
+    );
+
+    assert_tolerates_unknown_fields::<DomainRecord>(
+        "DomainRecord",
+        json!({
+            "id": 28448429,
+            "type": "A",
+            "name": "www",
+            "data": "162.10.66.0",
+            "priority": null,
+            "port": null,
+            "ttl": 1800,
+            "weight": null,
+            "flags": null,
+            "tag": null
+        }),
+    );
+
+    assert_tolerates_unknown_fields::<Droplet>("Droplet", droplet());
+
+    assert_tolerates_unknown_fields::<Firewall>(
+        "Firewall",
+        json!({
+            "id": "bb4b2611-3d72-467b-8602-280330ecd65c",
+            "status": "succeeded",
+            "created_at": "2017-05-23T21:24:00Z",
+            "pending_changes": [
+                { "droplet_id": 8043964, "removing": false, "status": "waiting" }
+            ],
+            "name": "firewall",
+            "inbound_rules": [
+                { "protocol": "tcp", "ports": "22", "sources": { "addresses": ["18.0.0.0/8"] } }
+            ],
+            "outbound_rules": [
+                { "protocol": "icmp", "destinations": { "addresses": ["0.0.0.0/0"] } }
+            ],
+            "droplet_ids": [8043964],
+            "tags": []
+        }),
+    );
+
+    assert_tolerates_unknown_fields::<FloatingIp>(
+        "FloatingIp",
+        json!({ "ip": "45.55.96.47", "region": region(), "droplet": droplet() }),
+    );
+
+    assert_tolerates_unknown_fields::<Image>("Image", image());
+
+    assert_tolerates_unknown_fields::<LoadBalancer>(
+        "LoadBalancer",
+        json!({
+            "id": "4de7ac8b-495b-4884-9a69-1050c6793cd6",
+            "name": "example-lb-01",
+            "ip": "104.131.186.241",
+            "algorithm": "round_robin",
+            "status": "active",
+            "created_at": "2017-02-01T22:22:58Z",
+            "forwarding_rules": [
+                {
+                    "entry_protocol": "http",
+                    "entry_port": 80,
+                    "target_protocol": "http",
+                    "target_port": 80,
+                    "certificate_id": "",
+                    "tls_passthrough": false
+                }
+            ],
+            "health_check": {
+                "protocol": "http",
+                "port": 80,
+                "path": "/",
+                "check_interval_seconds": 10,
+                "response_timeout_seconds": 5,
+                "healthy_threshold": 5,
+                "unhealthy_threshold": 3
+            },
+            "sticky_sessions": { "type": "none" },
+            "region": region(),
+            "tag": "",
+            "droplet_ids": [3164444],
+            "redirect_http_to_https": false
+        }),
+    );
+
+    assert_tolerates_unknown_fields::<Region>("Region", region());
+
+    assert_tolerates_unknown_fields::<Size>("Size", size());
+
+    assert_tolerates_unknown_fields::<Snapshot>(
+        "Snapshot",
+        json!({
+            "id": "6372321",
+            "name": "web-01-1595954862243",
+            "created_at": "2020-07-28T16:47:44Z",
+            "regions": ["nyc3"],
+            "resource_id": "200776916",
+            "resource_type": "droplet",
+            "min_disk_size": 25,
+            "size_gigabytes": 2.34,
+            "tags": ["web"]
+        }),
+    );
+
+    assert_tolerates_unknown_fields::<SshKey>(
+        "SshKey",
+        json!({
+            "id": 512189,
+            "fingerprint": "3b:16:bf:e4:8b:00:8b:b8:59:8c:a9:d3:f0:19:45:fa",
+            "public_key": "ssh-rsa AAAA example",
+            "name": "My SSH Public Key"
+        }),
+    );
+
+    assert_tolerates_unknown_fields::<Tag>(
+        "Tag",
+        json!({
+            "name": "extra-awesome",
+            "resources": { "count": 0, "droplets": { "count": 0 } }
+        }),
+    );
+
+    assert_tolerates_unknown_fields::<Volume>(
+        "Volume",
+        json!({
+            "id": "6fc4c277-ea5c-448a-93cd-dd496cfef71f",
+            "region": region(),
+            "droplet_ids": [],
+            "name": "example",
+            "description": "",
+            "size_gigabytes": 10,
+            "created_at": "2016-03-02T17:00:49Z"
+        }),
+    );
+}
+
+#[test]
+fn responses_tolerate_unknown_fields() {
+    before();
+
+    assert_tolerates_unknown_fields::<<Droplet as HasResponse>::Response>(
+        "DropletResponse",
+        json!({ "droplet": droplet(), "links": { "actions": [] } }),
+    );
+
+    assert_tolerates_unknown_fields::<<Vec<Droplet> as HasResponse>::Response>(
+        "DropletListResponse",
+        json!({
+            "droplets": [droplet()],
+            "links": {
+                "pages": {
+                    "next": "https://api.digitalocean.com/v2/droplets?page=2&per_page=1",
+                    "last": "https://api.digitalocean.com/v2/droplets?page=3&per_page=1"
+                }
+            },
+            "meta": { "total": 3 }
+        }),
+    );
+}
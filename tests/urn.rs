@@ -0,0 +1,249 @@
+extern crate digitalocean;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+extern crate url;
+
+mod utils;
+
+use std::net::{IpAddr, Ipv4Addr};
+
+use serde_json::Value;
+
+use digitalocean::api::{Domain, FloatingIp, HasUrn, Image, LoadBalancer, Urn, Volume};
+use digitalocean::error::Error;
+
+use crate::utils::before;
+
+#[test]
+fn parses_supported_urns() {
+    before();
+
+    let cases = vec![
+        ("do:droplet:4126873", Urn::Droplet(4126873)),
+        (
+            "do:volume:6fc4c277-ea5c-448a-93cd-dd496cfef71f",
+            Urn::Volume("6fc4c277-ea5c-448a-93cd-dd496cfef71f".to_string()),
+        ),
+        (
+            "do:floatingip:192.168.99.100",
+            Urn::FloatingIp(IpAddr::V4(Ipv4Addr::new(192, 168, 99, 100))),
+        ),
+        (
+            "do:loadbalancer:4de7ac8b-495b-4884-9a69-1050c6793cd6",
+            Urn::LoadBalancer("4de7ac8b-495b-4884-9a69-1050c6793cd6".to_string()),
+        ),
+        (
+            "do:domain:example.com",
+            Urn::Domain("example.com".to_string()),
+        ),
+        ("do:image:7555620", Urn::Image(7555620)),
+    ];
+
+    for (raw, expected) in cases {
+        let urn: Urn = raw.parse().unwrap();
+        info!("{:?}", urn);
+
+        assert_eq!(urn, expected);
+        assert_eq!(urn.to_string(), raw);
+    }
+}
+
+#[test]
+fn parses_ipv6_floating_ip() {
+    before();
+
+    let urn: Urn = "do:floatingip:2604:a880:800:10::1".parse().unwrap();
+
+    assert_eq!(urn, Urn::FloatingIp("2604:a880:800:10::1".parse().unwrap()));
+}
+
+#[test]
+fn rejects_invalid_urns() {
+    before();
+
+    let invalid = vec![
+        "",
+        "droplet:4126873",
+        "aws:droplet:4126873",
+        "do:droplet:",
+        "do:droplet:web-1",
+        "do:floatingip:not-an-ip",
+        "do:image:ubuntu-24-04-x64",
+        "do:kubernetes:bd5f5959-5e1e-4205-a714-a914373942af",
+    ];
+
+    for raw in invalid {
+        match raw.parse::<Urn>() {
+            Err(Error::InvalidRequest(reason)) => info!("{}", reason),
+            other => panic!("Unexpected result for {:?}: {:?}", raw, other),
+        }
+    }
+}
+
+fn region() -> Value {
+    json!({
+        "name": "New York 3",
+        "slug": "nyc3",
+        "sizes": ["s-1vcpu-1gb"],
+        "features": ["backups", "ipv6"],
+        "available": true
+    })
+}
+
+#[test]
+fn volume_urn() {
+    before();
+
+    let volume: Volume = serde_json::from_value(json!({
+        "id": "6fc4c277-ea5c-448a-93cd-dd496cfef71f",
+        "region": region(),
+        "droplet_ids": [],
+        "name": "example",
+        "description": "",
+        "size_gigabytes": 10,
+        "created_at": "2016-03-02T17:00:49Z"
+    }))
+    .unwrap();
+
+    assert_eq!(
+        volume.urn(),
+        "do:volume:6fc4c277-ea5c-448a-93cd-dd496cfef71f"
+    );
+}
+
+#[test]
+fn floating_ip_urn() {
+    before();
+
+    let floating_ip: FloatingIp = serde_json::from_value(json!({
+        "ip": "45.55.96.47",
+        "region": region(),
+        "droplet": null
+    }))
+    .unwrap();
+
+    assert_eq!(floating_ip.urn(), "do:floatingip:45.55.96.47");
+}
+
+#[test]
+fn load_balancer_urn() {
+    before();
+
+    let load_balancer: LoadBalancer = serde_json::from_value(json!({
+        "id": "4de7ac8b-495b-4884-9a69-1050c6793cd6",
+        "name": "example-lb-01",
+        "ip": "104.131.186.241",
+        "algorithm": "round_robin",
+        "status": "active",
+        "created_at": "2017-02-01T22:22:58Z",
+        "forwarding_rules": [],
+        "health_check": {
+            "protocol": "http",
+            "port": 80,
+            "path": "/",
+            "check_interval_seconds": 10,
+            "response_timeout_seconds": 5,
+            "healthy_threshold": 5,
+            "unhealthy_threshold": 3
+        },
+        "sticky_sessions": { "type": "none" },
+        "region": region(),
+        "tag": "",
+        "droplet_ids": [],
+        "redirect_http_to_https": false
+    }))
+    .unwrap();
+
+    assert_eq!(
+        load_balancer.urn(),
+        "do:loadbalancer:4de7ac8b-495b-4884-9a69-1050c6793cd6"
+    );
+}
+
+#[test]
+fn domain_urn() {
+    before();
+
+    let domain: Domain = serde_json::from_value(json!({
+        "name": "example.com",
+        "ttl": 1800,
+        "zone_file": null
+    }))
+    .unwrap();
+
+    assert_eq!(domain.urn(), "do:domain:example.com");
+}
+
+#[test]
+fn image_urn() {
+    before();
+
+    let image: Image = serde_json::from_value(json!({
+        "id": 7555620,
+        "name": "Nifty New Snapshot",
+        "distribution": "Ubuntu",
+        "slug": null,
+        "public": false,
+        "regions": ["nyc2", "nyc3"],
+        "created_at": "2014-11-04T22:23:02Z",
+        "type": "snapshot",
+        "min_disk_size": 20,
+        "size_gigabytes": 2.34,
+        "status": "available"
+    }))
+    .unwrap();
+
+    assert_eq!(image.urn(), "do:image:7555620");
+}
+
+#[cfg(feature = "testing")]
+mod mock {
+    use reqwest::{Method, StatusCode};
+    use serde_json::json;
+
+    use digitalocean::api::{ResolvedResource, Urn};
+    use digitalocean::testing::MockDigitalOcean;
+
+    use crate::utils::{before, block_on};
+
+    #[test]
+    fn fetch_dispatches_on_resource_type() {
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::GET,
+            "/domains/example.com",
+            StatusCode::OK,
+            json!({
+                "domain": {
+                    "name": "example.com",
+                    "ttl": 1800,
+                    "zone_file": null
+                }
+            }),
+        );
+
+        let urn: Urn = "do:domain:example.com".parse().unwrap();
+        let resource = block_on(urn.fetch(&mock)).unwrap();
+        info!("{:#?}", resource);
+
+        match resource {
+            ResolvedResource::Domain(domain) => assert_eq!(domain.name(), "example.com"),
+            other => panic!("Unexpected resource: {:?}", other),
+        }
+        assert_eq!(mock.received()[0].url.path(), "/v2/domains/example.com");
+    }
+
+    #[test]
+    fn fetch_of_missing_resource_is_not_found() {
+        before();
+
+        let mock = MockDigitalOcean::new();
+
+        let urn: Urn = "do:droplet:4126873".parse().unwrap();
+        assert!(block_on(urn.fetch(&mock)).is_err());
+        assert_eq!(mock.received()[0].url.path(), "/v2/droplets/4126873");
+    }
+}
@@ -1,8 +1,20 @@
 extern crate dotenv;
 extern crate env_logger;
+extern crate tokio;
+
+use std::future::Future;
 
 pub fn before() {
     // Setup for tests
     dotenv::dotenv().ok();
     env_logger::try_init().ok();
 }
+
+#[allow(dead_code)]
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("Could not build a runtime.")
+        .block_on(future)
+}
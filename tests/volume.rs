@@ -10,7 +10,9 @@ mod utils;
 
 use serde_json::Value;
 
+use digitalocean::api::volume_fields::FsType;
 use digitalocean::api::{Snapshot, Volume};
+use digitalocean::error::Error;
 use digitalocean::method::{Create, Delete, Get, List};
 use digitalocean::request::Request;
 
@@ -39,6 +41,26 @@ fn list_produces_correct_request() {
     assert_eq!(*req.body(), Value::Null);
 }
 
+#[test]
+fn list_by_name_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/volumes?region=nyc1&name=example";
+
+    let req: Request<List, Vec<Volume>> = Volume::list().region("nyc1").name("example");
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+
+    // Client side filters leave the url untouched.
+    let req: Request<List, Vec<Volume>> = Volume::list().region("nyc1").name_contains("exa");
+    assert_eq!(
+        req.url().as_str(),
+        "https://api.digitalocean.com/v2/volumes?region=nyc1"
+    );
+}
+
 #[test]
 fn create_produces_correct_request() {
     before();
@@ -60,6 +82,124 @@ fn create_produces_correct_request() {
     );
 }
 
+#[test]
+fn create_validates_correct_request() {
+    before();
+
+    let req: Request<Create, Volume> = Volume::create("bear", 123).region("tor1");
+    assert!(req.validate().is_ok());
+
+    let req: Request<Create, Volume> =
+        Volume::create("bear", 123).snapshot_id("b0798135-fb76-11e7-9a5f-0242ac116304");
+    assert!(req.validate().is_ok());
+}
+
+#[test]
+fn create_with_region_and_snapshot_is_invalid() {
+    before();
+
+    let req: Request<Create, Volume> = Volume::create("bear", 123)
+        .region("tor1")
+        .snapshot_id("b0798135-fb76-11e7-9a5f-0242ac116304");
+    info!("{:#?}", req);
+
+    match req.validate() {
+        Err(Error::InvalidRequest(_)) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn create_without_region_or_snapshot_is_invalid() {
+    before();
+
+    let req: Request<Create, Volume> = Volume::create("bear", 123);
+    info!("{:#?}", req);
+
+    match req.validate() {
+        Err(Error::InvalidRequest(_)) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn create_with_filesystem_produces_correct_request() {
+    before();
+
+    let req: Request<Create, Volume> = Volume::create("bear", 123)
+        .region("tor1")
+        .filesystem_type(FsType::Ext4)
+        .filesystem_label("bear-data");
+    info!("{:#?}", req);
+
+    assert_eq!(
+        *req.body(),
+        json!({
+            "name": "bear",
+            "size_gigabytes": 123,
+            "region": "tor1",
+            "filesystem_type": "ext4",
+            "filesystem_label": "bear-data",
+        })
+    );
+    assert!(req.validate().is_ok());
+
+    let req: Request<Create, Volume> = Volume::create("bear", 123)
+        .region("tor1")
+        .filesystem_type(FsType::Xfs);
+    info!("{:#?}", req);
+
+    assert_eq!(req.body()["filesystem_type"], json!("xfs"));
+    assert!(req.validate().is_ok());
+}
+
+#[test]
+fn create_with_long_filesystem_label_is_invalid() {
+    before();
+
+    // 16 characters fits ext4 but not xfs.
+    let label = "sixteen-chars-ok";
+
+    let req: Request<Create, Volume> = Volume::create("bear", 123)
+        .region("tor1")
+        .filesystem_type(FsType::Ext4)
+        .filesystem_label(label);
+    assert!(req.validate().is_ok());
+
+    let req: Request<Create, Volume> = Volume::create("bear", 123)
+        .region("tor1")
+        .filesystem_type(FsType::Ext4)
+        .filesystem_label("seventeen-chars!!");
+    match req.validate() {
+        Err(Error::InvalidRequest(_)) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+
+    let req: Request<Create, Volume> = Volume::create("bear", 123)
+        .region("tor1")
+        .filesystem_type(FsType::Xfs)
+        .filesystem_label(label);
+    match req.validate() {
+        Err(Error::InvalidRequest(_)) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn create_with_filesystem_label_but_no_type_is_invalid() {
+    before();
+
+    let req: Request<Create, Volume> = Volume::create("bear", 123)
+        .region("tor1")
+        .filesystem_label("bear-data");
+    info!("{:#?}", req);
+
+    match req.validate() {
+        Err(Error::InvalidRequest(_)) => {}
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
 #[test]
 fn get_produces_correct_request() {
     before();
@@ -85,7 +225,7 @@ fn get_by_name_produces_correct_request() {
         name, region
     );
 
-    let req: Request<Get, Volume> = Volume::get_by_name(name, region);
+    let req: Request<List, Vec<Volume>> = Volume::get_by_name(name, region);
     info!("{:#?}", req);
 
     assert_eq!(req.url().as_str(), correct_url);
@@ -158,3 +298,202 @@ fn snapshot_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), json!({ "name": snapshot_name }));
 }
+
+#[test]
+fn snapshot_with_tags_produces_correct_request() {
+    before();
+
+    let tags = vec![String::from("aninterestingtag")];
+
+    let req: Request<Create, Snapshot> = Volume::get("123").snapshot("test").tags(tags.clone());
+    info!("{:#?}", req);
+
+    assert_eq!(*req.body(), json!({ "name": "test", "tags": tags }));
+}
+
+#[cfg(feature = "testing")]
+mod mock {
+    use reqwest::{Method, StatusCode};
+    use serde_json::json;
+
+    use digitalocean::api::Volume;
+    use digitalocean::request::Executable;
+    use digitalocean::testing::MockDigitalOcean;
+
+    use crate::utils::{before, block_on};
+
+    fn volume(name: &str) -> serde_json::Value {
+        json!({
+            "id": format!("{name}-7724db7c-e098-11e5-b522-000f53304e51"),
+            "region": {
+                "name": "New York 1",
+                "slug": "nyc1",
+                "sizes": ["s-1vcpu-1gb"],
+                "features": ["private_networking"],
+                "available": true
+            },
+            "droplet_ids": [],
+            "name": name,
+            "description": "Block store for examples",
+            "size_gigabytes": 10,
+            "created_at": "2016-03-02T17:00:49Z"
+        })
+    }
+
+    #[test]
+    fn get_by_name_without_match_is_empty() {
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::GET,
+            "/volumes",
+            StatusCode::OK,
+            json!({
+                "volumes": [],
+                "links": {},
+                "meta": { "total": 0 }
+            }),
+        );
+
+        let volumes = block_on(Volume::get_by_name("missing", "nyc1").execute(&mock)).unwrap();
+        info!("{:#?}", volumes);
+
+        assert!(volumes.is_empty());
+        assert_eq!(
+            mock.received()[0].url.query(),
+            Some("name=missing&region=nyc1&per_page=200")
+        );
+    }
+
+    #[test]
+    fn get_by_name_with_match_is_listed() {
+        before();
+
+        let mock = MockDigitalOcean::new().respond(
+            Method::GET,
+            "/volumes",
+            StatusCode::OK,
+            json!({
+                "volumes": [volume("example")],
+                "links": {},
+                "meta": { "total": 1 }
+            }),
+        );
+
+        let volumes = block_on(Volume::get_by_name("example", "nyc1").execute(&mock)).unwrap();
+        info!("{:#?}", volumes);
+
+        assert_eq!(volumes.len(), 1);
+        assert_eq!(volumes[0].name(), "example");
+    }
+
+    fn paged() -> MockDigitalOcean {
+        MockDigitalOcean::new()
+            .respond(
+                Method::GET,
+                "/volumes",
+                StatusCode::OK,
+                json!({
+                    "volumes": [volume("example-data")],
+                    "links": {
+                        "pages": {
+                            "next": "https://api.digitalocean.com/v2/volumes?region=nyc1&page=2&per_page=1"
+                        }
+                    },
+                    "meta": { "total": 2 }
+                }),
+            )
+            .respond(
+                Method::GET,
+                "/volumes",
+                StatusCode::OK,
+                json!({
+                    "volumes": [volume("logs")],
+                    "links": {},
+                    "meta": { "total": 2 }
+                }),
+            )
+    }
+
+    #[test]
+    fn region_filter_is_kept_across_pages() {
+        before();
+
+        let mock = paged();
+        let volumes = block_on(Volume::list().region("nyc1").execute(&mock)).unwrap();
+        info!("{:#?}", volumes);
+
+        let received = mock.received();
+        assert_eq!(volumes.len(), 2);
+        assert_eq!(received[0].url.query(), Some("region=nyc1&per_page=200"));
+        assert_eq!(
+            received[1].url.query(),
+            Some("region=nyc1&page=2&per_page=1")
+        );
+    }
+
+    #[test]
+    fn name_contains_filters_every_page() {
+        before();
+
+        let mock = paged();
+        let volumes = block_on(
+            Volume::list()
+                .region("nyc1")
+                .name_contains("data")
+                .execute(&mock),
+        )
+        .unwrap();
+        info!("{:#?}", volumes);
+
+        assert_eq!(volumes.len(), 1);
+        assert_eq!(volumes[0].name(), "example-data");
+        assert_eq!(mock.received().len(), 2);
+    }
+
+    #[test]
+    fn name_prefix_filters_after_pagination() {
+        before();
+
+        let mock = MockDigitalOcean::new()
+            .respond(
+                Method::GET,
+                "/volumes",
+                StatusCode::OK,
+                json!({
+                    "volumes": [volume("ci-1"), volume("prod-ci"), volume("ci-2")],
+                    "links": {
+                        "pages": {
+                            "next": "https://api.digitalocean.com/v2/volumes?region=nyc1&page=2&per_page=3"
+                        }
+                    },
+                    "meta": { "total": 5 }
+                }),
+            )
+            .respond(
+                Method::GET,
+                "/volumes",
+                StatusCode::OK,
+                json!({
+                    "volumes": [volume("logs"), volume("ci-3")],
+                    "links": {},
+                    "meta": { "total": 5 }
+                }),
+            );
+        let volumes = block_on(
+            Volume::list()
+                .region("nyc1")
+                .name_prefix("ci-")
+                .execute(&mock),
+        )
+        .unwrap();
+        info!("{:#?}", volumes);
+
+        let names: Vec<&str> = volumes.iter().map(|v| v.name().as_str()).collect();
+        assert_eq!(names, ["ci-1", "ci-2", "ci-3"]);
+
+        let received = mock.received();
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0].url.query(), Some("region=nyc1&per_page=200"));
+    }
+}
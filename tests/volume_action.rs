@@ -165,3 +165,76 @@ fn get_produces_correct_request() {
     assert_eq!(req.url().as_str(), correct_url);
     assert_eq!(*req.body(), Value::Null);
 }
+
+#[test]
+fn action_history_get_produces_correct_request() {
+    before();
+
+    let correct_url = "https://api.digitalocean.com/v2/volumes/123/actions/456";
+
+    let req: Request<Get, Action> = Volume::get("123").actions().get(456);
+    info!("{:#?}", req);
+
+    assert_eq!(req.url().as_str(), correct_url);
+    assert_eq!(*req.body(), Value::Null);
+}
+
+#[cfg(feature = "testing")]
+mod mock {
+    use reqwest::{Method, StatusCode};
+    use serde_json::json;
+
+    use digitalocean::api::Volume;
+    use digitalocean::request::Executable;
+    use digitalocean::testing::MockDigitalOcean;
+
+    use crate::utils::{before, block_on};
+
+    fn action(id: usize, kind: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "status": "completed",
+            "type": kind,
+            "started_at": "2020-11-21T21:51:09Z",
+            "completed_at": "2020-11-21T21:51:09Z",
+            "resource_id": 0,
+            "resource_type": "volume",
+            "region_slug": "nyc1"
+        })
+    }
+
+    #[test]
+    fn action_history_is_listed() {
+        before();
+
+        let mock = MockDigitalOcean::new()
+            .respond(
+                Method::GET,
+                "/volumes/7724db7c-e098-11e5-b522-000f53304e51/actions",
+                StatusCode::OK,
+                json!({
+                    "actions": [action(72531856, "attach"), action(72531857, "resize")],
+                    "links": {},
+                    "meta": { "total": 2 }
+                }),
+            )
+            .respond(
+                Method::GET,
+                "/volumes/7724db7c-e098-11e5-b522-000f53304e51/actions/72531857",
+                StatusCode::OK,
+                json!({ "action": action(72531857, "resize") }),
+            );
+
+        let volume = Volume::get("7724db7c-e098-11e5-b522-000f53304e51");
+
+        let actions = block_on(volume.clone().actions().execute(&mock)).unwrap();
+        info!("{:#?}", actions);
+
+        let kinds: Vec<&str> = actions.iter().map(|a| a.kind().as_str()).collect();
+        assert_eq!(kinds, vec!["attach", "resize"]);
+
+        let action = block_on(volume.actions().get(72531857).execute(&mock)).unwrap();
+        assert_eq!(action.kind(), "resize");
+        assert_eq!(action.status(), "completed");
+    }
+}